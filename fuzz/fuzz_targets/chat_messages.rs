@@ -0,0 +1,14 @@
+#![no_main]
+
+// Messages posted in a chat room mid-game, and Slack slash command forms.
+use farkle::chat::ChatRooms;
+use farkle::game::Player;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let mut rooms = ChatRooms::new();
+    rooms.start("room", vec![Player::new("@ann"), Player::new("@bob")], 1);
+    rooms.handle("room", "@ann", text);
+    rooms.handle("room", "@ann", &format!("!farkle {}", text));
+    rooms.handle_slash_command(text);
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+// Everything typed at the keep and bank prompts, and on the command line.
+use farkle::parse::{parse_bank_answer, parse_dice, parse_keep, parse_keep_action, parse_player, positions_of};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let _ = parse_keep_action(text);
+    let _ = parse_bank_answer(text);
+    let _ = parse_keep(text);
+    let _ = parse_player(text);
+    if let Ok(dice) = parse_dice(text) {
+        let _ = positions_of(&[1, 2, 3, 4, 5, 6], &dice);
+        let _ = farkle::scoring::scoring_subsets(&dice);
+    }
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+// Commitments and secrets sent by the other peer in a fair roll.
+use farkle::fair::FairRoll;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|messages: (&str, &str)| {
+    let (commitment, secret) = messages;
+    let mut roll = FairRoll::with_secret(1, [7; 32]);
+    roll.receive_commitment(commitment);
+    let _ = roll.reveal();
+    let _ = roll.finish(secret, 6);
+});
@@ -0,0 +1,28 @@
+#![no_main]
+
+// Files people share or edit by hand: audit logs, recorded games,
+// correspondence games and scoresheets.
+use farkle::audit::AuditLog;
+use farkle::correspondence::Correspondence;
+use farkle::game::{Player, WinCondition};
+use farkle::notation::GameRecord;
+use farkle::scoresheet::Scoresheet;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    if let Ok(log) = AuditLog::parse(text) {
+        let _ = log.verify();
+    }
+    if let Ok(record) = GameRecord::parse(text) {
+        let _ = record.game();
+        assert_eq!(Ok(record.clone()), GameRecord::parse(&record.to_string()));
+    }
+    if let Ok(mut game) = Correspondence::parse(text) {
+        game.expire(u64::MAX);
+        let _ = game.game();
+    }
+    if let Ok(sheet) = Scoresheet::parse(text) {
+        let players = sheet.players().iter().map(|name| Player::new(name)).collect();
+        let _ = sheet.game(players, WinCondition::TargetScore);
+    }
+});
@@ -0,0 +1,280 @@
+//! Audit logs: the seed and every roll of a game, hash chained so the
+//! rolls can be checked later.
+//!
+//! Each roll's hash covers the previous hash and the roll, so changing,
+//! adding or dropping a roll breaks the chain from there on. With a seeded
+//! rng anyone can roll the same dice again and compare.
+
+use crate::config::RngBackend;
+use crate::error::FarkleError;
+use crate::event::Event;
+use crate::game::GameSnapshot;
+use crate::notation::GameRecord;
+use crate::sha256::{sha256, to_hex};
+use crate::turn::{Decider, PlayerAction, TurnSnapshot};
+use crate::{roll_dice, TOTAL_DICE};
+use std::fmt;
+
+/// A roll and the hash chaining it to the rolls before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditedRoll {
+    pub dice: Vec<i16>,
+    pub hash: String,
+}
+
+/// The rng and rolls of a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditLog {
+    pub rng: RngBackend,
+    pub rolls: Vec<AuditedRoll>,
+}
+
+/// Returns the hash starting the chain for a game rolled with rng.
+fn first_hash(rng: &RngBackend) -> String {
+    let start = match rng {
+        RngBackend::Seeded(seed) => format!("farkle-audit {}", seed),
+        rng => format!("farkle-audit {}", rng),
+    };
+    to_hex(&sha256(start.as_bytes()))
+}
+
+/// Returns the hash of dice rolled after the roll hashed to previous.
+fn next_hash(previous: &str, dice: &[i16]) -> String {
+    to_hex(&sha256(format!("{} {}", previous, join_dice(dice)).as_bytes()))
+}
+
+/// Returns dice separated by commas.
+fn join_dice(dice: &[i16]) -> String {
+    dice.iter().map(|die| die.to_string()).collect::<Vec<String>>().join(",")
+}
+
+impl AuditLog {
+    /// Returns an empty log for a game rolled with rng.
+    pub fn new(rng: RngBackend) -> AuditLog {
+        AuditLog { rng, rolls: Vec::new() }
+    }
+
+    /// Returns the hash of the latest roll, or of the rng before any rolls.
+    pub fn last_hash(&self) -> String {
+        match self.rolls.last() {
+            Some(roll) => roll.hash.clone(),
+            None => first_hash(&self.rng),
+        }
+    }
+
+    /// Add a roll to the log.
+    pub fn record(&mut self, dice: &[i16]) {
+        let hash = next_hash(&self.last_hash(), dice);
+        self.rolls.push(AuditedRoll { dice: dice.to_vec(), hash });
+    }
+
+    /// Parse a log written with `to_string`.
+    pub fn parse(text: &str) -> Result<AuditLog, FarkleError> {
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+        let first = lines.next().unwrap_or_default();
+        let rng = if let Some(seed) = first.strip_prefix("farkle-audit seed ") {
+            seed.trim().parse().map(RngBackend::Seeded).ok()
+        } else {
+            first.strip_prefix("farkle-audit rng ").and_then(|rng| RngBackend::parse(rng).ok())
+        };
+        let rng = rng.ok_or_else(|| {
+            let message = "The first line should be 'farkle-audit seed <number>' or 'farkle-audit rng <rng>'.";
+            FarkleError::SaveCorrupt(message.to_string())
+        })?;
+        let mut log = AuditLog::new(rng);
+        for (i, line) in lines.enumerate() {
+            let bad_line = || {
+                FarkleError::SaveCorrupt(format!("Line {} should be dice then a hash, like '1,5,3 ab12...'.", i + 2))
+            };
+            let (dice, hash) = line.trim().split_once(' ').ok_or_else(bad_line)?;
+            let dice: Result<Vec<i16>, _> = dice.split(',').map(str::parse).collect();
+            let dice = dice.map_err(|_| bad_line())?;
+            log.rolls.push(AuditedRoll { dice, hash: hash.trim().to_string() });
+        }
+        Ok(log)
+    }
+
+    /// Check the hash chain, and that the rolls are the ones the rng gives
+    /// if it always gives the same ones.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::audit::AuditLog;
+    /// use farkle::config::RngBackend;
+    /// let mut rng = farkle::seeded_rng(7);
+    /// let mut log = AuditLog::new(RngBackend::Seeded(7));
+    /// log.record(&farkle::roll_dice(6, &mut rng));
+    /// log.record(&farkle::roll_dice(2, &mut rng));
+    /// assert_eq!(Ok(()), log.verify());
+    ///
+    /// let text = log.to_string();
+    /// assert_eq!(Ok(log.clone()), AuditLog::parse(&text));
+    ///
+    /// let mut changed = log.clone();
+    /// changed.rolls[1].dice = vec![1, 1];
+    /// assert!(changed.verify().is_err());
+    ///
+    /// let mut dropped = log.clone();
+    /// dropped.rolls.remove(0);
+    /// assert!(dropped.verify().is_err());
+    ///
+    /// // Only the chain can be checked for the OS's dice.
+    /// let mut log = AuditLog::new(RngBackend::Os);
+    /// log.record(&[3, 3, 6, 1, 2, 5]);
+    /// assert_eq!(Ok(()), AuditLog::parse(&log.to_string()).unwrap().verify());
+    /// ```
+    pub fn verify(&self) -> Result<(), FarkleError> {
+        let mut rng = if self.rng.is_reproducible() { Some(self.rng.rng()?) } else { None };
+        let mut previous = first_hash(&self.rng);
+        for (i, roll) in self.rolls.iter().enumerate() {
+            if next_hash(&previous, &roll.dice) != roll.hash {
+                let message = format!("Roll {} doesn't match its hash, so the log was changed.", i + 1);
+                return Err(FarkleError::SaveCorrupt(message));
+            }
+            if let Some(rng) = &mut rng {
+                let expected = roll_dice(TOTAL_DICE, rng);
+                if roll.dice.is_empty() || !expected.starts_with(&roll.dice) {
+                    return Err(FarkleError::SaveCorrupt(format!(
+                        "Roll {} was {:?} but {} rolls {:?}.",
+                        i + 1,
+                        roll.dice,
+                        self.rng,
+                        &expected[..roll.dice.len().clamp(1, expected.len())]
+                    )));
+                }
+            }
+            previous = roll.hash.clone();
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AuditLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.rng {
+            RngBackend::Seeded(seed) => writeln!(f, "farkle-audit seed {}", seed)?,
+            rng => writeln!(f, "farkle-audit rng {}", rng)?,
+        }
+        for roll in &self.rolls {
+            writeln!(f, "{} {}", join_dice(&roll.dice), roll.hash)?;
+        }
+        Ok(())
+    }
+}
+
+/// A game's audit log and the game it rolled for, exported at the end so
+/// anyone can check the dice offline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollExport {
+    pub log: AuditLog,
+    pub record: GameRecord,
+}
+
+impl RollExport {
+    /// Returns the export of record, rolled as log says.
+    pub fn new(log: AuditLog, record: GameRecord) -> RollExport {
+        RollExport { log, record }
+    }
+
+    /// Parse an export written with `to_string`: the log, then the game in
+    /// farkle notation from its first tag.
+    pub fn parse(text: &str) -> Result<RollExport, FarkleError> {
+        let game = text.find("\n[").map_or(text.len(), |i| i + 1);
+        let (log, record) = text.split_at(game);
+        if record.trim().is_empty() {
+            return Err(FarkleError::SaveCorrupt("The game should follow the rolls, starting with its players.".into()));
+        }
+        Ok(RollExport { log: AuditLog::parse(log)?, record: GameRecord::parse(record)? })
+    }
+
+    /// Check the log like `AuditLog::verify`, and that its rolls are the
+    /// game's, in order, so the game was played with those dice.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::audit::{AuditLog, RollExport};
+    /// use farkle::bot::Bot;
+    /// use farkle::config::RngBackend;
+    /// use farkle::game::{Game, Player, WinCondition};
+    /// use farkle::harness::play_through;
+    ///
+    /// let game = Game::with_win_condition(vec![Player::new("Ann"), Player::new("Bob")], WinCondition::RoundLimit(2));
+    /// let played = play_through(game, &mut farkle::seeded_rng(4), &mut [Bot::new(300), Bot::new(500)]).unwrap();
+    /// let mut log = AuditLog::new(RngBackend::Seeded(4));
+    /// for roll in played.record.turns.iter().flat_map(|turn| &turn.rolls) {
+    ///     log.record(&roll.dice);
+    /// }
+    /// let export = RollExport::new(log, played.record.clone());
+    /// let read = RollExport::parse(&export.to_string()).unwrap();
+    /// assert_eq!(export, read);
+    /// assert_eq!(Ok(()), read.verify());
+    ///
+    /// // A log short of the game's rolls doesn't check out.
+    /// let mut short = export.clone();
+    /// short.log.rolls.pop();
+    /// assert!(short.verify().is_err());
+    /// short.log = AuditLog::new(RngBackend::Seeded(4));
+    /// assert!(short.verify().is_err());
+    /// ```
+    pub fn verify(&self) -> Result<(), FarkleError> {
+        self.log.verify()?;
+        let played: Vec<&Vec<i16>> =
+            self.record.turns.iter().flat_map(|turn| &turn.rolls).map(|roll| &roll.dice).collect();
+        let logged: Vec<&Vec<i16>> = self.log.rolls.iter().map(|roll| &roll.dice).collect();
+        if let Some(i) = (0..played.len().max(logged.len())).find(|i| played.get(*i) != logged.get(*i)) {
+            let message = match (played.get(i), logged.get(i)) {
+                (Some(played), Some(logged)) => {
+                    format!("Roll {} of the game was {:?} but the log has {:?}.", i + 1, played, logged)
+                }
+                (Some(_), None) => format!("The game has {} rolls but the log only {}.", played.len(), logged.len()),
+                _ => format!("The log has {} rolls but the game only {}.", logged.len(), played.len()),
+            };
+            return Err(FarkleError::SaveCorrupt(message));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RollExport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\n{}", self.log, self.record)
+    }
+}
+
+/// A decider whose rolls are added to an audit log.
+pub struct Audited<'a, D: ?Sized> {
+    decider: &'a mut D,
+    log: &'a mut AuditLog,
+}
+
+impl<'a, D: Decider + ?Sized> Audited<'a, D> {
+    /// Returns decider, recording its rolls in log.
+    pub fn new(decider: &'a mut D, log: &'a mut AuditLog) -> Audited<'a, D> {
+        Audited { decider, log }
+    }
+}
+
+impl<'a, D: Decider + ?Sized> Decider for Audited<'a, D> {
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> PlayerAction {
+        self.decider.choose_keep(dice, turn)
+    }
+
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> PlayerAction {
+        self.decider.choose_bank(turn)
+    }
+
+    fn remark(&mut self) -> Option<String> {
+        self.decider.remark()
+    }
+
+    fn on_turn_start(&mut self, game: &GameSnapshot) {
+        self.decider.on_turn_start(game);
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        if let Event::Rolled { dice } = event {
+            self.log.record(dice);
+        }
+        self.decider.on_event(event);
+    }
+}
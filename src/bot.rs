@@ -0,0 +1,459 @@
+//! Computer players.
+
+use crate::error::FarkleError;
+use crate::game::GameSnapshot;
+use crate::odds::bank_threshold;
+use crate::turn::{Decider, PlayerAction, TurnSnapshot};
+use crate::ScoringRules;
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Things a bot says about its choices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Personality {
+    pub roll_again: &'static [&'static str],
+    pub bank: &'static [&'static str],
+}
+
+impl Personality {
+    /// Returns an upbeat personality.
+    pub fn cheerful() -> Personality {
+        Personality {
+            roll_again: &["I'm feeling lucky!", "Let's ride!", "One more, what could go wrong?"],
+            bank: &["I'll take it!", "Points in the bank, yay!", "Safe and sound."],
+        }
+    }
+
+    /// Returns a sulky personality.
+    pub fn grumpy() -> Personality {
+        Personality {
+            roll_again: &["Fine, I'll roll.", "This had better work.", "Ugh, again."],
+            bank: &["Banking, no thanks to you.", "That'll have to do.", "Don't get used to it."],
+        }
+    }
+}
+
+/// Points from the end at which a bot playing the odds minds the scores.
+static NEAR_THE_END: u32 = 2000;
+
+/// A bot that keeps every scoring die and banks once it has enough.
+///
+/// Bots don't roll dice of their own or pick at random, so the same dice
+/// always get the same choices. Wrap one in `seat::Paced` to have it take
+/// time to think.
+///
+/// ### Examples
+/// ```
+/// use farkle::bot::Bot;
+/// use farkle::game::{Game, Player};
+/// use farkle::turn::{play_turn, Decider, TurnEngine};
+///
+/// // The same seed plays out the same game, choice for choice.
+/// let mut rng = farkle::seeded_rng(42);
+/// let mut bots = [Bot::new(300), Bot::by_odds()];
+/// let mut game = Game::new(vec![Player::new("Robo"), Player::new("Sharp")]);
+/// while !game.is_over() {
+///     let bot = &mut bots[game.current_index()];
+///     bot.on_turn_start(&game.snapshot());
+///     let (mut turn, _events) = TurnEngine::start(&mut rng);
+///     let score = play_turn(&mut turn, &mut rng, bot);
+///     game.record_round(score);
+/// }
+/// let scores: Vec<u32> = game.players().iter().map(|player| player.score).collect();
+/// assert_eq!(vec![10_150, 9_200], scores);
+/// assert_eq!(19, game.round_number());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bot {
+    /// Bank once the turn score is at least this.
+    pub bank_at: u32,
+    /// Bank rather than roll fewer than this many dice.
+    pub min_dice: i8,
+    /// Bank by the odds of the dice left instead, per `bank_threshold`,
+    /// pushing harder when behind near the end and playing safe when ahead.
+    pub by_odds: bool,
+    /// The game at the start of the turn, if it's been seen.
+    game: Option<GameSnapshot>,
+    /// Bank thresholds by dice left, for the rules they were worked out by.
+    thresholds: Option<(ScoringRules, Vec<u32>)>,
+    personality: Option<Personality>,
+    remarks_made: usize,
+    pending_remark: Option<String>,
+}
+
+impl Bot {
+    /// Returns a quiet bot banking at bank_at points.
+    pub fn new(bank_at: u32) -> Bot {
+        Bot {
+            bank_at,
+            min_dice: 3,
+            by_odds: false,
+            game: None,
+            thresholds: None,
+            personality: None,
+            remarks_made: 0,
+            pending_remark: None,
+        }
+    }
+
+    /// Returns a bot banking at bank_at points that comments on its choices.
+    pub fn with_personality(bank_at: u32, personality: Personality) -> Bot {
+        Bot {
+            personality: Some(personality),
+            ..Bot::new(bank_at)
+        }
+    }
+
+    /// Returns a quiet bot banking by the odds of the dice left and the
+    /// scores.
+    pub fn by_odds() -> Bot {
+        Bot { by_odds: true, ..Bot::new(0) }
+    }
+
+    /// Returns whether a bot playing the odds banks turn.
+    fn bank_by_odds(&mut self, turn: &TurnSnapshot) -> bool {
+        if self.thresholds.as_ref().is_none_or(|(rules, _thresholds)| *rules != turn.rules) {
+            let thresholds = (0..=6).map(|num_dice| bank_threshold(&turn.rules, num_dice)).collect();
+            self.thresholds = Some((turn.rules.clone(), thresholds));
+        }
+        let thresholds = &self.thresholds.as_ref().expect("just worked out").1;
+        // Hot dice roll all six again.
+        let num_dice = if turn.num_dice == 0 { 6 } else { turn.num_dice as usize };
+        let mut bank_at = thresholds[num_dice.min(6)];
+        let Some(game) = &self.game else {
+            return turn.score >= bank_at;
+        };
+        if game.scores_hidden {
+            return turn.score >= bank_at;
+        }
+        // With no turns to come, only first place matters.
+        if game.last_turn {
+            return turn.score >= game.points_to_lead;
+        }
+        let mut players = game.opponents.iter().chain([&game.player]);
+        let near_the_end = match game.rounds_left {
+            Some(rounds) => rounds <= 2,
+            None => players.any(|player| player.target.saturating_sub(player.score) < NEAR_THE_END),
+        };
+        if near_the_end {
+            let best = game.opponents.iter().map(|opponent| opponent.score).max().unwrap_or(0);
+            if game.points_to_lead > 0 {
+                bank_at = bank_at.saturating_mul(3) / 2;
+            } else if game.player.score >= best.saturating_add(NEAR_THE_END) {
+                bank_at = bank_at * 2 / 3;
+            }
+        }
+        turn.score >= bank_at
+    }
+
+    /// Queue the next of lines to say, taking turns through them.
+    fn say(&mut self, lines: &[&str]) {
+        if lines.is_empty() {
+            return;
+        }
+        self.pending_remark = Some(lines[self.remarks_made % lines.len()].to_string());
+        self.remarks_made += 1;
+    }
+}
+
+impl Decider for Bot {
+    /// ### Examples
+    /// ```
+    /// use farkle::bot::Bot;
+    /// use farkle::turn::{Decider, PlayerAction, TurnState};
+    /// let mut bot = Bot::new(300);
+    /// let mut turn = TurnState::new().snapshot();
+    /// assert_eq!(PlayerAction::KeepAllScoring, bot.choose_keep(&[2, 3, 4, 4, 6, 6], &turn));
+    /// // A free roll saves a roll that would farkle.
+    /// turn.free_rolls = 1;
+    /// assert_eq!(PlayerAction::FreeRoll, bot.choose_keep(&[2, 3, 4, 4, 6, 6], &turn));
+    /// assert_eq!(PlayerAction::KeepAllScoring, bot.choose_keep(&[1, 3, 4, 4, 6, 6], &turn));
+    /// ```
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> PlayerAction {
+        if turn.free_rolls > 0 && turn.rules.score(dice) == 0 {
+            PlayerAction::FreeRoll
+        } else {
+            PlayerAction::KeepAllScoring
+        }
+    }
+
+    /// ### Examples
+    /// ```
+    /// use farkle::bot::{Bot, Personality};
+    /// use farkle::turn::{Decider, PlayerAction, TurnState};
+    /// let mut bot = Bot::with_personality(300, Personality::cheerful());
+    /// let mut turn = TurnState::new();
+    /// turn.record(vec![1, 2, 3, 3, 4, 6], vec![1], 100);
+    /// assert_eq!(PlayerAction::RollAgain, bot.choose_bank(&turn.snapshot()));
+    /// assert_eq!(Some("I'm feeling lucky!".to_string()), bot.remark());
+    /// assert_eq!(None, bot.remark());
+    /// turn.record(vec![5, 5, 2, 3, 4], vec![5, 5], 100);
+    /// turn.record(vec![1, 3, 4], vec![1], 100);
+    /// assert_eq!(PlayerAction::Bank, bot.choose_bank(&turn.snapshot()));
+    /// assert_eq!(Some("Points in the bank, yay!".to_string()), bot.remark());
+    /// ```
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> PlayerAction {
+        // A bank short of the opening score counts for nothing, and one
+        // past a target to hit exactly can't be made good by rolling on.
+        let opening = self.game.as_ref().map_or(0, |game| game.opening_needed);
+        let to_finish = match &self.game {
+            Some(game) if turn.rules.exact_finish => Some(game.player.target.saturating_sub(game.player.score)),
+            _ => None,
+        };
+        let bank = if turn.score < opening {
+            false
+        } else if to_finish.is_some_and(|to_finish| turn.score >= to_finish) {
+            true
+        } else if self.by_odds {
+            self.bank_by_odds(turn)
+        } else {
+            turn.score >= self.bank_at || turn.num_dice < self.min_dice
+        };
+        if let Some(personality) = self.personality {
+            self.say(if bank { personality.bank } else { personality.roll_again });
+        }
+        if bank {
+            PlayerAction::Bank
+        } else {
+            PlayerAction::RollAgain
+        }
+    }
+
+    fn remark(&mut self) -> Option<String> {
+        self.pending_remark.take()
+    }
+
+    /// ### Examples
+    /// ```
+    /// use farkle::bot::Bot;
+    /// use farkle::game::{Game, Player};
+    /// use farkle::turn::{Decider, PlayerAction, TurnState};
+    /// use farkle::ScoringRules;
+    ///
+    /// let mut turn = TurnState::new();
+    /// turn.record(vec![1, 1, 1, 2, 3, 4], vec![1, 1, 1], 1000);
+    /// let mut bot = Bot::by_odds();
+    /// assert_eq!(PlayerAction::Bank, bot.choose_bank(&turn.snapshot()));
+    ///
+    /// // Last to play and behind, so 1000 isn't enough.
+    /// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bot")]);
+    /// game.record_round(10_500);
+    /// bot.on_turn_start(&game.snapshot());
+    /// assert_eq!(PlayerAction::RollAgain, bot.choose_bank(&turn.snapshot()));
+    ///
+    /// // Nor is it worth banking what won't count.
+    /// let mut game = Game::new(vec![Player::new("Bot")]);
+    /// game.set_rules(ScoringRules { opening_score: Some(1500), ..ScoringRules::default() });
+    /// bot.on_turn_start(&game.snapshot());
+    /// assert_eq!(PlayerAction::RollAgain, bot.choose_bank(&turn.snapshot()));
+    /// ```
+    fn on_turn_start(&mut self, game: &GameSnapshot) {
+        self.game = Some(game.clone());
+    }
+}
+
+/// Games a practice bot remembers when working out how often it wins.
+pub static PRACTICE_MEMORY: usize = 10;
+
+/// Bank at amounts a practice bot moves between: timid bots banking early
+/// lose more, and banking at 1000 is about as well as a bank at bot plays.
+const PRACTICE_BANK_AT: std::ops::RangeInclusive<u32> = 100..=1000;
+
+/// A bot for practice, which plays better or worse to win about as often as
+/// it's asked to against a person.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Handicap {
+    /// Share of games the bot aims to win, from 0 to 1.
+    pub win_rate: f64,
+    /// How much the bot banks at, for now.
+    pub bank_at: u32,
+    /// Whether the bot won each of its latest games, oldest first.
+    pub results: VecDeque<bool>,
+}
+
+impl Handicap {
+    /// Returns a practice bot aiming to win win_rate of its games, starting
+    /// in the middle.
+    pub fn new(win_rate: f64) -> Handicap {
+        Handicap { win_rate, bank_at: 500, results: VecDeque::new() }
+    }
+
+    /// Returns a bot playing as well as the practice bot is for now.
+    pub fn bot(&self) -> Bot {
+        Bot::new(self.bank_at)
+    }
+
+    /// Returns the share of its latest games the bot won, if it's played
+    /// any.
+    pub fn wins(&self) -> Option<f64> {
+        let won = self.results.iter().filter(|won| **won).count();
+        (!self.results.is_empty()).then(|| won as f64 / self.results.len() as f64)
+    }
+
+    /// Record a game the bot won or lost, and play better if it's been
+    /// losing too often or worse if it's been winning too often.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::bot::Handicap;
+    /// let mut practice = Handicap::new(0.5);
+    /// practice.record(true);
+    /// practice.record(true);
+    /// // Winning every game, so it banks sooner.
+    /// assert_eq!(300, practice.bank_at);
+    /// practice.record(false);
+    /// practice.record(false);
+    /// practice.record(false);
+    /// // Down to two wins in five, so it plays better again.
+    /// assert_eq!(Some(0.4), practice.wins());
+    /// assert_eq!(300, practice.bank_at);
+    /// ```
+    pub fn record(&mut self, won: bool) {
+        self.results.push_back(won);
+        while self.results.len() > PRACTICE_MEMORY {
+            self.results.pop_front();
+        }
+        let wins = self.wins().unwrap_or(self.win_rate);
+        if wins > self.win_rate {
+            self.bank_at = self.bank_at.saturating_sub(100);
+        } else if wins < self.win_rate {
+            self.bank_at += 100;
+        }
+        self.bank_at = self.bank_at.clamp(*PRACTICE_BANK_AT.start(), *PRACTICE_BANK_AT.end());
+    }
+
+    /// Parse a practice bot written with `to_string`.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::bot::Handicap;
+    /// let mut practice = Handicap::new(0.25);
+    /// practice.record(false);
+    /// assert_eq!(Ok(practice.clone()), Handicap::parse(&practice.to_string()));
+    /// assert!(Handicap::parse("aim lots").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<Handicap, FarkleError> {
+        let mut practice = Handicap::new(0.5);
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let bad_line = || FarkleError::SaveCorrupt(format!("'{}' isn't part of a practice bot.", line));
+            let (key, value) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+            match key {
+                "aim" => practice.win_rate = value.parse().map_err(|_| bad_line())?,
+                "bank" => practice.bank_at = value.parse().map_err(|_| bad_line())?,
+                "results" => {
+                    let results: Option<VecDeque<bool>> = value
+                        .chars()
+                        .map(|result| match result {
+                            'W' => Some(true),
+                            'L' => Some(false),
+                            _ => None,
+                        })
+                        .collect();
+                    practice.results = results.ok_or_else(bad_line)?;
+                }
+                _ => return Err(bad_line()),
+            }
+        }
+        Ok(practice)
+    }
+}
+
+impl fmt::Display for Handicap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "aim {}", self.win_rate)?;
+        writeln!(f, "bank {}", self.bank_at)?;
+        let results: String = self.results.iter().map(|won| if *won { 'W' } else { 'L' }).collect();
+        writeln!(f, "results {}", results)
+    }
+}
+
+/// How well a bot filling a seat plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    /// Banks at 300, throwing away turns that could have gone on.
+    Easy,
+    /// Banks at 500.
+    Normal,
+    /// Banks by the odds and the scores.
+    Hard,
+}
+
+impl Difficulty {
+    /// Returns the difficulty called name.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::bot::Difficulty;
+    /// assert_eq!(Ok(Difficulty::Hard), Difficulty::parse("Hard"));
+    /// assert!(Difficulty::parse("brutal").is_err());
+    /// ```
+    pub fn parse(name: &str) -> Result<Difficulty, FarkleError> {
+        match name.trim().to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(FarkleError::InvalidInput(format!("'{}' isn't a difficulty. Try easy, normal or hard.", name))),
+        }
+    }
+
+    /// Returns a quiet bot playing this well.
+    pub fn bot(self) -> Bot {
+        match self {
+            Difficulty::Easy => Bot::new(300),
+            Difficulty::Normal => Bot::new(500),
+            Difficulty::Hard => Bot::by_odds(),
+        }
+    }
+}
+
+/// Names given to bots filling seats, in order.
+pub static BOT_NAMES: [&str; 8] = ["Robo", "Grumble", "Dicey", "Pip", "Boxcar", "Snake Eyes", "Lucky", "Sixer"];
+
+/// Returns count names for bots, none of them in taken: from `BOT_NAMES`,
+/// then numbered.
+///
+/// ### Examples
+/// ```
+/// use farkle::bot::bot_names;
+/// assert_eq!(vec!["Grumble", "Dicey"], bot_names(2, &["Robo"]));
+/// assert_eq!("Bot 9", bot_names(9, &[])[8]);
+/// ```
+pub fn bot_names(count: usize, taken: &[&str]) -> Vec<String> {
+    let numbered = (BOT_NAMES.len() + 1..).map(|n| format!("Bot {}", n));
+    BOT_NAMES
+        .iter()
+        .map(|name| name.to_string())
+        .chain(numbered)
+        .filter(|name| !taken.contains(&name.as_str()))
+        .take(count)
+        .collect()
+}
+
+/// Returns humans and bots seated together, the bots spread out between
+/// the humans rather than all playing in a row, each group in the order
+/// given.
+///
+/// ### Examples
+/// ```
+/// use farkle::bot::seat_together;
+/// let humans = vec!["Ann", "Bob"];
+/// assert_eq!(vec!["Ann", "Robo", "Bob", "Pip"], seat_together(humans.clone(), vec!["Robo", "Pip"]));
+/// assert_eq!(vec!["Ann", "Robo", "Pip", "Bob", "Dicey"], seat_together(humans, vec!["Robo", "Pip", "Dicey"]));
+/// assert_eq!(vec!["Robo"], seat_together(vec![], vec!["Robo"]));
+/// ```
+pub fn seat_together<T>(humans: Vec<T>, bots: Vec<T>) -> Vec<T> {
+    let seats = humans.len() + bots.len();
+    let count = humans.len();
+    let mut humans = humans.into_iter();
+    let mut bots = bots.into_iter();
+    // The i'th human sits at seat i * seats / count, rounded up.
+    let mut seated = 0;
+    let mut table = Vec::with_capacity(seats);
+    for seat in 0..seats {
+        let human_due = seated < count && seat >= (seated * seats).div_ceil(count);
+        let next = if human_due { humans.next() } else { bots.next() };
+        seated += human_due as usize;
+        table.extend(next);
+    }
+    table
+}
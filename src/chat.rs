@@ -0,0 +1,257 @@
+//! One game per chat room, for bots on Matrix and the like, and Slack
+//! slash commands.
+//!
+//! The bot passes on each message posted in a room and posts back the
+//! replies. Connecting to the chat server is left to the bot, so the same
+//! rooms work whichever client library it uses.
+
+use crate::config::GameConfig;
+use crate::event::Event;
+use crate::game::{Game, Player};
+use crate::parse::{parse_bank_answer, parse_keep_action, tokenize};
+use crate::phase::{GameEngine, GamePhase};
+use crate::render::{render_chat, render_chat_scores, render_dice_emoji, render_points};
+use crate::seeded_rng;
+use crate::turn::PlayerAction;
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashMap;
+
+/// Messages starting with this are for the game.
+pub static COMMAND: &str = "!farkle";
+
+/// Help for players, posted for "!farkle help".
+static HELP: &str = "\
+!farkle new @bob @cat: start a game with everyone named, you first
+!farkle 1 5 or !farkle all: keep dice by position, or every scoring die
+!farkle 1 5 bank: keep dice and bank in one go
+!farkle roll or !farkle bank: roll again or bank
+!farkle score: how the dice score
+!farkle scores: everyone's scores
+!farkle stop: end the game";
+
+/// A game in a room, with its own dice.
+#[derive(Debug)]
+struct Room {
+    engine: GameEngine,
+    rng: ChaCha8Rng,
+}
+
+/// Games by room.
+#[derive(Debug, Default)]
+pub struct ChatRooms {
+    rooms: HashMap<String, Room>,
+}
+
+impl ChatRooms {
+    /// Returns rooms with no games.
+    pub fn new() -> ChatRooms {
+        ChatRooms::default()
+    }
+
+    /// Returns true if there's a game in room.
+    pub fn is_playing(&self, room: &str) -> bool {
+        self.rooms.contains_key(room)
+    }
+
+    /// Start a game between players in room, rolling with seed.
+    ///
+    /// Returns the messages to post.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::chat::ChatRooms;
+    /// use farkle::game::Player;
+    ///
+    /// let mut rooms = ChatRooms::new();
+    /// let replies = rooms.start("!games", vec![Player::new("@ann"), Player::new("@bob")], 1);
+    /// assert_eq!("🎲 @ann rolled ⚃⚂⚅⚀⚁⚃", replies[1]);
+    /// rooms.handle("!games", "@ann", "!farkle 4");
+    /// let replies = rooms.handle("!games", "@ann", "!farkle bank");
+    /// assert_eq!(vec!["🏦 @ann banked 100.", "📋 @ann: 100 this round."], replies[..2]);
+    /// assert_eq!("🎲 @bob rolled ⚂⚁⚄⚁⚅⚄", replies[2]);
+    /// ```
+    pub fn start(&mut self, room: &str, players: Vec<Player>, seed: u64) -> Vec<String> {
+        if self.is_playing(room) {
+            return vec!["There's already a game here. Finish it or !farkle stop first.".to_string()];
+        }
+        let first = players[0].name.clone();
+        let mut rng = seeded_rng(seed);
+        let (engine, events) = GameEngine::start(Game::new(players), &mut rng);
+        let mut replies = vec![format!("New game! {}", render_chat_scores(engine.game()))];
+        let shown = events.iter().filter_map(|event| engine.game().redact(event, &first, None));
+        replies.extend(shown.map(|event| render_chat(&first, &event)));
+        replies.push(prompt(&engine));
+        self.rooms.insert(room.to_string(), Room { engine, rng });
+        replies
+    }
+
+    /// Take a message sender posted in room.
+    ///
+    /// Returns the messages to post back, none if the message wasn't for
+    /// the game.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::chat::ChatRooms;
+    ///
+    /// let mut rooms = ChatRooms::new();
+    /// assert!(rooms.handle("!games", "@ann", "hi all").is_empty());
+    /// let replies = rooms.handle("!games", "@ann", "!farkle new @bob");
+    /// assert!(replies[0].starts_with("New game!"));
+    /// assert!(rooms.is_playing("!games"));
+    /// assert_eq!(
+    ///     vec!["Two players are called @bob. Give each of them a name of their own."],
+    ///     rooms.handle("!other", "@ann", "!farkle new @bob @bob")
+    /// );
+    ///
+    /// // Only the player whose turn it is can play.
+    /// assert_eq!(vec!["It's @ann's turn."], rooms.handle("!games", "@bob", "!farkle all"));
+    /// assert_eq!(vec!["The game is over."], rooms.handle("!games", "@bob", "!farkle stop"));
+    /// assert!(!rooms.is_playing("!games"));
+    /// ```
+    pub fn handle(&mut self, room: &str, sender: &str, text: &str) -> Vec<String> {
+        let tokens = tokenize(text);
+        let Some(args) = tokens.split_first().and_then(|(first, args)| (first == COMMAND).then_some(args)) else {
+            return Vec::new();
+        };
+        match args.first().map(String::as_str) {
+            Some("help") => return vec![HELP.to_string()],
+            Some("new") => {
+                let mut players = vec![Player::new(sender)];
+                // Names are matched as typed, since tokenize lowercases.
+                let named = text.split_whitespace().skip(2);
+                players.extend(named.filter(|name| *name != sender).map(Player::new));
+                if let Err(problems) = GameConfig::validate_players(&players) {
+                    return vec![problems.to_string()];
+                }
+                return self.start(room, players, rand::random());
+            }
+            _ => {}
+        }
+        let Some(current) = self.rooms.get_mut(room) else {
+            return vec!["There's no game here. Start one with !farkle new.".to_string()];
+        };
+        let in_game = current.engine.game().players().iter().any(|player| player.name == sender);
+        match args.first().map(String::as_str) {
+            Some("scores") => return vec![render_chat_scores(current.engine.game())],
+            Some("stop") if in_game => {
+                self.rooms.remove(room);
+                return vec!["The game is over.".to_string()];
+            }
+            Some("stop") => return vec!["Only players can stop the game.".to_string()],
+            _ => {}
+        }
+        let player = current.engine.game().current_player().name.clone();
+        if player != sender {
+            return vec![format!("It's {}'s turn.", player)];
+        }
+        let answer = args.join(" ");
+        let action = match current.engine.next() {
+            GamePhase::AwaitingKeep { roll, options, .. } => match parse_keep_action(&answer) {
+                Ok(PlayerAction::ShowBreakdown) => {
+                    let best = options.first().map_or("No scoring dice.".to_string(), |best| best.explain());
+                    return vec![format!("{} {}", render_dice_emoji(&roll), best)];
+                }
+                action => action,
+            },
+            GamePhase::AwaitingBankDecision { .. } => parse_bank_answer(&answer),
+            GamePhase::Over { .. } => return Vec::new(),
+        };
+        let action = match action {
+            Ok(PlayerAction::Quit) => {
+                self.rooms.remove(room);
+                return vec![format!("{} ended the game.", sender)];
+            }
+            Ok(action) => action,
+            Err(error) => return vec![error.to_string()],
+        };
+        let events = current.engine.respond(action, &mut current.rng);
+        // A turn that ends is followed by the next player's first roll.
+        let mut name = player;
+        let mut turn_over = false;
+        let mut replies = Vec::new();
+        for event in &events {
+            if turn_over && matches!(event, Event::Rolled { .. }) {
+                name = current.engine.game().current_player().name.clone();
+            }
+            turn_over |= matches!(event, Event::RoundSummary { .. });
+            // Everyone in the room sees the same messages.
+            if let Some(event) = current.engine.game().redact(event, &name, None) {
+                replies.push(render_chat(&name, &event));
+            }
+        }
+        if let GamePhase::Over { winner } = current.engine.next() {
+            replies.push(render_chat_scores(current.engine.game()));
+            if let Some(winner) = winner {
+                replies.push(format!("🎉 {} wins with {}!", winner.name, render_points(winner.score)));
+            }
+            self.rooms.remove(room);
+        } else {
+            replies.push(prompt(&current.engine));
+        }
+        replies
+    }
+
+    /// Take a Slack slash command, the form Slack posts for something like
+    /// "/farkle 1 5", in the channel it was typed in.
+    ///
+    /// Returns the messages to post back, for the server to send as the
+    /// response.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::chat::ChatRooms;
+    ///
+    /// let mut rooms = ChatRooms::new();
+    /// let form = "channel_id=C42&user_name=ann&command=%2Ffarkle&text=new+%40bob";
+    /// assert!(rooms.handle_slash_command(form)[0].starts_with("New game!"));
+    /// assert!(rooms.is_playing("C42"));
+    /// let form = "channel_id=C42&user_name=bob&command=%2Ffarkle&text=all";
+    /// assert_eq!(vec!["It's @ann's turn."], rooms.handle_slash_command(form));
+    /// ```
+    pub fn handle_slash_command(&mut self, form: &str) -> Vec<String> {
+        let (Some(channel), Some(user)) = (form_value(form, "channel_id"), form_value(form, "user_name")) else {
+            return vec!["Slack didn't say which channel or user this is.".to_string()];
+        };
+        let text = form_value(form, "text").unwrap_or_default();
+        self.handle(&channel, &format!("@{}", user), &format!("{} {}", COMMAND, text))
+    }
+}
+
+/// Returns the value for key in a form posted over HTTP, decoded.
+fn form_value(form: &str, key: &str) -> Option<String> {
+    let (_key, value) = form.split('&').filter_map(|pair| pair.split_once('=')).find(|(name, _value)| *name == key)?;
+    let mut bytes = Vec::new();
+    let mut rest = value.bytes();
+    while let Some(byte) = rest.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex: Vec<u8> = rest.by_ref().take(2).collect();
+                let decoded = std::str::from_utf8(&hex).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                bytes.push(decoded?);
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Returns what to ask the player whose turn it is.
+fn prompt(engine: &GameEngine) -> String {
+    match engine.next() {
+        GamePhase::AwaitingKeep { player, options, .. } if options.is_empty() => {
+            format!("{}: no scoring dice. !farkle all to move on.", player)
+        }
+        GamePhase::AwaitingKeep { player, .. } => format!("{}: which dice? !farkle 1 5, or !farkle all", player),
+        GamePhase::AwaitingBankDecision { player, odds, .. } => format!(
+            "{}: {} at stake, {:.0}% chance of a farkle with {} {}. !farkle roll or !farkle bank?",
+            player,
+            render_points(odds.at_risk),
+            odds.farkle_chance * 100.0,
+            odds.dice_left,
+            if odds.dice_left == 1 { "die" } else { "dice" }
+        ),
+        GamePhase::Over { .. } => String::new(),
+    }
+}
@@ -0,0 +1,223 @@
+//! Starting up: reading the settings from the flags and running the
+//! subcommand asked for.
+
+use farkle::audit::AuditLog;
+use farkle::config::{GameConfig, Preset};
+use farkle::console::Console;
+use farkle::confirm::ConfirmPolicy;
+use farkle::error::FarkleError;
+use farkle::frontend::FrontendCapabilities;
+use farkle::game::Visibility;
+use farkle::golden;
+use farkle::labels::DiceLabels;
+use farkle::saves::SaveSlots;
+use farkle::scoresheet::Scoresheet;
+use farkle::notation::GameRecord;
+use farkle::theme::Theme;
+use rand::RngCore;
+use std::fs;
+use std::path::Path;
+use std::io::{self, IsTerminal, StdinLock, Stdout};
+use std::process;
+use std::time::Duration;
+use super::commands::{
+    audit, chart, explore, play, play_correspondence, play_daily, practice, rngtest, saves, score, shell,
+    verify_rolls,
+};
+use super::line::LineEditor;
+use super::pause::Pausable;
+use super::input::{
+    clock, confirm_policy, default_target, demo_players, joining, players, rng, rules, turn_order, win_condition,
+};
+
+pub fn run(args: &[String]) {
+    // Run the subcommand args ask for, with the settings from their flags.
+    let settings = Settings::from_args(args);
+    match args.get(1).map(String::as_str) {
+        Some("audit") => audit(args.get(2)),
+        Some("verify-rolls") => verify_rolls(args.get(2)),
+        Some("score") => score(&args[2..], &settings),
+        Some("explore") => explore(&args[2..], &settings),
+        Some("chart") => chart(args, &settings),
+        Some("golden") => print!("{}", golden::table()),
+        Some("rngtest") => rngtest(args, &settings),
+        Some("daily") => play_daily(args.iter().any(|arg| arg == "--share"), &settings),
+        Some("correspondence") => play_correspondence(args, &settings),
+        Some("practice") => practice(args, &settings),
+        Some("shell") => shell(&settings),
+        Some("saves") => saves(&args[2..]),
+        Some("new") => {
+            play(players(args, &settings), &settings);
+        }
+        Some("demo") => {
+            play(demo_players(args, &settings), &settings);
+        }
+        _ => {
+            play(players(args, &settings), &settings);
+        }
+    }
+}
+
+/// The console games are played at.
+pub type Terminal = Console<Pausable<LineEditor<StdinLock<'static>>>, Stdout>;
+
+/// How long --dramatic waits before showing each die.
+const REVEAL_PAUSE: Duration = Duration::from_millis(300);
+
+pub struct Settings {
+    pub preset: Option<Preset>,
+    pub config: GameConfig,
+    pub policy: ConfirmPolicy,
+    pub coaching: bool,
+    pub bot_pause: Duration,
+    pub reveal_pause: Option<Duration>,
+    pub dice_labels: Option<DiceLabels>,
+    pub audit_file: Option<String>,
+    pub record_file: Option<String>,
+    pub scoresheet: Option<Scoresheet>,
+    pub saved: Option<GameRecord>,
+    pub theme: Theme,
+    pub capabilities: FrontendCapabilities,
+}
+
+impl Settings {
+    pub fn from_args(args: &[String]) -> Settings {
+        // Bots take their time in a demo, so it can be followed, or per
+        // --pause <seconds>.
+        let default_pause = if args.get(1).is_some_and(|arg| arg == "demo") { 1.0 } else { 0.0 };
+        let bot_pause = match args.iter().position(|arg| arg == "--pause") {
+            Some(i) => match args.get(i + 1).and_then(|seconds| seconds.parse::<f64>().ok()) {
+                Some(seconds) if Duration::try_from_secs_f64(seconds).is_ok() => seconds,
+                _ => {
+                    eprintln!("--pause needs a number of seconds, like --pause 0.5");
+                    process::exit(1);
+                }
+            },
+            None => default_pause,
+        };
+        // --preset <name> starts from a bundled setup, and other flags
+        // change it from there.
+        let preset = args.iter().position(|arg| arg == "--preset").map(|i| {
+            let name = args.get(i + 1).map_or("", String::as_str);
+            Preset::parse(name).unwrap_or_else(|message| {
+                eprintln!("{}", message);
+                process::exit(1);
+            })
+        });
+        let base = preset.map_or_else(GameConfig::default, |preset| preset.config());
+        let config = GameConfig {
+            rng: rng(args),
+            target: default_target(args, base.target),
+            win_condition: win_condition(args, base.win_condition),
+            clock: clock(args, base.clock),
+            rules: rules(args, base.rules),
+            turn_order: turn_order(args, base.turn_order),
+            joining: joining(args, base.joining),
+            // --blind hides every total, even a player's own, until the end.
+            visibility: if args.iter().any(|arg| arg == "--blind") { Visibility::Blind } else { base.visibility },
+        };
+        // Report every problem with the game at once, not one per try.
+        if let Err(problems) = config.validate() {
+            eprintln!("{}", problems);
+            process::exit(1);
+        }
+        let audit_file = args.iter().position(|arg| arg == "--audit").and_then(|i| args.get(i + 1)).cloned();
+        let record_file = args.iter().position(|arg| arg == "--record").and_then(|i| args.get(i + 1)).cloned();
+        // --scoresheet <file> carries on a game kept on paper.
+        let scoresheet = args.iter().position(|arg| arg == "--scoresheet").map(|i| {
+            let path = args.get(i + 1).map_or("", String::as_str);
+            let sheet = fs::read_to_string(path).map_err(FarkleError::io(path));
+            sheet.and_then(|sheet| Scoresheet::parse(&sheet)).unwrap_or_else(|error| {
+                eprintln!("{}", error);
+                process::exit(1);
+            })
+        });
+        // --load <name> carries on a game saved by name, or in a file.
+        let saved = args.iter().position(|arg| arg == "--load").map(|i| {
+            let name = args.get(i + 1).map_or("", String::as_str);
+            let saved = if Path::new(name).is_file() {
+                fs::read_to_string(name).map_err(FarkleError::io(name)).and_then(|text| GameRecord::parse(&text))
+            } else {
+                SaveSlots::in_data_dir().load(name)
+            };
+            saved.unwrap_or_else(|error| {
+                eprintln!("{}", error);
+                process::exit(1);
+            })
+        });
+        if saved.is_some() && scoresheet.is_some() {
+            eprintln!("--load and --scoresheet both start a game part way, so pick one.");
+            process::exit(1);
+        }
+        // --theme <name> changes how the game looks, as far as the terminal
+        // can show it.
+        let capabilities = FrontendCapabilities::detect();
+        let theme = args.iter().position(|arg| arg == "--theme").map_or(Theme::CLASSIC, |i| {
+            let name = args.get(i + 1).map_or("", String::as_str);
+            Theme::named(name).unwrap_or_else(|message| {
+                eprintln!("{}", message);
+                process::exit(1);
+            })
+        });
+        let theme = capabilities.theme(theme);
+        // --dice-labels <names> names each die, like the colours of a set
+        // on the table, or A to F for letters.
+        let dice_labels = args.iter().position(|arg| arg == "--dice-labels").map(|i| {
+            let names = args.get(i + 1).map_or("", String::as_str);
+            DiceLabels::parse(names).unwrap_or_else(|error| {
+                eprintln!("{}", error);
+                process::exit(1);
+            })
+        });
+        Settings {
+            preset,
+            config,
+            policy: confirm_policy(args),
+            coaching: args.iter().any(|arg| arg == "--coach"),
+            bot_pause: Duration::from_secs_f64(bot_pause),
+            // --dramatic shows each roll a die at a time.
+            reveal_pause: args.iter().any(|arg| arg == "--dramatic").then_some(REVEAL_PAUSE),
+            dice_labels,
+            audit_file,
+            record_file,
+            scoresheet,
+            saved,
+            theme,
+            capabilities,
+        }
+    }
+
+    pub fn console(&self) -> Terminal {
+        // The terminal, in the chosen theme, with lines edited as they're
+        // typed and pausing on Ctrl-C.
+        let input = LineEditor::new(io::stdin().lock(), io::stdin().is_terminal());
+        let mut console = Console::new(Pausable::new(input), io::stdout());
+        console.theme = self.theme;
+        console
+    }
+
+    pub fn rng(&self) -> Box<dyn RngCore> {
+        self.config.rng.rng().unwrap_or_else(|message| {
+            eprintln!("{}", message);
+            process::exit(1);
+        })
+    }
+
+    pub fn save(&self, log: &AuditLog) {
+        // --audit <file> writes an audit log.
+        if let Some(path) = &self.audit_file {
+            if let Err(e) = fs::write(path, log.to_string()) {
+                eprintln!("Couldn't write audit log to {}: {}", path, e);
+            }
+        }
+    }
+
+    pub fn save_record(&self, record: &GameRecord) {
+        // --record <file> writes the game down, to share or read back in.
+        if let Some(path) = &self.record_file {
+            if let Err(e) = fs::write(path, record.to_string()) {
+                eprintln!("Couldn't write the game to {}: {}", path, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,716 @@
+//! The subcommands: playing games, and the tools for studying them.
+
+use farkle::audit::{AuditLog, Audited, RollExport};
+use farkle::bot::{Bot, Handicap};
+use farkle::clock::{Clock, SystemClock};
+use farkle::coach::Coach;
+use farkle::config::RngBackend;
+use farkle::console::Console;
+use farkle::correspondence::Correspondence;
+use farkle::daily::{self, Date, DAILY_RECORD_FILE, DAILY_ROUNDS};
+use farkle::error::FarkleError;
+use farkle::game::{Game, Player, Visibility, WinCondition, DEFAULT_TARGET};
+use farkle::labels::Labelled;
+use farkle::parse::parse_dice;
+use farkle::paths;
+use farkle::saves::SaveSlots;
+use farkle::render::{render_clock, render_other_with, render_points, render_scoresheet, render_with};
+use farkle::rngtest::FaceCounts;
+use farkle::ScoringRules;
+use farkle::seat::{BotSeat, Dramatic, HumanSeat, Paced};
+use farkle::shell::Shell;
+use farkle::notation::{GameRecord, Recorded, RosterChange};
+use farkle::odds;
+use farkle::stats::{GameStats, Tallied};
+use farkle::event::Event;
+use farkle::turn::{play_round_with_rules, play_turn, Decider, TurnEngine};
+use rand::Rng;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process;
+use std::time::Duration;
+use super::app::{Settings, Terminal};
+use super::input::{deadline, players};
+use super::complete::TabCompleted;
+use super::pause;
+use super::render::{turn_heading, welcome_message};
+
+static TRANSCRIPT_FILE: &str = "farkle-transcript.txt";
+
+static ROLLS_FILE: &str = "farkle-rolls.txt";
+
+static PRACTICE_FILE: &str = "farkle_practice.txt";
+
+/// How long each hidden total waits to be shown at the end.
+const TOTAL_REVEAL_PAUSE: Duration = Duration::from_secs(1);
+
+fn data_file(name: &str) -> String {
+    // Where the file called name is kept between games.
+    match paths::data_file(name) {
+        Ok(path) => path.to_string_lossy().to_string(),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
+
+pub fn audit(path: Option<&String>) {
+    // Check an audit log's hash chain and rolls.
+    let Some(path) = path else {
+        eprintln!("Usage: farkle audit <file>");
+        process::exit(1);
+    };
+    let log = fs::read_to_string(path).map_err(FarkleError::io(path)).and_then(|text| AuditLog::parse(&text));
+    match log.and_then(|log| log.verify().map(|_| log)) {
+        Ok(log) if log.rng.is_reproducible() => {
+            println!("{}: all {} rolls check out against {}.", path, log.rolls.len(), log.rng)
+        }
+        Ok(log) => println!(
+            "{}: the chain of {} rolls checks out, but {} can't roll them again to compare.",
+            path,
+            log.rolls.len(),
+            log.rng
+        ),
+        Err(error @ FarkleError::Io { .. }) => {
+            println!("{}", error);
+            process::exit(1);
+        }
+        Err(error) => {
+            println!("{}: {}", path, error);
+            process::exit(1);
+        }
+    }
+}
+
+pub fn verify_rolls(path: Option<&String>) {
+    // Check the dice of a game exported at its end.
+    let Some(path) = path else {
+        eprintln!("Usage: farkle verify-rolls <file>");
+        process::exit(1);
+    };
+    let export = fs::read_to_string(path).map_err(FarkleError::io(path)).and_then(|text| RollExport::parse(&text));
+    match export.and_then(|export| export.verify().map(|_| export)) {
+        Ok(export) if export.log.rng.is_reproducible() => println!(
+            "{}: all {} rolls of the game check out against {}.",
+            path,
+            export.log.rolls.len(),
+            export.log.rng
+        ),
+        Ok(export) => println!(
+            "{}: the game's {} rolls match the chain, but {} can't roll them again to compare.",
+            path,
+            export.log.rolls.len(),
+            export.log.rng
+        ),
+        Err(error @ FarkleError::Io { .. }) => {
+            println!("{}", error);
+            process::exit(1);
+        }
+        Err(error) => {
+            println!("{}: {}", path, error);
+            process::exit(1);
+        }
+    }
+}
+
+pub fn saves(args: &[String]) {
+    // List, delete or rename the games saved by name.
+    let slots = SaveSlots::in_data_dir();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["list"] | [] => slots.list().map(|saves| {
+            if saves.is_empty() {
+                println!("No saved games.");
+            }
+            saves.iter().for_each(|save| println!("{}", save));
+        }),
+        ["delete", name] => slots.delete(name).map(|()| println!("Deleted {}.", name)),
+        ["rename", from, to] => slots.rename(from, to).map(|()| println!("Renamed {} to {}.", from, to)),
+        _ => {
+            eprintln!("Usage: farkle saves [list | delete <name> | rename <name> <new name>]");
+            process::exit(1);
+        }
+    };
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+pub fn score(args: &[String], settings: &Settings) {
+    // Show how dice score under the rules from the flags after them, and
+    // every other way to keep from them.
+    let rules = &settings.config.rules;
+    let dice: Vec<String> = args.iter().take_while(|arg| !arg.starts_with("--")).cloned().collect();
+    let dice = parse_dice(&dice.join(" ")).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        eprintln!("Usage: farkle score <dice> [rule flags]");
+        process::exit(1);
+    });
+    println!("Dice: {:?}", dice);
+    let breakdown = rules.breakdown(&dice);
+    println!("{}", breakdown);
+    let subsets = rules.scoring_subsets(&dice);
+    if !subsets.is_empty() {
+        println!("{}\n\nEvery keep that scores:", breakdown.explain());
+    }
+    for subset in subsets {
+        println!("{:?}: {}", subset.scoring_dice(), subset.explain());
+    }
+}
+
+pub fn explore(args: &[String], settings: &Settings) {
+    // Show the odds after each keep from dice, --at-stake <points> into a
+    // turn, under the rules from the flags.
+    let rules = &settings.config.rules;
+    let dice: Vec<String> = args.iter().take_while(|arg| !arg.starts_with("--")).cloned().collect();
+    let dice = parse_dice(&dice.join(" ")).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        eprintln!("Usage: farkle explore <dice> [--at-stake <points>] [rule flags]");
+        process::exit(1);
+    });
+    let at_stake = match args.iter().position(|arg| arg == "--at-stake") {
+        Some(i) => args.get(i + 1).and_then(|points| points.parse().ok()).unwrap_or_else(|| {
+            eprintln!("--at-stake needs a number of points, like --at-stake 350");
+            process::exit(1);
+        }),
+        None => 0,
+    };
+    let options = odds::explore(rules, &dice, at_stake);
+    if options.is_empty() {
+        println!("{:?} farkles.", dice);
+        return;
+    }
+    println!("{:<20} {:>7} {:>10} {:>12} {:>11}", "Keep", "Points", "Dice left", "Farkle next", "Roll again");
+    for option in options {
+        let keep = format!("{:?}", option.keep.scoring_dice());
+        println!(
+            "{:<20} {:>7} {:>10} {:>11.0}% {:>11.0}",
+            keep,
+            render_points(option.points),
+            option.dice_left,
+            option.farkle_chance * 100.0,
+            option.roll_again
+        );
+    }
+    println!("\nRoll again is the points on average after rolling once more, keeping every scoring die, and banking.");
+}
+
+pub fn chart(args: &[String], settings: &Settings) {
+    // Show when to bank by dice left, under the rules from the flags, as a
+    // table or with --csv as CSV.
+    let rules = &settings.config.rules;
+    let csv = args.iter().any(|arg| arg == "--csv");
+    if csv {
+        println!("dice_left,bank_at,farkle_chance");
+    } else {
+        println!("Bank once you have this many points at stake, by the dice left to roll.\n");
+        println!("{:>9} {:>8} {:>12}", "Dice left", "Bank at", "Farkle odds");
+    }
+    for num_dice in 1..=6 {
+        let threshold = odds::bank_threshold(rules, num_dice);
+        let chance = odds::farkle_chance(rules, num_dice);
+        if csv {
+            println!("{},{},{:.4}", num_dice, threshold, chance);
+        } else {
+            println!("{:>9} {:>8} {:>11.0}%", num_dice, render_points(threshold), chance * 100.0);
+        }
+    }
+}
+
+pub fn rngtest(args: &[String], settings: &Settings) {
+    // Roll --rolls <n> dice, a million by default, and check they're even.
+    let rolls = match args.iter().position(|arg| arg == "--rolls").and_then(|i| args.get(i + 1)) {
+        Some(rolls) => rolls.parse().unwrap_or_else(|_| {
+            eprintln!("--rolls needs a number, like --rolls 1000000");
+            process::exit(1);
+        }),
+        None => 1_000_000,
+    };
+    println!("Rng {}", settings.config.rng);
+    println!("{}", FaceCounts::roll(rolls, &mut settings.rng()));
+}
+
+pub fn practice(args: &[String], settings: &Settings) {
+    // Play a bot that plays better or worse to win --win-rate <percent> of
+    // games, half by default, remembering how it's done between games.
+    let practice_file = data_file(PRACTICE_FILE);
+    let mut practice = match fs::read_to_string(&practice_file) {
+        Ok(text) => Handicap::parse(&text).unwrap_or_else(|error| {
+            eprintln!("{}: {}", practice_file, error);
+            process::exit(1);
+        }),
+        Err(_) => Handicap::new(0.5),
+    };
+    if let Some(i) = args.iter().position(|arg| arg == "--win-rate") {
+        match args.get(i + 1).and_then(|percent| percent.parse::<f64>().ok()) {
+            Some(percent) if (0.0..=100.0).contains(&percent) => practice.win_rate = percent / 100.0,
+            _ => {
+                eprintln!("--win-rate needs a percent, like --win-rate 50");
+                process::exit(1);
+            }
+        }
+    }
+    let you = Player::with_target("You", settings.config.target);
+    let bot = Player::with_target("Sparky", settings.config.target);
+    let winner = play(vec![(you, None), (bot, Some(practice.bot()))], settings);
+    practice.record(winner.as_deref() == Some("Sparky"));
+    if let Some(wins) = practice.wins() {
+        println!("Sparky has won {:.0}% of your last {} games.", wins * 100.0, practice.results.len());
+    }
+    if let Err(e) = fs::write(&practice_file, practice.to_string()) {
+        eprintln!("Couldn't write {}: {}", practice_file, e);
+    }
+}
+
+pub fn shell(settings: &Settings) {
+    // Play a command at a time until the input ends or the player quits.
+    let mut shell = Shell::new(settings.rng(), settings.config.rules.clone());
+    let mut console = settings.console();
+    console.say("Farkle shell. Type 'help' for the commands, or 'quit' to stop.");
+    loop {
+        let line = console.read_line();
+        match line.trim() {
+            "quit" | "exit" => break,
+            "" if line.is_empty() => break,
+            "" => continue,
+            command => match shell.run(command) {
+                Ok(text) => console.say(&text),
+                Err(error) => console.say(&error.to_string()),
+            },
+        }
+    }
+}
+
+pub fn play(players: Vec<(Player, Option<Bot>)>, settings: &Settings) -> Option<String> {
+    // Play a game of Farkle, returning who won.
+    let win_condition = settings.saved.as_ref().map_or(settings.config.win_condition, |saved| saved.win_condition);
+    let demo = !players.is_empty() && players.iter().all(|(_player, bot)| bot.is_some());
+    pause::catch_interrupts();
+    if demo {
+        println!("Demo game: the bots play each other. Press Ctrl-C to pause or stop.\n");
+    } else {
+        println!("{}\n", welcome_message(&settings.config.rules, win_condition, settings.config.target));
+    }
+    let mut rng = settings.rng();
+    let mut log = AuditLog::new(settings.config.rng.clone());
+    if settings.audit_file.is_some() {
+        println!("Dice rng: {}\n", settings.config.rng);
+    }
+    let mut console = settings.console();
+    let mut coach = Coach::new();
+    let mut stats = GameStats::new();
+    let solo = players.is_empty() && settings.scoresheet.is_none() && settings.saved.is_none();
+    let players = match (&settings.saved, &settings.scoresheet) {
+        // A saved game carries on with its players, as bots where they're
+        // named as bots.
+        (Some(saved), _) => {
+            let mut named = players;
+            let mut bot_named = |name: &str| {
+                named.iter_mut().find(|(player, _bot)| player.name == name).and_then(|(_player, bot)| bot.take())
+            };
+            saved.game().players().iter().map(|player| (player.clone(), bot_named(&player.name))).collect()
+        }
+        // Everyone on the sheet plays on, unless they're named.
+        (None, Some(sheet)) if players.is_empty() => {
+            sheet.players().iter().map(|name| (Player::with_target(name, settings.config.target), None)).collect()
+        }
+        _ if solo => vec![(Player::with_target("You", settings.config.target), None)],
+        _ => players,
+    };
+    let players = if settings.saved.is_none() && settings.scoresheet.is_none() {
+        settle_turn_order(players, settings)
+    } else {
+        players
+    };
+    let (players, mut bots): (Vec<Player>, Vec<Option<Bot>>) = players.into_iter().unzip();
+    let mut game = match (&settings.saved, &settings.scoresheet) {
+        (Some(saved), _) => saved.game(),
+        (None, Some(sheet)) => sheet.game(players, win_condition).unwrap_or_else(|error| {
+            eprintln!("{}", error);
+            process::exit(1);
+        }),
+        (None, None) => Game::with_win_condition(players, win_condition),
+    };
+    game.set_visibility(settings.config.visibility);
+    if settings.scoresheet.is_some() || settings.saved.is_some() {
+        let from = if settings.saved.is_some() { "saved game" } else { "scoresheet" };
+        let seen = game.scores_seen_by(None).into_iter().filter(|(name, _score)| !game.has_left(name));
+        let score = |score: Option<u32>| score.map_or("?".to_string(), |score| score.to_string());
+        let scores: Vec<String> = seen.map(|(name, seen)| format!("{} {}", name, score(seen))).collect();
+        println!("Carrying on from the {} in round {}: {}.\n", from, game.round_number(), scores.join(", "));
+    }
+    if let Some(total) = settings.config.clock {
+        game.set_clock(total);
+    }
+    let mut record = match &settings.saved {
+        Some(saved) => saved.clone(),
+        None => {
+            game.set_rules(settings.config.rules.clone());
+            GameRecord::new(game.players().to_vec(), win_condition, settings.config.rules.clone())
+        }
+    };
+    pause::remember(&record);
+    // Say how this game differs from the usual one.
+    let mut rules = settings.config.describe();
+    if let Some(preset) = settings.preset {
+        rules.insert(0, format!("Playing the {} preset.", preset));
+    }
+    rules.extend(settings.capabilities.fallbacks(&game));
+    if !rules.is_empty() {
+        println!("{}\n", rules.join("\n"));
+    }
+    let mut round_changed = game.round_number();
+    let mut round_told = 0;
+    while !game.is_over() {
+        pause::check(&mut console.input);
+        // A casual game can change players as each round after the first
+        // starts.
+        let redemption = game.redemption_round().is_some();
+        if settings.config.joining.is_some() && !solo && !redemption && game.round_number() > round_changed {
+            round_changed = game.round_number();
+            change_roster(&mut game, &mut bots, &mut record, &mut stats, &mut console, settings);
+            if game.is_over() {
+                break;
+            }
+        }
+        // Say when double points rounds are coming, and when they're here.
+        if game.round_number() > round_told && !redemption {
+            round_told = game.round_number();
+            let played = |round: &u32| match win_condition {
+                WinCondition::RoundLimit(rounds) => *round <= rounds,
+                WinCondition::TargetScore => true,
+            };
+            let mut rounds = [round_told, round_told + 1].into_iter().filter(played);
+            let round = rounds.find(|round| game.rules().doubles(*round));
+            if let Some(round) = round {
+                println!("{}\n", render_with(&Event::DoubleRound { round }, &settings.theme));
+            }
+        }
+        let player = game.current_player().clone();
+        match win_condition {
+            _ if redemption => {
+                println!("Redemption round! {} is in last place, with one more round to catch up.", player.name)
+            }
+            WinCondition::RoundLimit(rounds) => println!("Round {} of {}.", game.round_number(), rounds),
+            WinCondition::TargetScore => {}
+        }
+        if !solo {
+            let seen = game.scores_seen_by(settings.capabilities.viewer(Some(&player.name)))[game.current_index()].1;
+            println!("{}\n", turn_heading(&player, seen, win_condition));
+        }
+        if let Some(time_left) = player.time_left {
+            println!("{} left on the clock.\n", render_clock(time_left));
+        }
+        let started = SystemClock.now();
+        let is_bot = bots[game.current_index()].is_some();
+        let completions = console.input.get_ref().completions();
+        let mut seat: Box<dyn Decider> = match &mut bots[game.current_index()] {
+            Some(bot) => Box::new(Paced::new(BotSeat::new(&player.name, bot, &mut console), settings.bot_pause)),
+            None if settings.coaching => {
+                let seat = HumanSeat::with_coach(&mut console, &settings.policy, &mut coach);
+                Box::new(TabCompleted::new(seat, completions))
+            }
+            None => Box::new(TabCompleted::new(HumanSeat::new(&mut console, &settings.policy), completions)),
+        };
+        // Only what's shown is drawn out, not what's logged.
+        let mut dramatic;
+        let seat: &mut dyn Decider = match settings.reveal_pause {
+            Some(pause) => {
+                dramatic = Dramatic::new(&mut *seat, pause);
+                &mut dramatic
+            }
+            None => &mut *seat,
+        };
+        let mut labelled;
+        let seat: &mut dyn Decider = match &settings.dice_labels {
+            Some(labels) => {
+                labelled = Labelled::new(seat, labels);
+                &mut labelled
+            }
+            None => seat,
+        };
+        let mut seat = Audited::new(seat, &mut log);
+        let mut seat = Tallied::new(&mut seat, &mut stats, &player.name);
+        let mut seat = Recorded::new(&mut seat, &mut record, &player.name);
+        seat.on_turn_start(&game.snapshot());
+        let (mut turn, events) = TurnEngine::start_with_rules(&mut rng, game.rules().clone());
+        turn.set_free_rolls(game.free_rolls());
+        for event in &events {
+            seat.on_event(event);
+        }
+        let mut round_score = play_turn(&mut turn, &mut rng, &mut seat);
+        settings.save(&log);
+        let mut summary = turn.summary(&player.name).expect("the turn is over");
+        if game.charge_time(SystemClock.now() - started) {
+            println!("Out of time! That round doesn't count, and the rest are skipped.");
+            round_score = 0;
+            if let Event::RoundSummary { banked, .. } = &mut summary {
+                *banked = 0;
+            }
+        }
+        let bonus = game.catch_up_bonus(round_score);
+        let mut events = vec![summary];
+        if bonus > 0 {
+            events.push(Event::CatchUp { player: player.name.clone(), bonus });
+        }
+        let doubled = game.double_bonus(round_score);
+        if doubled > 0 {
+            events.push(Event::DoublePoints { player: player.name.clone(), bonus: doubled });
+        }
+        let viewer = settings.capabilities.viewer(Some(&player.name));
+        for event in &events {
+            stats.record(&player.name, event);
+            record.record(&player.name, event);
+            let Some(event) = game.redact(event, &player.name, viewer) else {
+                continue;
+            };
+            if is_bot {
+                println!("{}", render_other_with(&player.name, &event, &settings.theme));
+            } else {
+                println!("{}", render_with(&event, &settings.theme));
+            }
+        }
+        let (seat, opening, penalty) = (game.current_index(), game.opening_needed(), game.zilch_penalty(round_score));
+        let busts = game.busts(round_score);
+        game.set_free_rolls(turn.free_rolls());
+        let triggered = game.record_round(round_score);
+        settings.save_record(&record);
+        pause::remember(&record);
+        if round_score > 0 && round_score < opening {
+            println!("That's short of the {} it takes to get on the board, so it doesn't count.", opening);
+        } else if busts {
+            let target = render_points(player.target);
+            println!("That goes past {}, which has to be hit exactly, so it doesn't count.", target);
+        }
+        if penalty > 0 {
+            println!("Three turns in a row scoring nothing: {} points off.", penalty);
+        }
+        // Once the game's over, hidden totals wait for the reveal.
+        let revealing = game.is_over() && settings.config.visibility != Visibility::Open;
+        match game.scores_seen_by(settings.capabilities.viewer(Some(&player.name)))[seat].1 {
+            Some(total) if !revealing => println!("Total score: {}\n", total),
+            _ => println!("Total score: hidden until the end.\n"),
+        }
+        if triggered && !solo {
+            println!("{} reached {} points! Everyone else gets one more round.\n", player.name, player.target);
+        }
+    }
+    // Totals hidden all game come out one at a time, last place first.
+    if settings.config.visibility != Visibility::Open {
+        println!("The totals, from last place to first...\n");
+        for event in game.reveal() {
+            SystemClock.sleep(TOTAL_REVEAL_PAUSE);
+            println!("{}", render_with(&event, &settings.theme));
+        }
+        println!();
+    }
+    match game.winner() {
+        Some(winner) if solo && win_condition != WinCondition::TargetScore => {
+            println!("You scored {}! Thanks for playing!", winner.score)
+        }
+        Some(_) if solo => println!("You win! Thanks for playing!"),
+        Some(winner) => println!("{} wins! Thanks for playing!", winner.name),
+        None => println!("Nobody wins. Thanks for playing!"),
+    }
+    if settings.coaching {
+        println!("{}", coach);
+    }
+    let winner = game.winner().map(|winner| winner.name.clone());
+    if solo {
+        return winner;
+    }
+    println!("\n{}", stats.podium(&game));
+    // Offer a transcript to anyone who played, not watched.
+    if !demo {
+        console.say("\nSave a transcript of the game? (y/n)");
+        if console.read_line().trim().starts_with('y') {
+            let transcript = stats.transcript().join("\n") + "\n\n" + &render_scoresheet(&record) + "\n";
+            let transcript_file = data_file(TRANSCRIPT_FILE);
+            match fs::write(&transcript_file, transcript) {
+                Ok(()) => println!("Saved to {}.", transcript_file),
+                Err(e) => eprintln!("Couldn't write {}: {}", transcript_file, e),
+            }
+        }
+        // Only a game rolled from the start in this run has all its rolls
+        // in the log.
+        if settings.saved.is_none() && settings.scoresheet.is_none() {
+            console.say("Export the dice, so anyone can check them? (y/n)");
+            if console.read_line().trim().starts_with('y') {
+                let rolls_file = data_file(ROLLS_FILE);
+                match fs::write(&rolls_file, RollExport::new(log, record).to_string()) {
+                    Ok(()) => println!("Saved to {}. Check it with farkle verify-rolls {}.", rolls_file, rolls_file),
+                    Err(e) => eprintln!("Couldn't write {}: {}", rolls_file, e),
+                }
+            }
+        }
+    }
+    winner
+}
+
+fn change_roster<R: BufRead, W: Write>(
+    game: &mut Game,
+    bots: &mut Vec<Option<Bot>>,
+    record: &mut GameRecord,
+    stats: &mut GameStats,
+    console: &mut Console<R, W>,
+    settings: &Settings,
+) {
+    // Ask whether anyone's joining or leaving, until nobody is.
+    let Some(join_score) = settings.config.joining else {
+        return;
+    };
+    loop {
+        console.say("Anyone joining or leaving? Enter 'join <name>' or 'leave <name>', or nothing to play on:");
+        let line = console.read_line();
+        let (change, name) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+        let name = name.trim();
+        let changed = match change.to_lowercase().as_str() {
+            "" => break,
+            "join" | "j" if !name.is_empty() => {
+                let score = join_score.score(game);
+                let player = Player::with_target(name, settings.config.target);
+                let player = Player { score, time_left: settings.config.clock, ..player };
+                game.add_player(player.clone()).map(|()| {
+                    bots.push(None);
+                    record.change_roster(RosterChange::Joined(player));
+                    Event::Joined { player: name.to_string(), score }
+                })
+            }
+            "leave" | "l" if !name.is_empty() => game.remove_player(name).map(|()| {
+                record.change_roster(RosterChange::Left(name.to_string()));
+                Event::Left { player: name.to_string() }
+            }),
+            _ => {
+                console.say("Enter 'join' or 'leave' and a name, like 'join Dee'.");
+                continue;
+            }
+        };
+        match changed {
+            Ok(event) => {
+                stats.record(name, &event);
+                console.say(&render_with(&event, &settings.theme));
+                settings.save_record(record);
+                pause::remember(record);
+            }
+            Err(error) => console.say(&error.to_string()),
+        }
+    }
+    console.say("");
+}
+
+fn settle_turn_order(players: Vec<(Player, Option<Bot>)>, settings: &Settings) -> Vec<(Player, Option<Bot>)> {
+    // Put a new game's players in the order --turn-order says, saying how
+    // it was settled.
+    let names: Vec<String> = players.iter().map(|(player, _bot)| player.name.clone()).collect();
+    let mut setup = settings.config.rng.setup_rng().unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(1);
+    });
+    let (order, events) = settings.config.turn_order.arrange(&names, &mut setup);
+    for event in &events {
+        println!("{}", render_with(event, &settings.theme));
+    }
+    if !events.is_empty() {
+        println!();
+    }
+    let mut seats: Vec<Option<(Player, Option<Bot>)>> = players.into_iter().map(Some).collect();
+    order.iter().filter_map(|i| seats[*i].take()).collect()
+}
+
+fn human_round<R: Rng>(rng: &mut R, rules: &ScoringRules, settings: &Settings, console: &mut Terminal) -> u32 {
+    // Play a round by rules at the console, with Tab filling in keeps.
+    let completions = console.input.get_ref().completions();
+    let mut seat = TabCompleted::new(HumanSeat::new(console, &settings.policy), completions);
+    play_round_with_rules(rng, rules.clone(), &mut seat)
+}
+
+pub fn play_daily(share_only: bool, settings: &Settings) {
+    // Play today's daily challenge, or reprint today's result.
+    let date = Date::today();
+    let record_file = data_file(DAILY_RECORD_FILE);
+    if share_only {
+        match daily::find_result(&record_file, date) {
+            Ok(Some(scores)) => println!("{}", daily::share_summary(date, &scores)),
+            Ok(None) => println!("No daily result recorded for {} yet.", date),
+            Err(e) => eprintln!("Couldn't read the daily record: {}", e),
+        }
+        return;
+    }
+    let rounds = WinCondition::RoundLimit(DAILY_ROUNDS as u32);
+    println!("{}\n", welcome_message(&ScoringRules::STANDARD, rounds, DEFAULT_TARGET));
+    println!("Daily challenge for {}: score as much as you can in {} rounds.\n", date, DAILY_ROUNDS);
+    let mut console = settings.console();
+    let mut scores = Vec::new();
+    for round_number in 0..DAILY_ROUNDS {
+        let mut rng = daily::round_rng(date.seed(), round_number);
+        let round_score = human_round(&mut rng, &ScoringRules::STANDARD, settings, &mut console);
+        scores.push(round_score);
+        println!("Round {} score: {}", round_number + 1, round_score);
+        println!("Total score: {}\n", daily::total(&scores));
+    }
+    if let Err(e) = daily::record_result(&record_file, date, &scores) {
+        eprintln!("Couldn't record the result: {}", e);
+    }
+    println!("{}", daily::share_summary(date, &scores));
+}
+
+pub fn play_correspondence(args: &[String], settings: &Settings) {
+    // Take the current player's turn in a game kept in a file, starting the
+    // game from the --player flags if the file isn't there yet.
+    let Some(path) = args.get(2).filter(|arg| !arg.starts_with("--")) else {
+        eprintln!("Usage: farkle correspondence <file> [--player <name> --player <name> ...]");
+        process::exit(1);
+    };
+    let now = || SystemClock.since_1970().as_secs();
+    let mut correspondence = match fs::read_to_string(path) {
+        Ok(text) => Correspondence::parse(&text).unwrap_or_else(|e| {
+            eprintln!("{}: {}", path, e);
+            process::exit(1);
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let (players, bots): (Vec<Player>, Vec<Option<Bot>>) = players(args, settings).into_iter().unzip();
+            if players.len() < 2 || bots.iter().any(Option::is_some) {
+                eprintln!("Start a correspondence game with a --player flag for each person playing.");
+                process::exit(1);
+            }
+            let seed = match settings.config.rng {
+                RngBackend::Seeded(seed) => seed,
+                _ => rand::random(),
+            };
+            let (rules, deadline) = (settings.config.rules.clone(), deadline(args));
+            println!("Started a correspondence game in {}.", path);
+            Correspondence::new(players, settings.config.win_condition, rules, deadline, seed, now())
+        }
+        Err(e) => {
+            eprintln!("{}", FarkleError::io(path)(e));
+            process::exit(1);
+        }
+    };
+    for name in correspondence.expire(now()) {
+        println!("{} missed the deadline, so their turn scored nothing.", name);
+    }
+    let game = correspondence.game();
+    if !game.is_over() {
+        let player = game.current_player();
+        println!("{}'s turn ({} of {} points).\n", player.name, player.score, player.target);
+        let score = human_round(&mut correspondence.rng(), &correspondence.rules, settings, &mut settings.console());
+        correspondence.record(score, now());
+        println!("{} scored {}.\n", player.name, score);
+    }
+    let game = correspondence.game();
+    for player in game.standings() {
+        println!("{}: {} points", player.name, player.score);
+    }
+    match game.winner() {
+        Some(winner) => println!("\n{} wins!", winner.name),
+        None => println!(
+            "\nIt's {}'s turn next, with {} hours to take it.",
+            game.current_player().name,
+            correspondence.due().saturating_sub(now()) / 3600
+        ),
+    }
+    if let Err(e) = fs::write(path, correspondence.to_string()) {
+        eprintln!("Couldn't save the game to {}: {}", path, e);
+    }
+}
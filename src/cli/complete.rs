@@ -0,0 +1,67 @@
+//! Tab at the keep prompt: cycling through the keeps that score, typed as
+//! the positions to keep. The line editor asks for them.
+
+use farkle::event::Event;
+use farkle::game::GameSnapshot;
+use farkle::parse::keep_completions;
+use farkle::turn::{Decider, PlayerAction, TurnSnapshot};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The keeps Tab cycles through while a player picks one, shared by the
+/// line editor reading the keep and the decider offering them.
+pub type Completions = Rc<RefCell<Vec<String>>>;
+
+pub fn completing(completions: &[String], typed: &str) -> Vec<String> {
+    // The keeps offered that start with what's typed, or all of them if
+    // none do.
+    let matching: Vec<String> = completions.iter().filter(|keep| keep.starts_with(typed)).cloned().collect();
+    if matching.is_empty() {
+        completions.to_vec()
+    } else {
+        matching
+    }
+}
+
+/// A decider whose keep can be filled in with Tab, from the keeps of only
+/// scoring dice.
+pub struct TabCompleted<D> {
+    decider: D,
+    completions: Completions,
+}
+
+impl<D: Decider> TabCompleted<D> {
+    pub fn new(decider: D, completions: Completions) -> TabCompleted<D> {
+        TabCompleted { decider, completions }
+    }
+
+    fn offer(&self, completions: Vec<String>) {
+        // Tab cycles through completions until the next offer.
+        *self.completions.borrow_mut() = completions;
+    }
+}
+
+impl<D: Decider> Decider for TabCompleted<D> {
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> PlayerAction {
+        self.offer(keep_completions(dice, &turn.rules));
+        let action = self.decider.choose_keep(dice, turn);
+        self.offer(Vec::new());
+        action
+    }
+
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> PlayerAction {
+        self.decider.choose_bank(turn)
+    }
+
+    fn remark(&mut self) -> Option<String> {
+        self.decider.remark()
+    }
+
+    fn on_turn_start(&mut self, game: &GameSnapshot) {
+        self.decider.on_turn_start(game);
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        self.decider.on_event(event);
+    }
+}
@@ -0,0 +1,339 @@
+//! Reading the flags into players, rules and the rest of the settings.
+
+use farkle::bot::{bot_names, seat_together, Bot, Difficulty, Personality};
+use farkle::config::{GameConfig, JoinScore, RngBackend, TurnOrder};
+use farkle::confirm::{ConfirmPolicy, DEFAULT_CONFIRM_THRESHOLD};
+use farkle::error::FarkleError;
+use farkle::game::{Player, WinCondition, MARATHON_TARGET};
+use farkle::parse::parse_player;
+use farkle::{KindScoring, ScoringRules};
+use std::process;
+use std::time::Duration;
+use super::app::Settings;
+
+static BOT_BANK_AT: u32 = 500;
+
+pub fn confirm_policy(args: &[String]) -> ConfirmPolicy {
+    // --confirm asks before risky choices, --confirm-over <points> sets when.
+    let threshold = args.iter().position(|arg| arg == "--confirm-over").map(|i| {
+        args.get(i + 1).and_then(|points| points.parse().ok()).unwrap_or_else(|| {
+            eprintln!("--confirm-over needs a number of points, like --confirm-over 1000");
+            process::exit(1);
+        })
+    });
+    match threshold {
+        Some(threshold) => ConfirmPolicy::with_threshold(threshold),
+        None if args.iter().any(|arg| arg == "--confirm") => {
+            ConfirmPolicy::with_threshold(DEFAULT_CONFIRM_THRESHOLD)
+        }
+        None => ConfirmPolicy::never(),
+    }
+}
+
+pub fn default_target(args: &[String], default: u32) -> u32 {
+    // --marathon races to 100,000 instead.
+    if args.iter().any(|arg| arg == "--marathon") {
+        MARATHON_TARGET
+    } else {
+        default
+    }
+}
+
+pub fn players(args: &[String], settings: &Settings) -> Vec<(Player, Option<Bot>)> {
+    // --player <name>[:<target>] adds a player and --bot <name>[:<target>]
+    // adds a bot, in turn order. --odds-bot adds a bot that plays the odds.
+    let quiet_bots = args.iter().any(|arg| arg == "--quiet-bots");
+    let mut players = Vec::new();
+    let mut bots = 0;
+    for (i, arg) in args.iter().enumerate() {
+        if arg != "--player" && arg != "--bot" && arg != "--odds-bot" {
+            continue;
+        }
+        let mut player = match args.get(i + 1).map(|spec| parse_player(spec)) {
+            Some(Ok(player)) => player,
+            Some(Err(message)) => {
+                eprintln!("{}", message);
+                process::exit(1);
+            }
+            None => {
+                eprintln!("{} needs a name, like {} Ann:12000", arg, arg);
+                process::exit(1);
+            }
+        };
+        if !args[i + 1].contains(':') {
+            player.target = settings.config.target;
+        }
+        let bot = if arg != "--player" {
+            bots += 1;
+            let personality = if bots % 2 == 1 { Personality::cheerful() } else { Personality::grumpy() };
+            let mut bot =
+                if quiet_bots { Bot::new(BOT_BANK_AT) } else { Bot::with_personality(BOT_BANK_AT, personality) };
+            bot.by_odds = arg == "--odds-bot";
+            Some(bot)
+        } else {
+            None
+        };
+        players.push((player, bot));
+    }
+    if args.iter().any(|arg| arg == "--players") {
+        players = fill_table(players, args, settings);
+    } else if !players.is_empty() && players.iter().all(|(_player, bot)| bot.is_some()) {
+        // Playing against bots, so there must be someone to play against them.
+        players.insert(0, (Player::with_target("You", settings.config.target), None));
+    }
+    let seated: Vec<Player> = players.iter().map(|(player, _bot)| player.clone()).collect();
+    if let Err(problems) = GameConfig::validate_players(&seated) {
+        eprintln!("{}", problems);
+        process::exit(1);
+    }
+    players
+}
+
+fn fill_table(
+    players: Vec<(Player, Option<Bot>)>,
+    args: &[String],
+    settings: &Settings,
+) -> Vec<(Player, Option<Bot>)> {
+    // --players <n> fills a table of n with bots, each --difficulty in turn,
+    // around the --humans <n> people playing.
+    let count = |flag: &str| {
+        args.iter().position(|arg| arg == flag).map(|i| {
+            args.get(i + 1).and_then(|count| count.parse::<usize>().ok()).unwrap_or_else(|| {
+                eprintln!("{} needs a number, like {} 4", flag, flag);
+                process::exit(1);
+            })
+        })
+    };
+    let seats = count("--players").unwrap_or_default();
+    let (mut humans, mut bots): (Vec<_>, Vec<_>) = players.into_iter().partition(|(_player, bot)| bot.is_none());
+    let wanted = count("--humans").unwrap_or(humans.len().max(1));
+    if wanted < humans.len() || wanted + bots.len() > seats {
+        let message = format!(
+            "--players {} has no seats for {} people and {} bots. Ask for more players.",
+            seats,
+            wanted.max(humans.len()),
+            bots.len()
+        );
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+    for n in humans.len()..wanted {
+        let name = if wanted == 1 { "You".to_string() } else { format!("Player {}", n + 1) };
+        humans.push((Player::with_target(&name, settings.config.target), None));
+    }
+    let difficulties = match args.iter().position(|arg| arg == "--difficulty") {
+        Some(i) => {
+            let names = args.get(i + 1).map_or("", String::as_str);
+            let parsed: Result<Vec<Difficulty>, _> = names.split(',').map(Difficulty::parse).collect();
+            parsed.unwrap_or_else(|error| {
+                eprintln!("{}", error);
+                process::exit(1);
+            })
+        }
+        None => vec![Difficulty::Normal],
+    };
+    let taken: Vec<String> = humans.iter().chain(&bots).map(|(player, _bot)| player.name.clone()).collect();
+    let taken: Vec<&str> = taken.iter().map(String::as_str).collect();
+    let quiet_bots = args.iter().any(|arg| arg == "--quiet-bots");
+    let names = bot_names(seats - humans.len() - bots.len(), &taken);
+    for (i, name) in names.into_iter().enumerate() {
+        let played = difficulties[i % difficulties.len()].bot();
+        let personality = if bots.len() % 2 == 0 { Personality::cheerful() } else { Personality::grumpy() };
+        let mut bot = if quiet_bots { played.clone() } else { Bot::with_personality(played.bank_at, personality) };
+        bot.by_odds = played.by_odds;
+        bots.push((Player::with_target(&name, settings.config.target), Some(bot)));
+    }
+    seat_together(humans, bots)
+}
+
+pub fn demo_players(args: &[String], settings: &Settings) -> Vec<(Player, Option<Bot>)> {
+    // A careful bot against a bold one.
+    let quiet_bots = args.iter().any(|arg| arg == "--quiet-bots");
+    let bot = |bank_at, personality| {
+        if quiet_bots {
+            Bot::new(bank_at)
+        } else {
+            Bot::with_personality(bank_at, personality)
+        }
+    };
+    vec![
+        (Player::with_target("Robo", settings.config.target), Some(bot(300, Personality::cheerful()))),
+        (Player::with_target("Grumble", settings.config.target), Some(bot(1000, Personality::grumpy()))),
+    ]
+}
+
+pub fn win_condition(args: &[String], default: WinCondition) -> WinCondition {
+    // --rounds <n> plays a fixed number of rounds instead of racing to a score.
+    match args.iter().position(|arg| arg == "--rounds") {
+        Some(i) => match args.get(i + 1).and_then(|rounds| rounds.parse().ok()) {
+            Some(rounds) => WinCondition::RoundLimit(rounds),
+            _ => {
+                eprintln!("--rounds needs a number of rounds, like --rounds 10");
+                process::exit(1);
+            }
+        },
+        None => default,
+    }
+}
+
+pub fn turn_order(args: &[String], default: TurnOrder) -> TurnOrder {
+    // --turn-order <fixed|random|roll-off> settles who goes first.
+    let Some(i) = args.iter().position(|arg| arg == "--turn-order") else {
+        return default;
+    };
+    TurnOrder::parse(args.get(i + 1).map_or("", String::as_str)).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(1);
+    })
+}
+
+pub fn joining(args: &[String], default: Option<JoinScore>) -> Option<JoinScore> {
+    // --casual lets players join and leave between rounds, and
+    // --join-at <zero|lowest> says what new ones start on.
+    let Some(i) = args.iter().position(|arg| arg == "--join-at") else {
+        let casual = args.iter().any(|arg| arg == "--casual");
+        return if casual { default.or(Some(JoinScore::Zero)) } else { default };
+    };
+    let join_score = JoinScore::parse(args.get(i + 1).map_or("", String::as_str)).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(1);
+    });
+    Some(join_score)
+}
+
+pub fn clock(args: &[String], default: Option<Duration>) -> Option<Duration> {
+    // --clock <minutes> gives each player that long to play all their rounds.
+    let Some(i) = args.iter().position(|arg| arg == "--clock") else {
+        return default;
+    };
+    let minutes = args.get(i + 1).and_then(|minutes| minutes.parse::<f64>().ok()).filter(|minutes| *minutes >= 0.0);
+    match minutes.and_then(|minutes| Duration::try_from_secs_f64(minutes * 60.0).ok()) {
+        Some(clock) => Some(clock),
+        None => {
+            eprintln!("--clock needs a number of minutes, like --clock 5");
+            process::exit(1);
+        }
+    }
+}
+
+pub fn rules(args: &[String], default: ScoringRules) -> ScoringRules {
+    // --hot-streak-bonus <points> scores points for clearing the dice again
+    // in a turn.
+    let hot_streak_bonus = args.iter().position(|arg| arg == "--hot-streak-bonus").map(|i| {
+        args.get(i + 1).and_then(|points| points.parse().ok()).unwrap_or_else(|| {
+            eprintln!("--hot-streak-bonus needs a number of points, like --hot-streak-bonus 500");
+            process::exit(1);
+        })
+    });
+    let hot_streak_bonus = hot_streak_bonus.or(default.hot_streak_bonus);
+    // --catch-up <percent> gives whoever's behind that much more on what
+    // they bank.
+    let catch_up_percent = args.iter().position(|arg| arg == "--catch-up").map(|i| {
+        args.get(i + 1).and_then(|percent| percent.parse().ok()).unwrap_or_else(|| {
+            eprintln!("--catch-up needs a percent, like --catch-up 10");
+            process::exit(1);
+        })
+    });
+    let catch_up_percent = catch_up_percent.or(default.catch_up_percent);
+    // --triples <points,...> sets what three of each face scores, three 1s
+    // first.
+    let triples = match args.iter().position(|arg| arg == "--triples") {
+        Some(i) => {
+            let points: Option<Vec<u32>> = args
+                .get(i + 1)
+                .and_then(|list| list.split(',').map(|points| points.trim().parse().ok()).collect());
+            match points.and_then(|points| points.try_into().ok()) {
+                Some(triples) => triples,
+                None => {
+                    eprintln!("--triples needs six scores, three 1s first, like --triples 300,200,300,400,500,600");
+                    process::exit(1);
+                }
+            }
+        }
+        None => default.triples,
+    };
+    // --opening <points> is the least a player's first turn to count has to
+    // score.
+    let opening_score = args.iter().position(|arg| arg == "--opening").map(|i| {
+        args.get(i + 1).and_then(|points| points.parse().ok()).unwrap_or_else(|| {
+            eprintln!("--opening needs a number of points, like --opening 300");
+            process::exit(1);
+        })
+    });
+    let opening_score = opening_score.or(default.opening_score);
+    // --zilch-penalty <points> takes points for three turns in a row
+    // scoring nothing.
+    let zilch_penalty = args.iter().position(|arg| arg == "--zilch-penalty").map(|i| {
+        args.get(i + 1).and_then(|points| points.parse().ok()).unwrap_or_else(|| {
+            eprintln!("--zilch-penalty needs a number of points, like --zilch-penalty 500");
+            process::exit(1);
+        })
+    });
+    let zilch_penalty = zilch_penalty.or(default.zilch_penalty);
+    // --free-rolls <most> plays with free rolls, up to most held at once.
+    let free_rolls = args.iter().position(|arg| arg == "--free-rolls").map(|i| {
+        args.get(i + 1).and_then(|most| most.parse().ok()).unwrap_or_else(|| {
+            eprintln!("--free-rolls needs the most a player can hold, like --free-rolls 2");
+            process::exit(1);
+        })
+    });
+    let free_rolls = free_rolls.or(default.free_rolls);
+    // --double-every <n> makes every nth round count double.
+    let double_every = args.iter().position(|arg| arg == "--double-every").map(|i| {
+        args.get(i + 1).and_then(|every| every.parse().ok()).unwrap_or_else(|| {
+            eprintln!("--double-every needs a number of rounds, like --double-every 5");
+            process::exit(1);
+        })
+    });
+    let double_every = double_every.or(default.double_every);
+    // --doubling-kinds doubles three of a kind for each die past three,
+    // --must-roll-hot-dice rolls on after clearing the dice,
+    // --exact-finish only ends on the target exactly, and --redemption gives
+    // last place a round at the end to catch up in.
+    let kinds = if args.iter().any(|arg| arg == "--doubling-kinds") { KindScoring::Doubling } else { default.kinds };
+    ScoringRules {
+        hot_streak_bonus,
+        catch_up_percent,
+        triples,
+        opening_score,
+        zilch_penalty,
+        kinds,
+        must_roll_hot_dice: default.must_roll_hot_dice || args.iter().any(|arg| arg == "--must-roll-hot-dice"),
+        exact_finish: default.exact_finish || args.iter().any(|arg| arg == "--exact-finish"),
+        free_rolls,
+        redemption_round: default.redemption_round || args.iter().any(|arg| arg == "--redemption"),
+        double_every,
+    }
+}
+
+pub fn rng(args: &[String]) -> RngBackend {
+    // --rng <rng> or --seed <number> picks the dice.
+    let value = |flag: &str| args.iter().position(|arg| arg == flag).and_then(|i| args.get(i + 1));
+    let rng = match (value("--rng"), value("--seed")) {
+        (Some(rng), _) => RngBackend::parse(rng),
+        (None, Some(seed)) => seed
+            .parse()
+            .map(RngBackend::Seeded)
+            .map_err(|_| FarkleError::InvalidConfig(vec!["--seed needs a number, like --seed 1234".to_string()])),
+        (None, None) => Ok(GameConfig::default().rng),
+    };
+    rng.unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        process::exit(1);
+    })
+}
+
+pub fn deadline(args: &[String]) -> Duration {
+    // --deadline <hours> gives each player that long to take their turn.
+    let Some(i) = args.iter().position(|arg| arg == "--deadline") else {
+        return Duration::from_secs(24 * 3600);
+    };
+    let hours = args.get(i + 1).and_then(|hours| hours.parse::<f64>().ok()).filter(|hours| *hours > 0.0);
+    match hours.and_then(|hours| Duration::try_from_secs_f64(hours * 3600.0).ok()) {
+        Some(deadline) => deadline,
+        None => {
+            eprintln!("--deadline needs a number of hours, like --deadline 24");
+            process::exit(1);
+        }
+    }
+}
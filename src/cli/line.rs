@@ -0,0 +1,348 @@
+//! Editing lines as they're typed at a terminal: moving about the line,
+//! going back through the lines typed before, and Tab filling in keeps.
+//!
+//! Only a terminal can take keys one at a time, so only a terminal has
+//! lines edited. Piped input is read a line at a time as ever, and either
+//! way the game just reads lines.
+
+use super::complete::{completing, Completions};
+use std::io::{self, BufRead, Read, Write};
+use std::rc::Rc;
+
+/// The terminal taking keys one at a time, without echoing them or turning
+/// them into signals, until dropped.
+#[cfg(unix)]
+struct RawMode {
+    saved: libc::termios,
+    raw: libc::termios,
+}
+
+#[cfg(unix)]
+impl RawMode {
+    fn enter() -> Option<RawMode> {
+        let mut saved: libc::termios = unsafe { std::mem::zeroed() };
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut saved) } != 0 {
+            return None;
+        }
+        let mut raw = saved;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+            return None;
+        }
+        Some(RawMode { saved, raw })
+    }
+
+    fn raise(&self, signal: libc::c_int) {
+        // Send signal with the terminal as it was, so whatever it does, like
+        // ending the game, leaves the terminal working.
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.saved);
+            libc::raise(signal);
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.raw);
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.saved) };
+    }
+}
+
+/// A key that does something other than type itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Left,
+    Right,
+    Home,
+    End,
+    Up,
+    Down,
+    Delete,
+    Ignored,
+}
+
+/// A line being typed, with where the cursor is in it.
+#[derive(Debug, Clone, Default)]
+struct Line {
+    chars: Vec<char>,
+    cursor: usize,
+}
+
+impl Line {
+    fn of(text: &str) -> Line {
+        let chars: Vec<char> = text.chars().collect();
+        Line { cursor: chars.len(), chars }
+    }
+
+    fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+}
+
+/// Input edited a line at a time on a terminal, remembering each line
+/// typed for Up and Down to go back through.
+pub struct LineEditor<R> {
+    inner: R,
+    terminal: bool,
+    history: Vec<String>,
+    completions: Completions,
+    line: Vec<u8>,
+    read: usize,
+}
+
+impl<R: BufRead> LineEditor<R> {
+    /// Returns inner, edited as it's typed if it's a terminal.
+    pub fn new(inner: R, terminal: bool) -> LineEditor<R> {
+        let completions = Completions::default();
+        LineEditor { inner, terminal, history: Vec::new(), completions, line: Vec::new(), read: 0 }
+    }
+
+    /// Returns the keeps Tab cycles through, for a decider to offer them.
+    pub fn completions(&self) -> Completions {
+        Rc::clone(&self.completions)
+    }
+
+    fn next_line(&mut self) -> io::Result<()> {
+        // Edit the line in raw mode if the terminal allows it.
+        #[cfg(unix)]
+        if self.terminal {
+            if let Some(raw) = RawMode::enter() {
+                return self.edit_line(&raw);
+            }
+        }
+        self.inner.read_until(b'\n', &mut self.line).map(drop)
+    }
+
+    fn next_byte(&mut self) -> io::Result<Option<u8>> {
+        // The next byte typed, or None at the end of input.
+        loop {
+            match self.inner.fill_buf() {
+                Ok(available) => {
+                    let byte = available.first().copied();
+                    if byte.is_some() {
+                        self.inner.consume(1);
+                    }
+                    return Ok(byte);
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    fn next_char(&mut self, first: u8) -> io::Result<char> {
+        // The character starting with first, reading the rest of its bytes.
+        let len = match first {
+            0xF0.. => 4,
+            0xE0.. => 3,
+            0xC0.. => 2,
+            _ => 1,
+        };
+        let mut bytes = vec![first];
+        for _ in 1..len {
+            match self.next_byte()? {
+                Some(byte) => bytes.push(byte),
+                None => break,
+            }
+        }
+        Ok(String::from_utf8_lossy(&bytes).chars().next().unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    fn escape(&mut self) -> io::Result<Key> {
+        // The key an escape sequence stands for, like an arrow key.
+        let mut sequence = String::new();
+        match self.next_byte()? {
+            Some(b'[') => {
+                while let Some(byte) = self.next_byte()? {
+                    sequence.push(byte as char);
+                    if (0x40..=0x7E).contains(&byte) {
+                        break;
+                    }
+                }
+            }
+            Some(b'O') => sequence.extend(self.next_byte()?.map(char::from)),
+            _ => {}
+        }
+        Ok(match sequence.as_str() {
+            "A" => Key::Up,
+            "B" => Key::Down,
+            "C" => Key::Right,
+            "D" => Key::Left,
+            "H" | "1~" | "7~" => Key::Home,
+            "F" | "4~" | "8~" => Key::End,
+            "3~" => Key::Delete,
+            _ => Key::Ignored,
+        })
+    }
+
+    #[cfg(unix)]
+    fn edit_line(&mut self, raw: &RawMode) -> io::Result<()> {
+        // Take keys until Enter, showing the line after each. Tab cycles
+        // through the keeps that start with what was typed, then back to
+        // it, and Up and Down through the lines typed before, then back to
+        // this one.
+        let mut out = io::stdout();
+        let mut line = Line::default();
+        let mut shown = Line::default();
+        let mut typed = String::new();
+        let mut cycle: Option<usize> = None;
+        let mut browsing: Option<usize> = None;
+        let mut draft = Line::default();
+        while let Some(byte) = self.next_byte()? {
+            let key = match byte {
+                b'\n' | b'\r' => {
+                    writeln!(out)?;
+                    let text = line.text();
+                    if !text.trim().is_empty() && self.history.last() != Some(&text) {
+                        self.history.push(text.clone());
+                    }
+                    self.line.extend(text.into_bytes());
+                    self.line.push(b'\n');
+                    return Ok(());
+                }
+                // Ctrl-D on an empty line ends the input, like it would
+                // outside raw mode.
+                4 if line.chars.is_empty() => return Ok(()),
+                4 => Key::Delete,
+                // Ctrl-C pauses the game, or ends it where that can't be
+                // caught, dropping the line typed.
+                3 => {
+                    raw.raise(libc::SIGINT);
+                    line = Line::default();
+                    shown = Line::default();
+                    Key::Ignored
+                }
+                26 => {
+                    raw.raise(libc::SIGTSTP);
+                    Key::Ignored
+                }
+                b'\t' => {
+                    let completions = completing(&self.completions.borrow(), &typed);
+                    let next = cycle.map_or(0, |i| i + 1);
+                    match completions.get(next) {
+                        Some(keep) => {
+                            cycle = Some(next);
+                            line = Line::of(keep);
+                        }
+                        None if cycle.is_some() => {
+                            cycle = None;
+                            line = Line::of(&typed);
+                        }
+                        None => {}
+                    }
+                    show(&mut out, &mut shown, &line)?;
+                    continue;
+                }
+                8 | 127 => {
+                    if line.cursor > 0 {
+                        line.cursor -= 1;
+                        line.chars.remove(line.cursor);
+                    }
+                    Key::Ignored
+                }
+                // Ctrl-A and Ctrl-E go to the start and end, Ctrl-U and
+                // Ctrl-K clear before and after the cursor.
+                1 => Key::Home,
+                5 => Key::End,
+                21 => {
+                    line.chars.drain(..line.cursor);
+                    line.cursor = 0;
+                    Key::Ignored
+                }
+                11 => {
+                    line.chars.truncate(line.cursor);
+                    Key::Ignored
+                }
+                27 => self.escape()?,
+                _ if byte < 0x20 => Key::Ignored,
+                _ => {
+                    let c = self.next_char(byte)?;
+                    line.chars.insert(line.cursor, c);
+                    line.cursor += 1;
+                    Key::Ignored
+                }
+            };
+            match key {
+                Key::Left => line.cursor = line.cursor.saturating_sub(1),
+                Key::Right => line.cursor = (line.cursor + 1).min(line.chars.len()),
+                Key::Home => line.cursor = 0,
+                Key::End => line.cursor = line.chars.len(),
+                Key::Delete if line.cursor < line.chars.len() => {
+                    line.chars.remove(line.cursor);
+                }
+                Key::Up if !self.history.is_empty() => {
+                    let i = match browsing {
+                        Some(i) => i.saturating_sub(1),
+                        None => {
+                            draft = line.clone();
+                            self.history.len() - 1
+                        }
+                    };
+                    browsing = Some(i);
+                    line = Line::of(&self.history[i]);
+                }
+                Key::Down => match browsing {
+                    Some(i) if i + 1 < self.history.len() => {
+                        browsing = Some(i + 1);
+                        line = Line::of(&self.history[i + 1]);
+                    }
+                    Some(_) => {
+                        browsing = None;
+                        line = draft.clone();
+                    }
+                    None => {}
+                },
+                _ => {}
+            }
+            typed = line.text();
+            cycle = None;
+            show(&mut out, &mut shown, &line)?;
+        }
+        Ok(())
+    }
+}
+
+fn show<W: Write>(out: &mut W, shown: &mut Line, line: &Line) -> io::Result<()> {
+    // Draw line over what's shown from the first character that changed,
+    // with only backspaces and spaces, so it works on any terminal.
+    let same = shown.chars.iter().zip(&line.chars).take_while(|(a, b)| a == b).count();
+    let mut text = "\x08".repeat(shown.cursor.saturating_sub(same));
+    text.extend(shown.chars.get(shown.cursor..same).unwrap_or_default());
+    text.extend(&line.chars[same..]);
+    let blanks = shown.chars.len().saturating_sub(line.chars.len());
+    text.push_str(&" ".repeat(blanks));
+    text.push_str(&"\x08".repeat(line.chars.len() + blanks - line.cursor));
+    out.write_all(text.as_bytes())?;
+    out.flush()?;
+    shown.clone_from(line);
+    Ok(())
+}
+
+impl<R: BufRead> Read for LineEditor<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for LineEditor<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.read == self.line.len() {
+            self.line.clear();
+            self.read = 0;
+            self.next_line()?;
+        }
+        Ok(&self.line[self.read..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.read = (self.read + amount).min(self.line.len());
+    }
+}
@@ -0,0 +1,9 @@
+//! The command line frontend, over the library.
+
+pub mod app;
+pub mod commands;
+pub mod complete;
+pub mod input;
+pub mod line;
+pub mod pause;
+pub mod render;
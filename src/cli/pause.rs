@@ -0,0 +1,150 @@
+//! Ctrl-C in a game: pausing it to save and quit, carry on or give up,
+//! instead of ending it on the spot.
+//!
+//! The game's saved by name as of the last turn finished, in farkle
+//! notation, so a turn interrupted part way is played again from its first
+//! roll.
+
+use farkle::error::FarkleError;
+use farkle::notation::GameRecord;
+use farkle::saves::SaveSlots;
+use std::io::{self, BufRead, Read};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// The name a game's saved under if the player doesn't give one.
+const DEFAULT_SLOT: &str = "paused";
+
+/// Set by Ctrl-C, until the game pauses.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// The game as of the last turn finished, to save.
+static SAVED: Mutex<Option<GameRecord>> = Mutex::new(None);
+
+#[cfg(unix)]
+extern "C" fn on_interrupt(_signal: libc::c_int) {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+    // Writing is all a signal handler can safely do.
+    let message = b"\nPaused. Press Enter for the options.\n";
+    unsafe { libc::write(libc::STDOUT_FILENO, message.as_ptr().cast(), message.len()) };
+}
+
+pub fn catch_interrupts() {
+    // Ctrl-C pauses the game instead of ending it, where it can be caught.
+    #[cfg(unix)]
+    unsafe {
+        libc::signal(libc::SIGINT, on_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t);
+    }
+}
+
+pub fn remember(record: &GameRecord) {
+    // Keep the game so far, to save if the player quits.
+    *SAVED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(record.clone());
+}
+
+pub fn check<R: BufRead>(input: &mut R) {
+    // Pause, if Ctrl-C's been pressed since the last check.
+    if !INTERRUPTED.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    loop {
+        println!("Enter 's' to save and quit, 'r' to resume, or 'a' to abandon the game:");
+        let mut answer = String::new();
+        let read = input.read_line(&mut answer).unwrap_or(0);
+        match answer.trim().to_lowercase().as_str() {
+            "s" | "save" => save_and_quit(input),
+            "r" | "resume" => {
+                println!("Resumed.");
+                return;
+            }
+            "a" | "abandon" => abandon(),
+            _ if read == 0 => abandon(),
+            _ => {}
+        }
+    }
+}
+
+fn save_and_quit<R: BufRead>(input: &mut R) -> ! {
+    // Save the game so far by name, and stop.
+    let Some(record) = SAVED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone() else {
+        eprintln!("There's no game to save yet.");
+        process::exit(1);
+    };
+    let slots = SaveSlots::in_data_dir();
+    loop {
+        println!("Name the save (Enter for '{}'):", DEFAULT_SLOT);
+        let mut name = String::new();
+        let read = input.read_line(&mut name).unwrap_or(0);
+        let name = if name.trim().is_empty() { DEFAULT_SLOT } else { name.trim() };
+        match slots.save(name, &record) {
+            Ok(()) => quit_saved(name),
+            Err(error @ FarkleError::InvalidInput(_)) if read > 0 => println!("{}", error),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn quit_saved(name: &str) -> ! {
+    println!("Saved as of the last turn finished. Carry on with --load {}.", name);
+    process::exit(0);
+}
+
+fn abandon() -> ! {
+    println!("Game abandoned.");
+    process::exit(0);
+}
+
+/// Input that pauses the game when Ctrl-C's been pressed, before it hands
+/// on the next line.
+pub struct Pausable<R> {
+    inner: R,
+    line: Vec<u8>,
+    read: usize,
+}
+
+impl<R: BufRead> Pausable<R> {
+    pub fn new(inner: R) -> Pausable<R> {
+        Pausable { inner, line: Vec::new(), read: 0 }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+}
+
+impl<R: BufRead> Read for Pausable<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Pausable<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // A line a time, so a line typed after Ctrl-C goes to the pause
+        // and a fresh one to the game.
+        while self.read == self.line.len() {
+            self.line.clear();
+            self.read = 0;
+            if self.inner.read_until(b'\n', &mut self.line)? == 0 {
+                break;
+            }
+            if INTERRUPTED.load(Ordering::SeqCst) {
+                self.line.clear();
+                check(&mut self.inner);
+            }
+        }
+        Ok(&self.line[self.read..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.read = (self.read + amount).min(self.line.len());
+    }
+}
@@ -0,0 +1,55 @@
+//! Text the command line shows that the library doesn't.
+
+use farkle::game::{Player, WinCondition};
+use farkle::render::render_points;
+use farkle::ScoringRules;
+
+static WELCOME_MESSAGE: &str = "
+Welcome to Farkle! The rules are simple. You roll 6 dice and try to get
+scoring combinations.
+
+Scoring combinations are as follows:
+1's: 100 points each
+5's: 50 points each
+3 of a kind: {triples}
+{kinds}
+3 pairs: 1500 points
+straight: 1500 points
+2 triplets: 2500 points
+
+If you'd like to keep the 1st, 3rd, and 5th dice, you would type '135'
+(or '1 3 5', or '1,3,5'). You can also name the dice you want, like
+'one one five'.
+Type 'a' to keep all your scoring dice, 'b' to keep them and bank right
+away, or 's' to see how your roll scores.
+You can roll as many times as you want, but if you
+don't get any scoring combinations, you lose all your points for that turn.
+You can bank your points at any time by entering 'y' instead of picking dice.
+{win}
+Good luck!
+";
+
+pub fn welcome_message(rules: &ScoringRules, win_condition: WinCondition, target: u32) -> String {
+    // The rules, with of a kind scored the way this game plays it, and how
+    // it's won: reaching target, or the most points after some rounds.
+    let win = match win_condition {
+        WinCondition::TargetScore => format!("Reach {} points to win!", render_points(target)),
+        WinCondition::RoundLimit(rounds) => format!("Score the most points in {} rounds to win!", rounds),
+    };
+    WELCOME_MESSAGE
+        .replace("{triples}", &rules.describe_triples())
+        .replace("{kinds}", &rules.describe_kinds().join("\n"))
+        .replace("{win}", &win)
+}
+
+pub fn turn_heading(player: &Player, seen: Option<u32>, win_condition: WinCondition) -> String {
+    // Whose turn it is and where they stand, if that's seen, out of their
+    // target if racing to one.
+    let whose = if player.name == "You" { "Your".to_string() } else { format!("{}'s", player.name) };
+    match (seen, win_condition) {
+        (Some(score), WinCondition::TargetScore) => format!("{} turn ({} of {} points).", whose, score, player.target),
+        (Some(score), _) => format!("{} turn ({} points).", whose, score),
+        (None, WinCondition::TargetScore) => format!("{} turn (aiming for {} points).", whose, player.target),
+        (None, _) => format!("{} turn.", whose),
+    }
+}
@@ -0,0 +1,92 @@
+//! Telling the time and waiting, behind a trait so anything that pauses or
+//! times out can be tested with a clock moved by hand instead of sleeping.
+
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Where the time comes from.
+pub trait Clock {
+    /// Returns the time now, for measuring how long things take.
+    fn now(&self) -> Instant;
+
+    /// Returns the time since 1970, for dates and times saved in files.
+    fn since_1970(&self) -> Duration;
+
+    /// Wait for duration.
+    fn sleep(&self, duration: Duration);
+}
+
+/// The computer's own clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn since_1970(&self) -> Duration {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO)
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// A clock that only moves when it's told to, or slept on. Clones share
+/// the same time, so a test can keep one and hand the other out.
+///
+/// ### Examples
+/// ```
+/// use farkle::clock::{Clock, TestClock};
+/// use std::time::Duration;
+///
+/// let clock = TestClock::new(Duration::from_secs(86_400));
+/// let started = clock.now();
+/// let shared = clock.clone();
+/// shared.sleep(Duration::from_secs(60));
+/// clock.advance(Duration::from_secs(30));
+/// assert_eq!(Duration::from_secs(90), clock.now() - started);
+/// assert_eq!(Duration::from_secs(86_490), shared.since_1970());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TestClock {
+    started: Instant,
+    since_1970: Duration,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl TestClock {
+    /// Returns a clock that starts since_1970 after 1970.
+    pub fn new(since_1970: Duration) -> TestClock {
+        TestClock { started: Instant::now(), since_1970, elapsed: Arc::new(Mutex::new(Duration::ZERO)) }
+    }
+
+    /// Move the clock on by duration.
+    pub fn advance(&self, duration: Duration) {
+        let mut elapsed = self.elapsed.lock().unwrap_or_else(PoisonError::into_inner);
+        *elapsed = elapsed.saturating_add(duration);
+    }
+
+    /// Returns how far the clock has moved.
+    fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clock for TestClock {
+    fn now(&self) -> Instant {
+        self.started + self.elapsed()
+    }
+
+    fn since_1970(&self) -> Duration {
+        self.since_1970 + self.elapsed()
+    }
+
+    /// Moves the clock on without waiting.
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+        std::thread::yield_now();
+    }
+}
@@ -0,0 +1,89 @@
+//! Coaching: pointing out keeps that left points behind.
+
+use crate::ScoringRules;
+use std::fmt;
+
+/// Reviews a player's keeps and how many were the best available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Coach {
+    keeps: u32,
+    best_keeps: u32,
+}
+
+impl Coach {
+    /// Returns a coach who hasn't seen any keeps yet.
+    pub fn new() -> Coach {
+        Coach::default()
+    }
+
+    /// Review keeping kept from dice under rules.
+    ///
+    /// Returns a note if some other keep of no more dice scored more.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::ScoringRules;
+    /// let rules = ScoringRules::default();
+    /// let mut coach = farkle::coach::Coach::new();
+    /// assert_eq!(None, coach.review(&rules, &[1, 5, 3, 3, 3, 2], &[3, 3, 3, 1]));
+    /// assert_eq!(
+    ///     Some("Tip: keeping [1, 1] would have scored 200, 50 more than [1, 5].".to_string()),
+    ///     coach.review(&rules, &[1, 5, 1, 2, 4, 6], &[1, 5])
+    /// );
+    /// assert_eq!(Some(0.5), coach.accuracy());
+    ///
+    /// // Three 4s are worth more than 1s and a 5 when they score 1000.
+    /// let rules = ScoringRules { triples: [1000, 200, 300, 1000, 500, 600], ..ScoringRules::default() };
+    /// assert_eq!(
+    ///     Some("Tip: keeping [4, 4, 4] would have scored 1000, 750 more than [1, 1, 5].".to_string()),
+    ///     coach.review(&rules, &[1, 1, 5, 4, 4, 4], &[1, 1, 5])
+    /// );
+    /// ```
+    pub fn review(&mut self, rules: &ScoringRules, dice: &[i16], kept: &[i16]) -> Option<String> {
+        let subsets = rules.scoring_subsets(dice);
+        let best = subsets.iter().find(|subset| subset.scoring_dice().len() <= kept.len())?;
+        self.keeps += 1;
+        let score = rules.score(kept);
+        if score >= best.total() {
+            self.best_keeps += 1;
+            return None;
+        }
+        Some(format!(
+            "Tip: keeping {:?} would have scored {}, {} more than {:?}.",
+            best.scoring_dice(),
+            best.total(),
+            best.total() - score,
+            kept
+        ))
+    }
+
+    /// Returns the number of keeps reviewed.
+    pub fn keeps(&self) -> u32 {
+        self.keeps
+    }
+
+    /// Returns the share of keeps that were the best available, once
+    /// there's been a keep.
+    pub fn accuracy(&self) -> Option<f64> {
+        if self.keeps == 0 {
+            None
+        } else {
+            Some(self.best_keeps as f64 / self.keeps as f64)
+        }
+    }
+}
+
+impl fmt::Display for Coach {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.accuracy() {
+            Some(accuracy) => write!(
+                f,
+                "Coach accuracy: {} of {} keeps were the best available ({:.0}%).",
+                self.best_keeps,
+                self.keeps,
+                accuracy * 100.0
+            ),
+            None => write!(f, "Coach accuracy: no keeps yet."),
+        }
+    }
+}
@@ -0,0 +1,683 @@
+//! Settings for a game.
+
+use crate::error::FarkleError;
+use crate::event::Event;
+use crate::game::{Game, Player, Visibility, WinCondition, DEFAULT_TARGET, MARATHON_TARGET};
+use crate::render::{render_clock, render_points};
+use crate::{roll_dice, seeded_rng};
+use crate::{KindScoring, ScoringRules};
+use crate::sha256::sha256;
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+use std::fs;
+use std::time::Duration;
+
+/// Where the dice's randomness comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RngBackend {
+    /// The thread's rng, reseeded from the OS now and then.
+    Thread,
+    /// ChaCha with a seed, for games that can be rolled again.
+    Seeded(u64),
+    /// Straight from the OS.
+    Os,
+    /// ChaCha seeded with the hash of a file, like one of recorded dice
+    /// rolls or atmospheric noise.
+    EntropyFile(String),
+}
+
+impl RngBackend {
+    /// Parse a backend written with `to_string`.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::config::RngBackend;
+    /// assert_eq!(Ok(RngBackend::Seeded(42)), RngBackend::parse("seed:42"));
+    /// assert_eq!(Ok(RngBackend::EntropyFile("noise.bin".to_string())), RngBackend::parse("file:noise.bin"));
+    /// for backend in [RngBackend::Thread, RngBackend::Os, RngBackend::Seeded(7)] {
+    ///     assert_eq!(Ok(backend.clone()), RngBackend::parse(&backend.to_string()));
+    /// }
+    /// assert!(RngBackend::parse("dice").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<RngBackend, FarkleError> {
+        let text = text.trim();
+        match text {
+            "thread" => return Ok(RngBackend::Thread),
+            "os" => return Ok(RngBackend::Os),
+            _ => {}
+        }
+        if let Some(seed) = text.strip_prefix("seed:") {
+            return seed.parse().map(RngBackend::Seeded).map_err(|_| {
+                FarkleError::InvalidConfig(vec![format!("'{}' isn't a seed; seeds are whole numbers.", seed)])
+            });
+        }
+        match text.strip_prefix("file:") {
+            Some(path) if !path.is_empty() => Ok(RngBackend::EntropyFile(path.to_string())),
+            _ => Err(FarkleError::InvalidConfig(vec![format!(
+                "'{}' isn't an rng. Use thread, os, seed:<number> or file:<path>.",
+                text
+            )])),
+        }
+    }
+
+    /// Returns true if the same backend always rolls the same dice.
+    pub fn is_reproducible(&self) -> bool {
+        matches!(self, RngBackend::Seeded(_) | RngBackend::EntropyFile(_))
+    }
+
+    /// Returns a new rng from the backend.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::config::RngBackend;
+    /// let mut first = RngBackend::Seeded(9).rng().unwrap();
+    /// let mut second = RngBackend::Seeded(9).rng().unwrap();
+    /// assert_eq!(farkle::roll_dice(6, &mut first), farkle::roll_dice(6, &mut second));
+    /// assert!(RngBackend::EntropyFile("no/such/file".to_string()).rng().is_err());
+    /// ```
+    pub fn rng(&self) -> Result<Box<dyn RngCore>, FarkleError> {
+        Ok(match self {
+            RngBackend::Thread => Box::new(rand::thread_rng()),
+            RngBackend::Seeded(seed) => Box::new(seeded_rng(*seed)),
+            RngBackend::Os => Box::new(OsRng),
+            RngBackend::EntropyFile(path) => {
+                let entropy = fs::read(path).map_err(FarkleError::io(path))?;
+                Box::new(ChaCha8Rng::from_seed(sha256(&entropy)))
+            }
+        })
+    }
+
+    /// Returns an rng for setting the game up, like drawing the turn order,
+    /// apart from the dice's so the dice rolled are the same either way.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::config::RngBackend;
+    /// let mut dice = RngBackend::Seeded(9).rng().unwrap();
+    /// let mut setup = RngBackend::Seeded(9).setup_rng().unwrap();
+    /// assert_ne!(farkle::roll_dice(6, &mut dice), farkle::roll_dice(6, &mut setup));
+    /// ```
+    pub fn setup_rng(&self) -> Result<Box<dyn RngCore>, FarkleError> {
+        let apart = |mut rng: ChaCha8Rng| {
+            rng.set_stream(1);
+            rng
+        };
+        Ok(match self {
+            RngBackend::Seeded(seed) => Box::new(apart(seeded_rng(*seed))),
+            RngBackend::EntropyFile(path) => {
+                let entropy = fs::read(path).map_err(FarkleError::io(path))?;
+                Box::new(apart(ChaCha8Rng::from_seed(sha256(&entropy))))
+            }
+            rng => rng.rng()?,
+        })
+    }
+}
+
+impl fmt::Display for RngBackend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RngBackend::Thread => write!(f, "thread"),
+            RngBackend::Seeded(seed) => write!(f, "seed:{}", seed),
+            RngBackend::Os => write!(f, "os"),
+            RngBackend::EntropyFile(path) => write!(f, "file:{}", path),
+        }
+    }
+}
+
+/// How the order players take their turns in is settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnOrder {
+    /// In the order they're given.
+    Fixed,
+    /// Shuffled.
+    Random,
+    /// Everyone rolls a die, highest first, rolling again to settle ties.
+    RollOff,
+}
+
+impl TurnOrder {
+    /// Parse a turn order written with `to_string`.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::config::TurnOrder;
+    /// assert_eq!(Ok(TurnOrder::RollOff), TurnOrder::parse("roll-off"));
+    /// assert!(TurnOrder::parse("alphabetical").is_err());
+    /// ```
+    pub fn parse(name: &str) -> Result<TurnOrder, FarkleError> {
+        match name.trim().to_lowercase().as_str() {
+            "fixed" => Ok(TurnOrder::Fixed),
+            "random" => Ok(TurnOrder::Random),
+            "roll-off" => Ok(TurnOrder::RollOff),
+            _ => Err(FarkleError::InvalidConfig(vec![format!(
+                "'{}' isn't a turn order. Use fixed, random or roll-off.",
+                name.trim()
+            )])),
+        }
+    }
+
+    /// Returns the order players named names play in, as positions in
+    /// names, and the events settling it: each die rolled off, then the
+    /// order.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::config::TurnOrder;
+    /// use farkle::event::Event;
+    /// let names = vec!["Ann".to_string(), "Bob".to_string(), "Cy".to_string()];
+    /// let mut rng = farkle::seeded_rng(3);
+    /// assert_eq!(vec![0, 1, 2], TurnOrder::Fixed.arrange(&names, &mut rng).0);
+    ///
+    /// let (order, events) = TurnOrder::RollOff.arrange(&names, &mut rng);
+    /// let mut sorted = order.clone();
+    /// sorted.sort();
+    /// assert_eq!(vec![0, 1, 2], sorted);
+    /// let players: Vec<String> = order.iter().map(|i| names[*i].clone()).collect();
+    /// assert_eq!(Some(&Event::TurnOrder { players }), events.last());
+    /// // The first player rolled the highest die, the first time round.
+    /// let rolled = |name: &str| events.iter().find_map(|event| match event {
+    ///     Event::RolledForOrder { player, die } if player == name => Some(*die),
+    ///     _ => None,
+    /// });
+    /// assert!(names.iter().all(|name| rolled(name) <= rolled(&names[order[0]])));
+    /// ```
+    pub fn arrange<R: Rng>(&self, names: &[String], rng: &mut R) -> (Vec<usize>, Vec<Event>) {
+        let mut order: Vec<usize> = (0..names.len()).collect();
+        let mut events = Vec::new();
+        match self {
+            TurnOrder::Fixed => return (order, events),
+            TurnOrder::Random => order.shuffle(rng),
+            TurnOrder::RollOff => order = roll_off(order, names, rng, &mut events),
+        }
+        events.push(Event::TurnOrder { players: order.iter().map(|i| names[*i].clone()).collect() });
+        (order, events)
+    }
+}
+
+/// Returns players, positions in names, highest roll first, rolling again
+/// among any who tie.
+fn roll_off<R: Rng>(players: Vec<usize>, names: &[String], rng: &mut R, events: &mut Vec<Event>) -> Vec<usize> {
+    if players.len() < 2 {
+        return players;
+    }
+    let rolled: Vec<(usize, i16)> = players.iter().map(|i| (*i, roll_dice(1, rng)[0])).collect();
+    events.extend(rolled.iter().map(|(i, die)| Event::RolledForOrder { player: names[*i].clone(), die: *die }));
+    (1..=6)
+        .rev()
+        .flat_map(|face| {
+            let tied = rolled.iter().filter(|(_i, die)| *die == face).map(|(i, _die)| *i).collect();
+            roll_off(tied, names, rng, events)
+        })
+        .collect()
+}
+
+impl fmt::Display for TurnOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            TurnOrder::Fixed => "fixed",
+            TurnOrder::Random => "random",
+            TurnOrder::RollOff => "roll-off",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// What a player joining a game part way through starts on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinScore {
+    Zero,
+    /// The lowest score of anyone still playing.
+    Lowest,
+}
+
+impl JoinScore {
+    /// Parse a starting score written with `to_string`.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::config::JoinScore;
+    /// assert_eq!(Ok(JoinScore::Lowest), JoinScore::parse("lowest"));
+    /// assert!(JoinScore::parse("highest").is_err());
+    /// ```
+    pub fn parse(name: &str) -> Result<JoinScore, FarkleError> {
+        match name.trim().to_lowercase().as_str() {
+            "zero" => Ok(JoinScore::Zero),
+            "lowest" => Ok(JoinScore::Lowest),
+            _ => Err(FarkleError::InvalidConfig(vec![format!(
+                "'{}' isn't a score to join on. Use zero or lowest.",
+                name.trim()
+            )])),
+        }
+    }
+
+    /// Returns the score a player joining game starts on.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::config::JoinScore;
+    /// use farkle::game::{Game, Player};
+    /// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+    /// game.record_round(500);
+    /// game.record_round(350);
+    /// assert_eq!(0, JoinScore::Zero.score(&game));
+    /// assert_eq!(350, JoinScore::Lowest.score(&game));
+    /// ```
+    pub fn score(&self, game: &Game) -> u32 {
+        let playing = game.players().iter().filter(|player| !game.has_left(&player.name));
+        match self {
+            JoinScore::Zero => 0,
+            JoinScore::Lowest => playing.map(|player| player.score).min().unwrap_or(0),
+        }
+    }
+}
+
+impl fmt::Display for JoinScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            JoinScore::Zero => "zero",
+            JoinScore::Lowest => "lowest",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How a game is set up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameConfig {
+    pub rng: RngBackend,
+    /// Target for players not given their own.
+    pub target: u32,
+    pub win_condition: WinCondition,
+    /// Time each player has for all their rounds, in a timed game.
+    pub clock: Option<Duration>,
+    pub rules: ScoringRules,
+    pub turn_order: TurnOrder,
+    /// In a casual game, players can join and leave between rounds, and
+    /// new ones start on this.
+    pub joining: Option<JoinScore>,
+    /// Whose totals players see during the game.
+    pub visibility: Visibility,
+}
+
+impl Default for GameConfig {
+    /// Returns the usual race to 10,000, with a random seed so the game
+    /// can be rolled again.
+    fn default() -> Self {
+        GameConfig {
+            rng: RngBackend::Seeded(rand::random()),
+            target: DEFAULT_TARGET,
+            win_condition: WinCondition::TargetScore,
+            clock: None,
+            rules: ScoringRules::default(),
+            turn_order: TurnOrder::Fixed,
+            joining: None,
+            visibility: Visibility::Open,
+        }
+    }
+}
+
+impl GameConfig {
+    /// Returns a line for each way the game differs from the usual one.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::config::Preset;
+    /// let expected = vec![
+    ///     "Target: 5,000 points.",
+    ///     "Hot streak bonus: clearing the dice again in a turn scores 500 more.",
+    ///     "Catch-up bonus: whoever's behind everyone else banks 25% more.",
+    /// ];
+    /// assert_eq!(expected, Preset::Party.config().describe());
+    /// assert!(Preset::Classic.config().describe().is_empty());
+    /// ```
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        match self.win_condition {
+            WinCondition::TargetScore if self.target != DEFAULT_TARGET => {
+                lines.push(format!("Target: {} points.", render_points(self.target)))
+            }
+            WinCondition::RoundLimit(rounds) => lines.push(format!("Highest score after {} rounds wins.", rounds)),
+            _ => {}
+        }
+        if let Some(total) = self.clock {
+            lines.push(format!("Each player has {} on their clock.", render_clock(total)));
+        }
+        match self.turn_order {
+            TurnOrder::Fixed => {}
+            TurnOrder::Random => lines.push("Turn order: drawn at random.".to_string()),
+            TurnOrder::RollOff => lines.push("Turn order: everyone rolls a die, highest first.".to_string()),
+        }
+        match self.visibility {
+            Visibility::Open => {}
+            Visibility::HiddenTotals => {
+                lines.push("Hidden totals: players only see their own until the end.".to_string())
+            }
+            Visibility::Blind => lines.push(
+                "Blind: nobody sees a running total, their own included, until they're shown at the end.".to_string(),
+            ),
+        }
+        match self.joining {
+            None => {}
+            Some(JoinScore::Zero) => lines.push("Casual: players can join or leave between rounds.".to_string()),
+            Some(JoinScore::Lowest) => lines.push(
+                "Casual: players can join or leave between rounds, joining on the lowest score.".to_string(),
+            ),
+        }
+        if self.rules.triples != ScoringRules::STANDARD.triples {
+            lines.push(format!("Three of a kind: {}.", self.rules.describe_triples()));
+        }
+        if let Some(bonus) = self.rules.hot_streak_bonus {
+            lines.push(format!("Hot streak bonus: clearing the dice again in a turn scores {} more.", bonus));
+        }
+        if let Some(percent) = self.rules.catch_up_percent {
+            lines.push(format!("Catch-up bonus: whoever's behind everyone else banks {}% more.", percent));
+        }
+        if let Some(opening) = self.rules.opening_score {
+            lines.push(format!("Opening score: a player's turns count once they bank {} in one.", opening));
+        }
+        if let Some(penalty) = self.rules.zilch_penalty {
+            lines.push(format!("Zilch penalty: three turns in a row scoring nothing lose {} points.", penalty));
+        }
+        if self.rules.kinds == KindScoring::Doubling {
+            lines.push("Four of a kind or more: three of a kind doubled for each die past three.".to_string());
+        }
+        if self.rules.must_roll_hot_dice {
+            lines.push("Hot dice: clearing the dice means rolling all six again.".to_string());
+        }
+        if self.rules.exact_finish {
+            lines.push("Exact finish: a turn that goes past the target doesn't count.".to_string());
+        }
+        if self.rules.redemption_round {
+            lines.push(format!(
+                "Redemption round: last place gets one more round at the end, if they're within {} of first.",
+                render_points(self.rules.best_roll())
+            ));
+        }
+        if let Some(every) = self.rules.double_every {
+            lines.push(format!(
+                "Double points: one round in every {}, announced the round before, counts twice.",
+                every
+            ));
+        }
+        if let Some(most) = self.rules.free_rolls {
+            lines.push(format!(
+                "Free rolls: keeping all six dice in one combination earns one, up to {}, to roll dice again.",
+                most
+            ));
+        }
+        lines
+    }
+
+    /// Check the settings make a game that can be played, and that none of
+    /// them are ignored.
+    ///
+    /// Returns every problem found, each saying how to fix it.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::config::GameConfig;
+    /// use farkle::error::FarkleError;
+    /// use farkle::game::WinCondition;
+    /// use std::time::Duration;
+    /// assert_eq!(Ok(()), GameConfig::default().validate());
+    ///
+    /// let config = GameConfig {
+    ///     target: 25,
+    ///     win_condition: WinCondition::RoundLimit(0),
+    ///     clock: Some(Duration::ZERO),
+    ///     ..GameConfig::default()
+    /// };
+    /// let problems = match config.validate() {
+    ///     Err(FarkleError::InvalidConfig(problems)) => problems,
+    ///     result => panic!("{:?}", result),
+    /// };
+    /// assert_eq!(3, problems.len());
+    /// ```
+    ///
+    /// An opening score can't be more than the target.
+    /// ```
+    /// use farkle::config::GameConfig;
+    /// use farkle::scoring::ScoringRules;
+    /// let rules = ScoringRules { opening_score: Some(1500), ..ScoringRules::default() };
+    /// let config = GameConfig { target: 1000, rules, ..GameConfig::default() };
+    /// assert!(config.validate().unwrap_err().to_string().contains("opening score of 1500"));
+    /// let rules = ScoringRules { opening_score: Some(1000), ..ScoringRules::default() };
+    /// assert_eq!(Ok(()), GameConfig { target: 1000, rules, ..GameConfig::default() }.validate());
+    /// ```
+    ///
+    /// A zilch penalty has to be below the target.
+    /// ```
+    /// use farkle::config::GameConfig;
+    /// use farkle::scoring::ScoringRules;
+    /// let rules = ScoringRules { zilch_penalty: Some(1000), ..ScoringRules::default() };
+    /// let config = GameConfig { target: 1000, rules, ..GameConfig::default() };
+    /// assert!(config.validate().unwrap_err().to_string().contains("zilch penalty of 1000"));
+    /// let rules = ScoringRules { zilch_penalty: Some(950), ..ScoringRules::default() };
+    /// assert_eq!(Ok(()), GameConfig { target: 1000, rules, ..GameConfig::default() }.validate());
+    /// ```
+    pub fn validate(&self) -> Result<(), FarkleError> {
+        let mut problems = Vec::new();
+        match self.win_condition {
+            WinCondition::TargetScore if self.target == 0 => {
+                problems.push("A target of 0 ends the game before anyone plays. Aim for more points.".to_string())
+            }
+            WinCondition::TargetScore if !self.target.is_multiple_of(50) => problems.push(format!(
+                "Scores go up in 50s, so a target of {} plays like {}. Pick a multiple of 50.",
+                self.target,
+                self.target.next_multiple_of(50)
+            )),
+            WinCondition::RoundLimit(0) => {
+                problems.push("A limit of 0 rounds ends the game before anyone plays. Play at least 1.".to_string())
+            }
+            _ => {}
+        }
+        if matches!(self.win_condition, WinCondition::RoundLimit(_)) && self.rules.exact_finish {
+            problems.push(
+                "An exact finish doesn't count when playing a number of rounds. Drop one or the other.".to_string(),
+            );
+        }
+        if matches!(self.win_condition, WinCondition::RoundLimit(_)) && self.target != DEFAULT_TARGET {
+            problems.push(
+                "The target score doesn't count when playing a number of rounds. Drop one or the other.".to_string(),
+            );
+        }
+        if self.rules.double_every == Some(0) {
+            problems.push("Double points rounds can't come every 0 rounds. Make it 1 or more.".to_string());
+        }
+        match self.rules.hot_streak_bonus {
+            Some(0) => problems.push("A hot streak bonus of 0 points does nothing. Leave it out instead.".to_string()),
+            Some(bonus) if !bonus.is_multiple_of(50) => problems.push(format!(
+                "Scores go up in 50s, so a hot streak bonus of {} would leave odd scores. Pick a multiple of 50.",
+                bonus
+            )),
+            _ => {}
+        }
+        match self.rules.catch_up_percent {
+            Some(0) => problems.push("A catch-up bonus of 0% does nothing. Leave it out instead.".to_string()),
+            Some(percent) if percent > 100 => problems.push(format!(
+                "A catch-up bonus of {}% more than doubles what the last player banks. Keep it to 100 or less.",
+                percent
+            )),
+            _ => {}
+        }
+        match self.rules.opening_score {
+            Some(0) => problems.push("An opening score of 0 does nothing. Leave it out instead.".to_string()),
+            Some(opening) if !opening.is_multiple_of(50) => problems.push(format!(
+                "Scores go up in 50s, so an opening score of {} plays like {}. Pick a multiple of 50.",
+                opening,
+                opening.next_multiple_of(50)
+            )),
+            _ => {}
+        }
+        match self.rules.zilch_penalty {
+            Some(0) => problems.push("A zilch penalty of 0 points does nothing. Leave it out instead.".to_string()),
+            Some(penalty) if !penalty.is_multiple_of(50) => problems.push(format!(
+                "Scores go up in 50s, so a zilch penalty of {} would leave odd scores. Pick a multiple of 50.",
+                penalty
+            )),
+            _ => {}
+        }
+        // An opening score or penalty only matters against a target that's played to.
+        if self.win_condition == WinCondition::TargetScore {
+            if let Some(opening) = self.rules.opening_score.filter(|opening| *opening > self.target) {
+                problems.push(format!(
+                    "An opening score of {} is over the target of {}, so getting on the board wins. Lower it.",
+                    opening, self.target
+                ));
+            }
+            if let Some(penalty) = self.rules.zilch_penalty.filter(|penalty| *penalty >= self.target) {
+                problems.push(format!(
+                    "A zilch penalty of {} takes away the whole target of {}. Keep it below the target.",
+                    penalty, self.target
+                ));
+            }
+        }
+        if self.rules.free_rolls == Some(0) {
+            problems.push("Holding at most 0 free rolls does nothing. Leave them out instead.".to_string());
+        }
+        for (face, points) in (1..).zip(self.rules.triples) {
+            if points == 0 {
+                problems.push(format!(
+                    "Three {}s scoring 0 points would farkle a roll that should score. Give them some points.",
+                    face
+                ));
+            } else if !points.is_multiple_of(50) {
+                problems.push(format!("Three {}s scoring {} would leave odd scores. Pick a multiple of 50.", face, points));
+            }
+        }
+        if self.clock == Some(Duration::ZERO) {
+            problems.push("A clock of 0 minutes runs out straight away. Give players some time.".to_string());
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(FarkleError::InvalidConfig(problems))
+        }
+    }
+
+    /// Check no two players share a name, since turns and scores go by
+    /// name.
+    ///
+    /// Returns a problem for each name that's taken more than once.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::config::GameConfig;
+    /// use farkle::game::Player;
+    /// assert_eq!(Ok(()), GameConfig::validate_players(&[Player::new("Ann"), Player::new("Bob")]));
+    /// let players = [Player::new("Ann"), Player::new("Bob"), Player::new("Ann"), Player::new("Ann")];
+    /// let problems = GameConfig::validate_players(&players).unwrap_err().to_string();
+    /// assert_eq!("Two players are called Ann. Give each of them a name of their own.", problems);
+    /// ```
+    pub fn validate_players(players: &[Player]) -> Result<(), FarkleError> {
+        let mut problems = Vec::new();
+        for (i, player) in players.iter().enumerate() {
+            // Say so at the second of a name, and only then.
+            if players[..i].iter().filter(|earlier| earlier.name == player.name).count() == 1 {
+                let problem = format!("Two players are called {}. Give each of them a name of their own.", player.name);
+                problems.push(problem);
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(FarkleError::InvalidConfig(problems))
+        }
+    }
+}
+
+/// Bundled game setups, for groups who want to get going.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// The usual game.
+    Classic,
+    /// Three 1s score 300, and everyone has 20 minutes on their clock.
+    Strict,
+    /// A short game with bonuses to keep it close.
+    Party,
+    /// A race to 100,000.
+    Marathon,
+    /// Zilch, Farkle's sibling: a first bank of 300 or more, and 500 off for
+    /// three turns in a row scoring nothing.
+    Zilch,
+    /// Dix Mille: four of a kind and more doubling, hot dice rolled on, and
+    /// 10,000 hit exactly.
+    DixMille,
+}
+
+impl Preset {
+    /// Every preset, in the order they're listed.
+    pub const ALL: [Preset; 6] =
+        [Preset::Classic, Preset::Strict, Preset::Party, Preset::Marathon, Preset::Zilch, Preset::DixMille];
+
+    /// Parse a preset's name.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::config::Preset;
+    /// assert_eq!(Ok(Preset::Party), Preset::parse("party"));
+    /// assert_eq!(Ok(Preset::Strict), Preset::parse(" Strict"));
+    /// assert!(Preset::parse("chaos").is_err());
+    /// ```
+    pub fn parse(name: &str) -> Result<Preset, FarkleError> {
+        let name = name.trim().to_lowercase();
+        Preset::ALL.into_iter().find(|preset| preset.to_string() == name).ok_or_else(|| {
+            let names: Vec<String> = Preset::ALL.iter().map(Preset::to_string).collect();
+            let problem = format!("'{}' isn't a preset. Pick one of {}.", name, names.join(", "));
+            FarkleError::InvalidConfig(vec![problem])
+        })
+    }
+
+    /// Returns the preset's config, with a random seed.
+    pub fn config(&self) -> GameConfig {
+        let standard = GameConfig::default();
+        match self {
+            Preset::Classic => standard,
+            Preset::Strict => GameConfig {
+                clock: Some(Duration::from_secs(20 * 60)),
+                rules: ScoringRules { triples: [300, 200, 300, 400, 500, 600], ..ScoringRules::default() },
+                ..standard
+            },
+            Preset::Party => GameConfig {
+                target: 5000,
+                rules: ScoringRules {
+                    hot_streak_bonus: Some(500),
+                    catch_up_percent: Some(25),
+                    ..ScoringRules::default()
+                },
+                ..standard
+            },
+            Preset::Marathon => GameConfig { target: MARATHON_TARGET, ..standard },
+            Preset::Zilch => GameConfig {
+                rules: ScoringRules { opening_score: Some(300), zilch_penalty: Some(500), ..ScoringRules::default() },
+                ..standard
+            },
+            Preset::DixMille => GameConfig {
+                rules: ScoringRules {
+                    kinds: KindScoring::Doubling,
+                    must_roll_hot_dice: true,
+                    exact_finish: true,
+                    ..ScoringRules::default()
+                },
+                ..standard
+            },
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Preset::Classic => "classic",
+            Preset::Strict => "strict",
+            Preset::Party => "party",
+            Preset::Marathon => "marathon",
+            Preset::Zilch => "zilch",
+            Preset::DixMille => "dix-mille",
+        };
+        write!(f, "{}", name)
+    }
+}
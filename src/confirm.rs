@@ -0,0 +1,112 @@
+//! Asking players to confirm risky choices.
+
+use crate::console::Console;
+use crate::odds::farkle_chance;
+use crate::ScoringRules;
+use std::io::{BufRead, Write};
+
+/// Default unbanked score above which rolling again is confirmed.
+pub static DEFAULT_CONFIRM_THRESHOLD: u32 = 1000;
+
+/// When to ask a player to confirm a choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ConfirmPolicy {
+    /// Confirm rolling again with more than this many unbanked points.
+    pub roll_again_threshold: Option<u32>,
+    /// Confirm keeps that score less than keeping every scoring die.
+    pub confirm_keeps: bool,
+}
+
+impl ConfirmPolicy {
+    /// Returns a policy that confirms nothing.
+    pub fn never() -> ConfirmPolicy {
+        ConfirmPolicy::default()
+    }
+
+    /// Returns a policy that confirms both kinds of choice.
+    pub fn with_threshold(threshold: u32) -> ConfirmPolicy {
+        ConfirmPolicy {
+            roll_again_threshold: Some(threshold),
+            confirm_keeps: true,
+        }
+    }
+
+    /// Returns a risk summary if rolling num_dice dice under rules with
+    /// turn_score unbanked should be confirmed.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::confirm::ConfirmPolicy;
+    /// use farkle::ScoringRules;
+    /// let (policy, rules) = (ConfirmPolicy::with_threshold(1000), ScoringRules::default());
+    /// assert_eq!(None, policy.roll_again_warning(&rules, 1000, 3));
+    /// assert_eq!(
+    ///     Some("Rolling 3 dice has a 27.8% chance to farkle and lose 1050 points.".to_string()),
+    ///     policy.roll_again_warning(&rules, 1050, 3)
+    /// );
+    /// assert_eq!(None, ConfirmPolicy::never().roll_again_warning(&rules, 5000, 1));
+    /// ```
+    pub fn roll_again_warning(&self, rules: &ScoringRules, turn_score: u32, num_dice: i8) -> Option<String> {
+        let threshold = self.roll_again_threshold?;
+        if turn_score <= threshold {
+            return None;
+        }
+        Some(format!(
+            "Rolling {} dice has a {:.1}% chance to farkle and lose {} points.",
+            num_dice,
+            farkle_chance(rules, num_dice) * 100.0,
+            turn_score
+        ))
+    }
+
+    /// Returns a risk summary if keeping kept from dice under rules should
+    /// be confirmed.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::confirm::ConfirmPolicy;
+    /// use farkle::{KindScoring, ScoringRules};
+    /// let (policy, rules) = (ConfirmPolicy::with_threshold(1000), ScoringRules::default());
+    /// let dice = vec![1, 5, 2, 3, 4, 4];
+    /// assert_eq!(None, policy.keep_warning(&rules, &dice, &vec![1, 5]));
+    /// assert_eq!(
+    ///     Some("Keeping [1] scores 100 but your scoring dice are worth 150.".to_string()),
+    ///     policy.keep_warning(&rules, &dice, &vec![1])
+    /// );
+    /// assert_eq!(
+    ///     Some("Keeping [2] scores nothing and ends your round.".to_string()),
+    ///     policy.keep_warning(&rules, &dice, &vec![2])
+    /// );
+    ///
+    /// let doubling = ScoringRules { kinds: KindScoring::Doubling, ..ScoringRules::default() };
+    /// assert_eq!(
+    ///     Some("Keeping [2, 2, 2] scores 200 but your scoring dice are worth 400.".to_string()),
+    ///     policy.keep_warning(&doubling, &[2, 2, 2, 2, 3, 4], &[2, 2, 2])
+    /// );
+    /// ```
+    pub fn keep_warning(&self, rules: &ScoringRules, dice: &[i16], kept: &[i16]) -> Option<String> {
+        if !self.confirm_keeps {
+            return None;
+        }
+        let kept_score = rules.score(kept);
+        let best_score = rules.score(dice);
+        if kept_score == 0 && best_score > 0 {
+            Some(format!("Keeping {:?} scores nothing and ends your round.", kept))
+        } else if kept_score < best_score {
+            Some(format!(
+                "Keeping {:?} scores {} but your scoring dice are worth {}.",
+                kept, kept_score, best_score
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Ask the player to confirm after showing warning.
+///
+/// Returns true if the player confirms.
+pub fn confirm<R: BufRead, W: Write>(console: &mut Console<R, W>, warning: &str) -> bool {
+    console.say(&format!("{}\nAre you sure? (y/n)", warning));
+    console.read_line().trim().starts_with('y')
+}
@@ -0,0 +1,45 @@
+//! Where the game reads player input and writes its output.
+
+use crate::theme::Theme;
+use std::io::{self, BufRead, Stdin, StdinLock, Stdout, Write};
+
+/// Player input and game output streams.
+pub struct Console<R, W> {
+    pub input: R,
+    pub output: W,
+    /// How prompts and events look.
+    pub theme: Theme,
+}
+
+impl<R: BufRead, W: Write> Console<R, W> {
+    /// Returns a console reading from input and writing to output, in the
+    /// classic theme.
+    pub fn new(input: R, output: W) -> Console<R, W> {
+        Console { input, output, theme: Theme::CLASSIC }
+    }
+
+    /// Returns a line of input, or an empty string at the end of input.
+    ///
+    /// Input that can't be read, like bytes that aren't UTF-8, ends the
+    /// input too, so the game finishes instead of crashing.
+    pub fn read_line(&mut self) -> String {
+        let mut line = String::new();
+        match self.input.read_line(&mut line) {
+            Ok(_) => line,
+            Err(_) => String::new(),
+        }
+    }
+
+    /// Write a line of output.
+    pub fn say(&mut self, text: &str) {
+        writeln!(self.output, "{}", text).expect("Failed to write output");
+    }
+}
+
+impl Console<StdinLock<'static>, Stdout> {
+    /// Returns a console on stdin and stdout.
+    pub fn stdio() -> Console<StdinLock<'static>, Stdout> {
+        let stdin: Stdin = io::stdin();
+        Console::new(stdin.lock(), io::stdout())
+    }
+}
@@ -0,0 +1,197 @@
+//! Correspondence games: each player takes their turn whenever they're
+//! around, and the game waits in a file in between.
+//!
+//! Only the round scores are kept. The game is played again from them when
+//! it's loaded, and the dice for each turn come from the seed and the turn
+//! number, so there's no rng state to keep either.
+
+use crate::daily::round_rng;
+use crate::error::FarkleError;
+use crate::game::{Game, Player, WinCondition};
+use crate::notation::{rule_tags, set_rule_tag};
+use crate::parse::parse_player;
+use crate::ScoringRules;
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+use std::time::Duration;
+
+/// How a turn went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnRecord {
+    /// The player took their turn and scored this.
+    Played(u32),
+    /// The deadline passed, so the turn scored nothing.
+    Forfeited,
+}
+
+/// A correspondence game between turns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Correspondence {
+    pub seed: u64,
+    /// Time each player has to take their turn.
+    pub deadline: Duration,
+    /// Players as they started, in turn order.
+    pub players: Vec<Player>,
+    pub win_condition: WinCondition,
+    /// The rules every turn is played by.
+    pub rules: ScoringRules,
+    /// Every turn so far, in order.
+    pub turns: Vec<TurnRecord>,
+    /// When the current turn came up, in seconds since 1970.
+    pub turn_started: u64,
+}
+
+impl Correspondence {
+    /// Returns a game between players played by rules, started at now in
+    /// seconds since 1970.
+    pub fn new(
+        players: Vec<Player>,
+        win_condition: WinCondition,
+        rules: ScoringRules,
+        deadline: Duration,
+        seed: u64,
+        now: u64,
+    ) -> Correspondence {
+        Correspondence { seed, deadline, players, win_condition, rules, turns: Vec::new(), turn_started: now }
+    }
+
+    /// Returns the game as it stands after the turns so far.
+    pub fn game(&self) -> Game {
+        let mut game = Game::with_win_condition(self.players.clone(), self.win_condition);
+        game.set_rules(self.rules.clone());
+        for turn in &self.turns {
+            match turn {
+                TurnRecord::Played(score) => game.record_round(*score),
+                TurnRecord::Forfeited => game.record_round(0),
+            };
+        }
+        game
+    }
+
+    /// Returns the rng for the current turn's dice.
+    pub fn rng(&self) -> ChaCha8Rng {
+        round_rng(self.seed, self.turns.len() as u64)
+    }
+
+    /// Returns when the current turn is due, in seconds since 1970.
+    pub fn due(&self) -> u64 {
+        self.turn_started.saturating_add(self.deadline.as_secs())
+    }
+
+    /// Forfeit every turn whose deadline passed by now, each next turn
+    /// coming up when the one before was due.
+    ///
+    /// Returns the names of the players who forfeited, in order.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::correspondence::{Correspondence, TurnRecord};
+    /// use farkle::game::{Player, WinCondition};
+    /// use farkle::ScoringRules;
+    /// use std::time::Duration;
+    ///
+    /// let players = vec![Player::new("Ann"), Player::new("Bob")];
+    /// let day = Duration::from_secs(86_400);
+    /// let mut game = Correspondence::new(players, WinCondition::TargetScore, ScoringRules::default(), day, 7, 0);
+    /// game.record(450, 3_600);
+    /// // Bob's turn came up an hour in, and they didn't take it for two days.
+    /// assert_eq!(vec!["Bob", "Ann"], game.expire(3_600 + 2 * 86_400));
+    /// assert_eq!(vec![TurnRecord::Played(450), TurnRecord::Forfeited, TurnRecord::Forfeited], game.turns);
+    /// assert_eq!("Bob", game.game().current_player().name);
+    /// assert_eq!(3_600 + 3 * 86_400, game.due());
+    /// ```
+    pub fn expire(&mut self, now: u64) -> Vec<String> {
+        let mut game = self.game();
+        let mut forfeited = Vec::new();
+        while !game.is_over() && now >= self.due() {
+            forfeited.push(game.current_player().name.clone());
+            game.record_round(0);
+            self.turn_started = self.due();
+            self.turns.push(TurnRecord::Forfeited);
+        }
+        forfeited
+    }
+
+    /// Record the current player's turn scoring score, taken at now.
+    pub fn record(&mut self, score: u32, now: u64) {
+        self.turns.push(TurnRecord::Played(score));
+        self.turn_started = now;
+    }
+
+    /// Parse a game written with `to_string`.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::correspondence::Correspondence;
+    /// use farkle::game::{Player, WinCondition};
+    /// use farkle::{KindScoring, ScoringRules};
+    /// use std::time::Duration;
+    ///
+    /// let players = vec![Player::new("Ann"), Player::with_target("Bob", 8000)];
+    /// let rules = ScoringRules { kinds: KindScoring::Doubling, zilch_penalty: Some(500), ..ScoringRules::default() };
+    /// let hour = Duration::from_secs(3600);
+    /// let mut game = Correspondence::new(players, WinCondition::RoundLimit(5), rules, hour, 7, 100);
+    /// game.record(350, 200);
+    /// game.expire(10_000);
+    /// assert_eq!(Ok(game.clone()), Correspondence::parse(&game.to_string()));
+    /// assert!(Correspondence::parse("farkle-correspondence seed 7\nround lots\n").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<Correspondence, FarkleError> {
+        let corrupt = |message: String| FarkleError::SaveCorrupt(message);
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+        let first = lines.next().unwrap_or_default();
+        let seed = first
+            .strip_prefix("farkle-correspondence seed ")
+            .and_then(|seed| seed.trim().parse().ok())
+            .ok_or_else(|| corrupt("The first line should be 'farkle-correspondence seed <number>'.".to_string()))?;
+        let rules = ScoringRules::default();
+        let mut game = Correspondence::new(Vec::new(), WinCondition::TargetScore, rules, Duration::ZERO, seed, 0);
+        for (i, line) in lines.enumerate() {
+            let bad_line = || corrupt(format!("Line {} doesn't make sense: '{}'.", i + 2, line));
+            let (key, value) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+            let number = || value.parse::<u64>().map_err(|_| bad_line());
+            match key {
+                "deadline" => game.deadline = Duration::from_secs(number()?),
+                "rounds" => game.win_condition = WinCondition::RoundLimit(number()? as u32),
+                "player" => game.players.push(parse_player(value).map_err(|_| bad_line())?),
+                "started" => game.turn_started = number()?,
+                "turn" => game.turns.push(TurnRecord::Played(value.parse().map_err(|_| bad_line())?)),
+                "forfeit" => game.turns.push(TurnRecord::Forfeited),
+                "rule" => {
+                    let (name, value) = value.split_once(' ').ok_or_else(bad_line)?;
+                    if !set_rule_tag(&mut game.rules, name, value).map_err(|_| bad_line())? {
+                        return Err(bad_line());
+                    }
+                }
+                _ => return Err(bad_line()),
+            }
+        }
+        if game.players.is_empty() {
+            return Err(corrupt("The game has no players.".to_string()));
+        }
+        Ok(game)
+    }
+}
+
+impl fmt::Display for Correspondence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "farkle-correspondence seed {}", self.seed)?;
+        writeln!(f, "deadline {}", self.deadline.as_secs())?;
+        if let WinCondition::RoundLimit(rounds) = self.win_condition {
+            writeln!(f, "rounds {}", rounds)?;
+        }
+        for (name, value) in rule_tags(&self.rules) {
+            writeln!(f, "rule {} {}", name, value)?;
+        }
+        for player in &self.players {
+            writeln!(f, "player {}:{}", player.name, player.target)?;
+        }
+        for turn in &self.turns {
+            match turn {
+                TurnRecord::Played(score) => writeln!(f, "turn {}", score)?,
+                TurnRecord::Forfeited => writeln!(f, "forfeit")?,
+            }
+        }
+        writeln!(f, "started {}", self.turn_started)
+    }
+}
@@ -0,0 +1,171 @@
+//! Daily challenge mode.
+//!
+//! Every player on the same (UTC) date gets the same seed, so everyone faces
+//! the same sequence of rolls.
+
+use crate::clock::{Clock, SystemClock};
+use crate::error::FarkleError;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+
+/// Number of rounds in a daily challenge.
+pub static DAILY_ROUNDS: u64 = 10;
+
+/// File daily results are recorded in.
+pub static DAILY_RECORD_FILE: &str = "farkle_daily.txt";
+
+/// A calendar date in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Returns today's date in UTC.
+    pub fn today() -> Date {
+        Date::today_by(&SystemClock)
+    }
+
+    /// Returns today's date in UTC by clock.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::clock::TestClock;
+    /// use farkle::daily::Date;
+    /// use std::time::Duration;
+    /// let clock = TestClock::new(Duration::from_secs(20_740 * 86_400 + 3_600));
+    /// assert_eq!(Date { year: 2026, month: 10, day: 14 }, Date::today_by(&clock));
+    /// ```
+    pub fn today_by(clock: &impl Clock) -> Date {
+        Date::from_days((clock.since_1970().as_secs() / 86_400) as i64)
+    }
+
+    /// Returns the date `days` days after 1970-01-01.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::daily::Date;
+    /// assert_eq!(Date { year: 1970, month: 1, day: 1 }, Date::from_days(0));
+    /// assert_eq!(Date { year: 2000, month: 2, day: 29 }, Date::from_days(11_016));
+    /// assert_eq!(Date { year: 2026, month: 10, day: 14 }, Date::from_days(20_740));
+    /// ```
+    pub fn from_days(days: i64) -> Date {
+        // Shift the epoch to 0000-03-01 so leap days fall at the end of a year.
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+        let year = (year_of_era + era * 400) as i32 + if month <= 2 { 1 } else { 0 };
+        Date { year, month, day }
+    }
+
+    /// Returns the challenge seed for this date.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::daily::Date;
+    /// let date = Date { year: 2026, month: 10, day: 14 };
+    /// assert_eq!(20261014, date.seed());
+    /// ```
+    pub fn seed(&self) -> u64 {
+        self.year as u64 * 10_000 + self.month as u64 * 100 + self.day as u64
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Returns the rng for a round of the challenge with seed.
+///
+/// Each round gets its own stream, so a round's rolls don't depend on how
+/// many dice were rolled in earlier rounds.
+///
+/// ### Examples
+/// ```
+/// use rand::Rng;
+/// let first: u32 = farkle::daily::round_rng(20261014, 0).gen();
+/// let again: u32 = farkle::daily::round_rng(20261014, 0).gen();
+/// let second: u32 = farkle::daily::round_rng(20261014, 1).gen();
+/// assert_eq!(first, again);
+/// assert_ne!(first, second);
+/// ```
+pub fn round_rng(seed: u64, round: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(round);
+    rng
+}
+
+/// Returns the total of the round scores.
+pub fn total(round_scores: &[u32]) -> u32 {
+    round_scores.iter().fold(0, |total, score| total.saturating_add(*score))
+}
+
+/// Append a result to the record file at path.
+///
+/// Each line holds the date, seed, total score and round scores.
+pub fn record_result(path: &str, date: Date, round_scores: &[u32]) -> Result<(), FarkleError> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path).map_err(FarkleError::io(path))?;
+    let rounds: Vec<String> = round_scores.iter().map(|score| score.to_string()).collect();
+    let line = format!("{} {} {} {}", date, date.seed(), total(round_scores), rounds.join(","));
+    writeln!(file, "{}", line).map_err(FarkleError::io(path))
+}
+
+/// Returns the round scores of the latest recorded result for date, if any.
+pub fn find_result(path: &str, date: Date) -> Result<Option<Vec<u32>>, FarkleError> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(FarkleError::io(path)(e)),
+    };
+    let date = date.to_string();
+    let mut found = None;
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(FarkleError::io(path))?;
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 4 || fields[0] != date {
+            continue;
+        }
+        let scores: Result<Vec<u32>, _> = fields[3].split(',').map(str::parse).collect();
+        if let Ok(scores) = scores {
+            found = Some(scores);
+        }
+    }
+    Ok(found)
+}
+
+/// Returns a shareable summary of a daily result.
+///
+/// ### Examples
+/// ```
+/// use farkle::daily::Date;
+/// let date = Date { year: 2026, month: 10, day: 14 };
+/// let summary = farkle::daily::share_summary(date, &[350, 0, 1200]);
+/// assert_eq!(
+///     "Farkle Daily 2026-10-14 (seed 20261014)\nScore: 1550 in 3 rounds\nRounds: 350 0 1200",
+///     summary
+/// );
+/// ```
+pub fn share_summary(date: Date, round_scores: &[u32]) -> String {
+    let rounds: Vec<String> = round_scores.iter().map(|score| score.to_string()).collect();
+    format!(
+        "Farkle Daily {} (seed {})\nScore: {} in {} rounds\nRounds: {}",
+        date,
+        date.seed(),
+        total(round_scores),
+        round_scores.len(),
+        rounds.join(" ")
+    )
+}
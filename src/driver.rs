@@ -0,0 +1,354 @@
+//! Async game driver, for servers running many games at once.
+//!
+//! Player choices are futures, so a game waiting on a remote player doesn't
+//! hold a thread. Nothing here depends on a particular runtime.
+
+use crate::event::Event;
+use crate::game::{Game, GameSnapshot, Player};
+use crate::turn::{Decider, Pending, PlayerAction, TurnEngine, TurnSnapshot};
+use rand::Rng;
+use std::future::{self, Future};
+use std::pin::pin;
+use std::task::Poll;
+
+/// Makes a player's choices during their turn, when they're ready.
+pub trait AsyncDecider {
+    /// Returns what to keep from dice, given the turn so far.
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> impl Future<Output = PlayerAction> + Send;
+
+    /// Returns whether to bank the turn score or roll again.
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> impl Future<Output = PlayerAction> + Send;
+
+    /// Returns something to say about the last choice, if anything.
+    fn remark(&mut self) -> Option<String> {
+        None
+    }
+
+    /// See something that happened during the turn.
+    fn on_event(&mut self, _event: &Event) {}
+
+    /// See the game as the player's turn starts.
+    fn on_turn_start(&mut self, _game: &GameSnapshot) {}
+}
+
+/// A decider that's always ready, like a bot, used where an async one is
+/// needed.
+pub struct Ready<D>(pub D);
+
+impl<D: Decider> AsyncDecider for Ready<D> {
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> impl Future<Output = PlayerAction> + Send {
+        future::ready(self.0.choose_keep(dice, turn))
+    }
+
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> impl Future<Output = PlayerAction> + Send {
+        future::ready(self.0.choose_bank(turn))
+    }
+
+    fn remark(&mut self) -> Option<String> {
+        self.0.remark()
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        self.0.on_event(event);
+    }
+
+    fn on_turn_start(&mut self, game: &GameSnapshot) {
+        self.0.on_turn_start(game);
+    }
+}
+
+/// Pass on the decider's remark about its last choice, if it made one.
+fn pass_on_remark<D: AsyncDecider>(decider: &mut D) {
+    if let Some(text) = decider.remark() {
+        decider.on_event(&Event::Remark { text });
+    }
+}
+
+/// Tell decider what happened.
+fn tell<D: AsyncDecider>(decider: &mut D, events: &[Event]) {
+    for event in events {
+        decider.on_event(event);
+    }
+}
+
+/// Tell every decider what happened to player, as much of it as the
+/// decider's own player can see.
+fn tell_all_seen<D: AsyncDecider>(game: &Game, deciders: &mut [D], player: &str, event: &Event) {
+    for (seat, decider) in game.players().iter().zip(deciders) {
+        if let Some(event) = game.redact(event, player, Some(&seat.name)) {
+            decider.on_event(&event);
+        }
+    }
+}
+
+/// Tell every decider what happened.
+fn tell_all<D: AsyncDecider>(deciders: &mut [D], event: &Event) {
+    for decider in deciders {
+        decider.on_event(event);
+    }
+}
+
+/// Returns the action chosen, or None if timer finishes first.
+async fn before<C, T>(choice: C, timer: T) -> Option<PlayerAction>
+where
+    C: Future<Output = PlayerAction>,
+    T: Future<Output = ()>,
+{
+    let mut choice = pin!(choice);
+    let mut timer = pin!(timer);
+    future::poll_fn(|context| {
+        if let Poll::Ready(action) = choice.as_mut().poll(context) {
+            return Poll::Ready(Some(action));
+        }
+        timer.as_mut().poll(context).map(|()| None)
+    })
+    .await
+}
+
+/// Returns the score for a turn, waiting on decider for each choice.
+pub async fn play_round<R: Rng, D: AsyncDecider>(rng: &mut R, decider: &mut D) -> u32 {
+    let (mut turn, events) = TurnEngine::start(rng);
+    tell(decider, &events);
+    play_turn(&mut turn, rng, decider).await
+}
+
+/// Returns the score for turn once it's over, waiting on decider for each
+/// choice left.
+///
+/// This is cancellation safe: if the future is dropped while waiting on
+/// decider, turn is left waiting on the same choice, to respond to or play
+/// on from later.
+///
+/// ### Examples
+/// ```
+/// use farkle::driver::{play_turn, AsyncDecider};
+/// use farkle::turn::{Pending, PlayerAction, TurnEngine, TurnSnapshot};
+/// use std::future::{self, Future};
+/// use std::pin::pin;
+/// use std::task::{Context, Poll, Waker};
+///
+/// // A player who keeps their dice, then drops off before choosing to bank.
+/// struct Disconnects;
+/// impl AsyncDecider for Disconnects {
+///     fn choose_keep(&mut self, _dice: &[i16], _turn: &TurnSnapshot) -> impl Future<Output = PlayerAction> + Send {
+///         future::ready(PlayerAction::KeepAllScoring)
+///     }
+///     fn choose_bank(&mut self, _turn: &TurnSnapshot) -> impl Future<Output = PlayerAction> + Send {
+///         future::pending()
+///     }
+/// }
+///
+/// let mut rng = farkle::daily::round_rng(1, 0);
+/// let (mut turn, _) = TurnEngine::start(&mut rng);
+/// let mut player = Disconnects;
+/// {
+///     let mut waiting = pin!(play_turn(&mut turn, &mut rng, &mut player));
+///     let mut context = Context::from_waker(Waker::noop());
+///     assert!(waiting.as_mut().poll(&mut context).is_pending());
+///     // Time's up, stop waiting.
+/// }
+/// assert_eq!(&Pending::Bank, turn.pending());
+/// assert_eq!(100, turn.snapshot().score);
+/// turn.respond(turn.default_action(), &mut rng);
+/// assert_eq!(Some(100), turn.score());
+/// ```
+pub async fn play_turn<R: Rng, D: AsyncDecider>(turn: &mut TurnEngine, rng: &mut R, decider: &mut D) -> u32 {
+    play_turn_before(turn, rng, decider, &mut future::pending).await.0
+}
+
+/// Returns the score for turn once it's over, and whether decider ran out
+/// of time, waiting on decider for each choice until a timer from timeout
+/// finishes.
+///
+/// A choice that runs out of time gets the turn's default action.
+async fn play_turn_before<R, D, F, T>(turn: &mut TurnEngine, rng: &mut R, decider: &mut D, timeout: &mut F) -> (u32, bool)
+where
+    R: Rng,
+    D: AsyncDecider,
+    F: FnMut() -> T,
+    T: Future<Output = ()>,
+{
+    let mut timed_out = false;
+    loop {
+        let action = match turn.pending() {
+            Pending::Keep(dice) => before(decider.choose_keep(dice, &turn.snapshot()), timeout()).await,
+            Pending::Bank => before(decider.choose_bank(&turn.snapshot()), timeout()).await,
+            Pending::Over(score) => return (*score, timed_out),
+        };
+        let action = match action {
+            Some(action) => {
+                pass_on_remark(decider);
+                action
+            }
+            None => {
+                timed_out = true;
+                turn.default_action()
+            }
+        };
+        let events = turn.respond(action, rng);
+        tell(decider, &events);
+    }
+}
+
+/// Play game to the end, with deciders[i] choosing for the i'th player.
+///
+/// Returns the winner.
+///
+/// ### Examples
+/// ```
+/// use farkle::bot::Bot;
+/// use farkle::driver::{run_game, Ready};
+/// use farkle::game::{Game, Player, WinCondition};
+/// use std::future::Future;
+/// use std::pin::pin;
+/// use std::task::{Context, Poll, Waker};
+///
+/// // Any executor will do; this one just polls until the game is done.
+/// fn block_on<F: Future>(future: F) -> F::Output {
+///     let mut future = pin!(future);
+///     let mut context = Context::from_waker(Waker::noop());
+///     loop {
+///         if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+///             return output;
+///         }
+///     }
+/// }
+///
+/// let players = vec![Player::new("Ann"), Player::new("Bob")];
+/// let mut game = Game::with_win_condition(players, WinCondition::RoundLimit(3));
+/// let mut bots = vec![Ready(Bot::new(300)), Ready(Bot::new(1000))];
+/// let mut rng = farkle::seeded_rng(5);
+/// let winner = block_on(run_game(&mut game, &mut rng, &mut bots));
+/// assert!(game.is_over());
+/// assert_eq!(game.winner().cloned(), winner);
+/// ```
+pub async fn run_game<R: Rng, D: AsyncDecider>(game: &mut Game, rng: &mut R, deciders: &mut [D]) -> Option<Player> {
+    resume_game(game, &mut None, rng, deciders).await
+}
+
+/// Play game to the end like `run_game`, giving each choice until a timer
+/// from timeout finishes, like a sleep from the server's runtime.
+///
+/// A player who runs out of time has their scoring dice kept and banked,
+/// and every decider is sent `Event::TimedOut` for them.
+///
+/// Returns the winner.
+///
+/// ### Examples
+/// ```
+/// use farkle::driver::{run_game_with_timeout, AsyncDecider};
+/// use farkle::event::Event;
+/// use farkle::game::{Game, Player, WinCondition};
+/// use farkle::turn::{PlayerAction, TurnSnapshot};
+/// use std::future::{self, Future};
+/// use std::pin::pin;
+/// use std::task::{Context, Waker};
+///
+/// // A player who walked away, and counts the idle players they see.
+/// struct Away(usize);
+/// impl AsyncDecider for Away {
+///     fn choose_keep(&mut self, _dice: &[i16], _turn: &TurnSnapshot) -> impl Future<Output = PlayerAction> + Send {
+///         future::pending()
+///     }
+///     fn choose_bank(&mut self, _turn: &TurnSnapshot) -> impl Future<Output = PlayerAction> + Send {
+///         future::pending()
+///     }
+///     fn on_event(&mut self, event: &Event) {
+///         if let Event::TimedOut { .. } = event {
+///             self.0 += 1;
+///         }
+///     }
+/// }
+///
+/// let mut game = Game::with_win_condition(vec![Player::new("Ann")], WinCondition::RoundLimit(2));
+/// let mut players = vec![Away(0)];
+/// let mut rng = farkle::seeded_rng(5);
+/// {
+///     // Time runs out straight away.
+///     let mut waiting = pin!(run_game_with_timeout(&mut game, &mut rng, &mut players, || future::ready(())));
+///     let mut context = Context::from_waker(Waker::noop());
+///     assert!(waiting.as_mut().poll(&mut context).is_ready());
+/// }
+/// assert_eq!(2, players[0].0);
+/// ```
+pub async fn run_game_with_timeout<R, D, F, T>(
+    game: &mut Game,
+    rng: &mut R,
+    deciders: &mut [D],
+    timeout: F,
+) -> Option<Player>
+where
+    R: Rng,
+    D: AsyncDecider,
+    F: FnMut() -> T,
+    T: Future<Output = ()>,
+{
+    resume_game_with_timeout(game, &mut None, rng, deciders, timeout).await
+}
+
+/// Play game to the end from turn, the current player's turn in progress
+/// if there is one.
+///
+/// Like `play_turn` this is cancellation safe: if the future is dropped,
+/// game and turn are left as they were at the choice being waited on.
+///
+/// Returns the winner.
+pub async fn resume_game<R: Rng, D: AsyncDecider>(
+    game: &mut Game,
+    turn: &mut Option<TurnEngine>,
+    rng: &mut R,
+    deciders: &mut [D],
+) -> Option<Player> {
+    resume_game_with_timeout(game, turn, rng, deciders, future::pending).await
+}
+
+/// Play game to the end from turn like `resume_game`, giving each choice
+/// until a timer from timeout finishes, like `run_game_with_timeout`.
+///
+/// Returns the winner.
+pub async fn resume_game_with_timeout<R, D, F, T>(
+    game: &mut Game,
+    turn: &mut Option<TurnEngine>,
+    rng: &mut R,
+    deciders: &mut [D],
+    mut timeout: F,
+) -> Option<Player>
+where
+    R: Rng,
+    D: AsyncDecider,
+    F: FnMut() -> T,
+    T: Future<Output = ()>,
+{
+    assert_eq!(game.players().len(), deciders.len(), "Every player needs a decider");
+    while !game.is_over() {
+        let decider = &mut deciders[game.current_index()];
+        let current = turn.get_or_insert_with(|| {
+            decider.on_turn_start(&game.snapshot());
+            let (mut started, events) = TurnEngine::start_with_rules(rng, game.rules().clone());
+            started.set_free_rolls(game.free_rolls());
+            tell(decider, &events);
+            started
+        });
+        let (score, timed_out) = play_turn_before(current, rng, decider, &mut timeout).await;
+        let player = &game.current_player().name;
+        if timed_out {
+            tell_all(deciders, &Event::TimedOut { player: player.clone() });
+        }
+        if let Some(summary) = current.summary(player) {
+            tell_all_seen(game, deciders, player, &summary);
+        }
+        let bonus = game.catch_up_bonus(score);
+        if bonus > 0 {
+            tell_all_seen(game, deciders, player, &Event::CatchUp { player: player.clone(), bonus });
+        }
+        let doubled = game.double_bonus(score);
+        if doubled > 0 {
+            let event = Event::DoublePoints { player: player.clone(), bonus: doubled };
+            tell_all_seen(game, deciders, player, &event);
+        }
+        game.set_free_rolls(current.free_rolls());
+        *turn = None;
+        game.record_round(score);
+    }
+    game.winner().cloned()
+}
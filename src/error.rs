@@ -0,0 +1,79 @@
+//! What can go wrong, for callers who want to tell problems apart.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// A problem, with a message saying how to fix it where that's possible.
+#[derive(Debug)]
+pub enum FarkleError {
+    /// Dice to keep that can't be kept, or a keep that can't be understood.
+    InvalidKeep(String),
+    /// Input that can't be understood, like at the bank prompt.
+    InvalidInput(String),
+    /// Settings that can't make a game, every problem found.
+    InvalidConfig(Vec<String>),
+    /// Reading or writing the file at path failed.
+    Io { path: String, source: io::Error },
+    /// The other end of a connection broke the rules of the protocol.
+    Protocol(String),
+    /// A saved file, like an audit log, doesn't hold what it should.
+    SaveCorrupt(String),
+}
+
+impl FarkleError {
+    /// Returns a conversion of an io error using the file at path, for
+    /// `map_err`.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::error::FarkleError;
+    /// let error = std::fs::read("no/such/file").map_err(FarkleError::io("no/such/file")).unwrap_err();
+    /// assert!(error.to_string().starts_with("no/such/file: "));
+    /// ```
+    pub fn io(path: &str) -> impl FnOnce(io::Error) -> FarkleError + '_ {
+        move |source| FarkleError::Io { path: path.to_string(), source }
+    }
+}
+
+impl fmt::Display for FarkleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FarkleError::InvalidKeep(message)
+            | FarkleError::InvalidInput(message)
+            | FarkleError::Protocol(message)
+            | FarkleError::SaveCorrupt(message) => write!(f, "{}", message),
+            FarkleError::InvalidConfig(problems) => write!(f, "{}", problems.join("\n")),
+            FarkleError::Io { path, source } => write!(f, "{}: {}", path, source),
+        }
+    }
+}
+
+impl Error for FarkleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FarkleError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Io errors don't compare, so they're equal if they're the same kind of
+/// error with the same file.
+impl PartialEq for FarkleError {
+    fn eq(&self, other: &FarkleError) -> bool {
+        match (self, other) {
+            (FarkleError::InvalidKeep(a), FarkleError::InvalidKeep(b))
+            | (FarkleError::InvalidInput(a), FarkleError::InvalidInput(b))
+            | (FarkleError::Protocol(a), FarkleError::Protocol(b))
+            | (FarkleError::SaveCorrupt(a), FarkleError::SaveCorrupt(b)) => a == b,
+            (FarkleError::InvalidConfig(a), FarkleError::InvalidConfig(b)) => a == b,
+            (FarkleError::Io { path: a, source: a_source }, FarkleError::Io { path: b, source: b_source }) => {
+                a == b && a_source.kind() == b_source.kind()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for FarkleError {}
@@ -0,0 +1,71 @@
+//! Things that happen during a game, for frontends to show.
+
+use crate::ScoreBreakdown;
+
+/// Something that happened during a round, or setting the game up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// Dice were rolled.
+    Rolled { dice: Vec<i16> },
+    /// A player rolled a die to settle who goes first.
+    RolledForOrder { player: String, die: i16 },
+    /// The players take their turns in this order.
+    TurnOrder { players: Vec<String> },
+    /// A player joined the game part way through, starting on score.
+    Joined { player: String, score: u32 },
+    /// A player left the game, their score standing.
+    Left { player: String },
+    /// One die of a roll was shown, the position'th of of, for frontends
+    /// that show dice a die at a time. The whole roll follows as Rolled.
+    DieRevealed { die: i16, position: usize, of: usize },
+    /// All six dice were rolled and came up the same as the player's last
+    /// roll of six, in some order. Told by `Tallied`, from the rolls.
+    SameRollAgain { dice: Vec<i16> },
+    /// These of the labelled dice were rolled, or kept if kept, each with
+    /// the value it shows. Told by `Labelled`, after Rolled or Kept.
+    LabelledDice { dice: Vec<(String, i16)>, kept: bool },
+    /// Dice were kept from the roll, scoring breakdown.
+    Kept { dice: Vec<i16>, breakdown: ScoreBreakdown },
+    /// Every die was kept, so all of them get rolled again.
+    HotDice,
+    /// The dice were cleared again in the same turn, clears times in all,
+    /// scoring a streak bonus.
+    HotStreak { clears: u32, bonus: u32 },
+    /// A free roll was earned, leaving the player holding held.
+    FreeRollEarned { held: u32 },
+    /// A free roll was spent to roll the same dice again, leaving held.
+    FreeRollUsed { held: u32 },
+    /// The dice kept didn't score, losing the round score.
+    Farkled,
+    /// The round score was banked.
+    Banked { score: u32 },
+    /// A choice couldn't be used, so the player is asked again.
+    Rejected { message: String },
+    /// The player said something about their choice.
+    Remark { text: String },
+    /// A player took too long to choose, so their scoring dice were kept
+    /// and banked for them, and they're marked idle.
+    TimedOut { player: String },
+    /// A player behind everyone else got bonus points on top of what they
+    /// banked.
+    CatchUp { player: String, bonus: u32 },
+    /// A player banked in a double points round, so got bonus points on
+    /// top of what they banked.
+    DoublePoints { player: String, bonus: u32 },
+    /// Everything banked in round counts double. Told as the round before
+    /// starts, and as it starts.
+    DoubleRound { round: u32 },
+    /// A total hidden during the game is shown at the end, with the place
+    /// it came in. Told from last place to first.
+    TotalRevealed { player: String, score: u32, place: usize },
+    /// A remote player's turn is starting, and this is what happened since
+    /// their last one.
+    SinceLastTurn { summary: String },
+    /// A player's round is over, after rolls rolls.
+    RoundSummary {
+        player: String,
+        banked: u32,
+        farkled: bool,
+        rolls: u32,
+    },
+}
@@ -0,0 +1,103 @@
+//! Commit-reveal dice for two peers who don't trust each other.
+//!
+//! For each roll both peers pick a secret and send a commitment (its hash).
+//! Once both commitments have arrived they reveal their secrets, check the
+//! other's against its commitment, and roll with a seed made from both. A
+//! peer can't pick their secret after seeing the other's, so neither can
+//! steer the dice.
+
+use crate::error::FarkleError;
+use crate::sha256::{sha256, to_hex};
+use crate::{roll_dice, seeded_rng};
+use rand::Rng;
+
+/// One peer's side of a fair roll.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FairRoll {
+    roll_number: u64,
+    secret: [u8; 32],
+    their_commitment: Option<String>,
+}
+
+/// Returns bytes from lowercase or uppercase hex.
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+        .collect()
+}
+
+impl FairRoll {
+    /// Returns this peer's side of roll roll_number, with a secret from rng.
+    pub fn new<R: Rng>(roll_number: u64, rng: &mut R) -> FairRoll {
+        FairRoll::with_secret(roll_number, rng.gen())
+    }
+
+    /// Returns this peer's side of roll roll_number, with secret.
+    pub fn with_secret(roll_number: u64, secret: [u8; 32]) -> FairRoll {
+        FairRoll { roll_number, secret, their_commitment: None }
+    }
+
+    /// Returns the commitment to send the other peer first.
+    pub fn commitment(&self) -> String {
+        to_hex(&sha256(&self.secret))
+    }
+
+    /// Take the other peer's commitment.
+    pub fn receive_commitment(&mut self, commitment: &str) {
+        self.their_commitment = Some(commitment.trim().to_lowercase());
+    }
+
+    /// Returns the secret to send once the other peer's commitment is in.
+    pub fn reveal(&self) -> Result<String, FarkleError> {
+        match self.their_commitment {
+            Some(_) => Ok(to_hex(&self.secret)),
+            None => Err(FarkleError::Protocol("Wait for the other peer's commitment before revealing.".to_string())),
+        }
+    }
+
+    /// Check the other peer's revealed secret and roll num_dice dice.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::fair::FairRoll;
+    /// let mut alice = FairRoll::with_secret(1, [1; 32]);
+    /// let mut bob = FairRoll::with_secret(1, [2; 32]);
+    /// alice.receive_commitment(&bob.commitment());
+    /// bob.receive_commitment(&alice.commitment());
+    /// let alice_secret = alice.reveal().unwrap();
+    /// let bob_secret = bob.reveal().unwrap();
+    /// let alice_dice = alice.finish(&bob_secret, 6).unwrap();
+    /// assert_eq!(alice_dice, bob.finish(&alice_secret, 6).unwrap());
+    ///
+    /// // A secret that doesn't match its commitment is refused.
+    /// let mut cheat = FairRoll::with_secret(1, [3; 32]);
+    /// cheat.receive_commitment(&bob.commitment());
+    /// assert!(cheat.finish(&alice_secret, 6).is_err());
+    /// ```
+    pub fn finish(&self, their_secret: &str, num_dice: i8) -> Result<Vec<i16>, FarkleError> {
+        let their_commitment = self
+            .their_commitment
+            .as_ref()
+            .ok_or_else(|| FarkleError::Protocol("No commitment from the other peer yet.".to_string()))?;
+        let theirs = from_hex(their_secret.trim())
+            .ok_or_else(|| FarkleError::Protocol("The other peer's secret isn't hex.".to_string()))?;
+        if &to_hex(&sha256(&theirs)) != their_commitment {
+            let message = "The other peer's secret doesn't match their commitment.".to_string();
+            return Err(FarkleError::Protocol(message));
+        }
+        // Both peers must build the same seed, so order the secrets.
+        let mine = self.secret.to_vec();
+        let (first, second) = if mine <= theirs { (mine, theirs) } else { (theirs, mine) };
+        let mut seed_input = self.roll_number.to_be_bytes().to_vec();
+        seed_input.extend(first);
+        seed_input.extend(second);
+        let digest = sha256(&seed_input);
+        let mut seed = [0u8; 8];
+        seed.copy_from_slice(&digest[..8]);
+        Ok(roll_dice(num_dice, &mut seeded_rng(u64::from_be_bytes(seed))))
+    }
+}
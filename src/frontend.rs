@@ -0,0 +1,116 @@
+//! What a frontend can show and do, so the features it can't show fall back
+//! to ones it can: colourless themes, clocks checked between turns, and
+//! scores hidden from everyone on a shared screen.
+
+use crate::game::{Game, Visibility};
+use crate::theme::{DiceStyle, Theme};
+use std::env;
+use std::io::{self, IsTerminal};
+
+/// What a frontend can do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrontendCapabilities {
+    /// Shows ANSI colours.
+    pub color: bool,
+    /// Shows characters past ASCII, like die faces.
+    pub unicode: bool,
+    /// Takes input without stopping the game to wait for it, so a clock can
+    /// end a turn as it runs out.
+    pub async_input: bool,
+    /// Shows each player things the others can't see.
+    pub hidden_info: bool,
+}
+
+impl FrontendCapabilities {
+    /// Text and nothing else, like a log or a pipe.
+    pub const PLAIN: FrontendCapabilities =
+        FrontendCapabilities { color: false, unicode: false, async_input: false, hidden_info: false };
+
+    /// A terminal shared by everyone playing, waiting on each line typed.
+    pub const TERMINAL: FrontendCapabilities = FrontendCapabilities { color: true, unicode: true, ..Self::PLAIN };
+
+    /// A chat room, where everyone sees every message as it's posted.
+    pub const CHAT: FrontendCapabilities = FrontendCapabilities { unicode: true, async_input: true, ..Self::PLAIN };
+
+    /// Returns what stdout can show: a terminal, unless it's a pipe or a
+    /// file, with no colours if `NO_COLOR` is set or `TERM` is "dumb".
+    pub fn detect() -> FrontendCapabilities {
+        if !io::stdout().is_terminal() {
+            return FrontendCapabilities::PLAIN;
+        }
+        let dumb = env::var("TERM").is_ok_and(|term| term == "dumb");
+        let no_color = env::var("NO_COLOR").is_ok_and(|value| !value.is_empty());
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"].iter().find_map(|name| env::var(name).ok());
+        let utf8 = locale.is_some_and(|locale| locale.to_lowercase().replace('-', "").contains("utf8"));
+        FrontendCapabilities { color: !dumb && !no_color, unicode: !dumb && utf8, ..FrontendCapabilities::TERMINAL }
+    }
+
+    /// Returns theme as the frontend can show it: without colours, or with
+    /// digits for die faces.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::frontend::FrontendCapabilities;
+    /// use farkle::theme::Theme;
+    /// let plain = FrontendCapabilities::PLAIN;
+    /// assert_eq!(None, plain.theme(Theme::HIGH_CONTRAST).palette);
+    /// assert_eq!("1 5 3", plain.theme(Theme::FACES).dice(&[1, 5, 3]));
+    /// assert_eq!(Theme::FACES, FrontendCapabilities::TERMINAL.theme(Theme::FACES));
+    /// ```
+    pub fn theme(&self, theme: Theme) -> Theme {
+        let mut shown = theme;
+        if !self.color {
+            shown.palette = None;
+        }
+        if !self.unicode && shown.dice == DiceStyle::Faces {
+            shown.dice = DiceStyle::Digits;
+        }
+        shown
+    }
+
+    /// Returns who to show the game to, for `Game::redact`: viewer, or a
+    /// spectator when everyone sees the same screen.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::frontend::FrontendCapabilities;
+    /// use farkle::game::{Game, Player, Visibility};
+    /// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+    /// game.set_visibility(Visibility::HiddenTotals);
+    /// let bonus = Event::CatchUp { player: "Ann".to_string(), bonus: 50 };
+    /// let shared = FrontendCapabilities::TERMINAL.viewer(Some("Ann"));
+    /// assert_eq!(None, game.redact(&bonus, "Ann", shared));
+    /// let own = FrontendCapabilities { hidden_info: true, ..FrontendCapabilities::TERMINAL }.viewer(Some("Ann"));
+    /// assert_eq!(Some(bonus.clone()), game.redact(&bonus, "Ann", own));
+    /// ```
+    pub fn viewer<'a>(&self, viewer: Option<&'a str>) -> Option<&'a str> {
+        viewer.filter(|_viewer| self.hidden_info)
+    }
+
+    /// Returns what plays differently in game on this frontend, to tell the
+    /// players before it starts.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::frontend::FrontendCapabilities;
+    /// use farkle::game::{Game, Player, Visibility};
+    /// use std::time::Duration;
+    /// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+    /// assert!(FrontendCapabilities::PLAIN.fallbacks(&game).is_empty());
+    /// game.set_clock(Duration::from_secs(300));
+    /// game.set_visibility(Visibility::HiddenTotals);
+    /// assert_eq!(2, FrontendCapabilities::PLAIN.fallbacks(&game).len());
+    /// assert_eq!(1, FrontendCapabilities::CHAT.fallbacks(&game).len());
+    /// ```
+    pub fn fallbacks(&self, game: &Game) -> Vec<String> {
+        let mut fallbacks = Vec::new();
+        if !self.async_input && game.players().iter().any(|player| player.time_left.is_some()) {
+            fallbacks.push("The clock is checked as each turn ends, so a turn can run over before it's stopped.");
+        }
+        if !self.hidden_info && game.visibility() == Visibility::HiddenTotals {
+            fallbacks.push("Everyone shares this screen, so totals are hidden from everyone until the end.");
+        }
+        fallbacks.into_iter().map(String::from).collect()
+    }
+}
@@ -0,0 +1,875 @@
+//! A game between one or more players, taking rounds in turn.
+
+use crate::error::FarkleError;
+use crate::event::Event;
+use crate::ScoringRules;
+use std::cmp::Reverse;
+use std::time::Duration;
+
+/// Score a player needs to reach unless given their own target.
+pub static DEFAULT_TARGET: u32 = 10_000;
+
+/// Target for a marathon game.
+pub static MARATHON_TARGET: u32 = 100_000;
+
+/// A player, their banked score and the score they need to win.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub target: u32,
+    pub score: u32,
+    /// Time left on the player's clock in a timed game.
+    pub time_left: Option<Duration>,
+}
+
+impl Player {
+    /// Returns a player needing the default target score.
+    pub fn new(name: &str) -> Player {
+        Player::with_target(name, DEFAULT_TARGET)
+    }
+
+    /// Returns a player needing target to win.
+    pub fn with_target(name: &str, target: u32) -> Player {
+        Player {
+            name: name.to_string(),
+            target,
+            score: 0,
+            time_left: None,
+        }
+    }
+
+    /// Returns true if the player has reached their target.
+    pub fn reached_target(&self) -> bool {
+        self.score >= self.target
+    }
+
+    /// Returns true if the player's clock has run out.
+    pub fn out_of_time(&self) -> bool {
+        self.time_left == Some(Duration::ZERO)
+    }
+}
+
+/// How a game ends and who wins it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WinCondition {
+    /// Race to each player's target. When a player reaches their target
+    /// everyone else gets one final round.
+    #[default]
+    TargetScore,
+    /// Play this many rounds each; the highest total wins.
+    RoundLimit(u32),
+}
+
+/// Whose banked totals players can see.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Visibility {
+    /// Everyone sees everyone's score.
+    #[default]
+    Open,
+    /// Players only see their own banked total until the game ends. The
+    /// dice, and the points each turn banks, are seen by all.
+    HiddenTotals,
+    /// Nobody sees a banked total, their own included, until the game ends
+    /// and they're revealed. The points each turn banks are seen by all.
+    Blind,
+}
+
+/// A game as the player whose turn it is sees it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameSnapshot {
+    pub player: Player,
+    /// Everyone else, in turn order after the player.
+    pub opponents: Vec<Player>,
+    /// Rounds left including this one, in a game of a number of rounds.
+    pub rounds_left: Option<u32>,
+    /// True if this is the player's last turn.
+    pub last_turn: bool,
+    /// Points the player needs this turn to go into first place, 0 if
+    /// they're in first place already.
+    pub points_to_lead: u32,
+    /// True if the player can't see the other players' scores, which are
+    /// left at 0, along with points_to_lead, and in a blind game their own.
+    pub scores_hidden: bool,
+    /// Points the player's turn has to score to count, 0 once they've
+    /// opened or if there's no opening score.
+    pub opening_needed: u32,
+}
+
+/// Where the redemption round's got to, when playing with one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Redemption {
+    NotYet,
+    /// The player in this seat is playing it.
+    Playing(usize),
+    Played,
+}
+
+/// Players taking rounds in turn until the win condition ends the game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    players: Vec<Player>,
+    win_condition: WinCondition,
+    current: usize,
+    rounds_completed: u32,
+    final_round_trigger: Option<usize>,
+    rules: ScoringRules,
+    visibility: Visibility,
+    /// Whether each player has banked a turn that counted.
+    opened: Vec<bool>,
+    /// Turns in a row each player has scored nothing in, since their last
+    /// zilch penalty.
+    scoreless_turns: Vec<u32>,
+    /// Free rolls each player holds, when playing with them.
+    free_rolls: Vec<u32>,
+    /// Whether each player has left the game. Their seat is kept, with
+    /// their score, but skipped.
+    left: Vec<bool>,
+    redemption: Redemption,
+}
+
+impl Game {
+    /// Returns a new race to the players' targets, in turn order.
+    pub fn new(players: Vec<Player>) -> Game {
+        Game::with_win_condition(players, WinCondition::TargetScore)
+    }
+
+    /// Returns a new game between players, in turn order, ending per
+    /// win_condition.
+    pub fn with_win_condition(players: Vec<Player>, win_condition: WinCondition) -> Game {
+        assert!(!players.is_empty(), "A game needs at least one player");
+        Game {
+            opened: vec![false; players.len()],
+            scoreless_turns: vec![0; players.len()],
+            free_rolls: vec![0; players.len()],
+            left: vec![false; players.len()],
+            players,
+            win_condition,
+            current: 0,
+            rounds_completed: 0,
+            final_round_trigger: None,
+            rules: ScoringRules::default(),
+            visibility: Visibility::Open,
+            redemption: Redemption::NotYet,
+        }
+    }
+
+    /// Returns how the game ends.
+    pub fn win_condition(&self) -> WinCondition {
+        self.win_condition
+    }
+
+    /// Returns the number of the round being played, counting from 1.
+    pub fn round_number(&self) -> u32 {
+        self.rounds_completed + 1
+    }
+
+    /// Returns the players, in turn order.
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// Returns the player whose round it is.
+    pub fn current_player(&self) -> &Player {
+        &self.players[self.current]
+    }
+
+    /// Seat player last in turn order, from their score as it is. They
+    /// take their first turn when it comes round to them.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+    /// game.record_round(300);
+    /// game.record_round(500);
+    /// game.add_player(Player { score: 300, ..Player::new("Cy") }).unwrap();
+    /// game.record_round(0);
+    /// game.record_round(0);
+    /// assert_eq!("Cy", game.current_player().name);
+    /// assert_eq!(300, game.current_player().score);
+    /// assert!(game.add_player(Player::new("Ann")).is_err());
+    /// ```
+    pub fn add_player(&mut self, player: Player) -> Result<(), FarkleError> {
+        if self.players.iter().any(|other| other.name == player.name) {
+            return Err(FarkleError::InvalidInput(format!("There's already a player called {}.", player.name)));
+        }
+        self.players.push(player);
+        self.opened.push(false);
+        self.scoreless_turns.push(0);
+        self.free_rolls.push(0);
+        self.left.push(false);
+        Ok(())
+    }
+
+    /// Take the player called name out of the game. Their score stands, but
+    /// they take no more turns and can't win.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob"), Player::new("Cy")]);
+    /// game.record_round(300);
+    /// game.remove_player("Bob").unwrap();
+    /// assert_eq!("Cy", game.current_player().name);
+    /// assert!(game.has_left("Bob"));
+    /// game.record_round(0);
+    /// game.record_round(0);
+    /// assert_eq!("Cy", game.current_player().name);
+    /// assert!(game.remove_player("Bob").is_err());
+    /// game.remove_player("Ann").unwrap();
+    /// // Somebody has to stay to finish the game.
+    /// assert!(game.remove_player("Cy").is_err());
+    /// ```
+    pub fn remove_player(&mut self, name: &str) -> Result<(), FarkleError> {
+        let Some(seat) = (0..self.players.len()).find(|i| self.players[*i].name == name && !self.left[*i]) else {
+            return Err(FarkleError::InvalidInput(format!("There's nobody called {} playing.", name)));
+        };
+        if self.left.iter().filter(|left| !**left).count() == 1 {
+            return Err(FarkleError::InvalidInput(format!("{} is the last player left.", name)));
+        }
+        self.left[seat] = true;
+        if seat == self.current && !self.is_over() {
+            self.advance();
+        }
+        Ok(())
+    }
+
+    /// Returns true if the player called name has left the game.
+    pub fn has_left(&self, name: &str) -> bool {
+        self.players.iter().zip(&self.left).any(|(player, left)| *left && player.name == name)
+    }
+
+    /// Returns the game as the player whose round it is sees it.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// let mut game = Game::new(vec![Player::with_target("Ann", 1000), Player::new("Bob")]);
+    /// game.record_round(1200);
+    /// let snapshot = game.snapshot();
+    /// assert_eq!("Bob", snapshot.player.name);
+    /// assert!(snapshot.last_turn);
+    /// // Ann is 200 past 1000, so Bob has to get 201 past 10,000.
+    /// assert_eq!(10_201, snapshot.points_to_lead);
+    /// ```
+    pub fn snapshot(&self) -> GameSnapshot {
+        let player = self.current_player().clone();
+        let count = self.players.len();
+        let seats = (1..count).map(|i| (self.current + i) % count).filter(|seat| !self.left[*seat]);
+        let opponents: Vec<Player> = seats.map(|seat| self.players[seat].clone()).collect();
+        let rounds_left = match self.win_condition {
+            WinCondition::RoundLimit(rounds) => Some(rounds.saturating_sub(self.rounds_completed)),
+            WinCondition::TargetScore => None,
+        };
+        // Once someone's reached their target, first place goes furthest
+        // past it, like in standings.
+        let lead = match self.final_round_trigger {
+            Some(_) => opponents
+                .iter()
+                .filter(|opponent| opponent.reached_target())
+                .map(|opponent| i64::from(opponent.score) - i64::from(opponent.target) + i64::from(player.target))
+                .max(),
+            None => opponents.iter().map(|opponent| i64::from(opponent.score)).max(),
+        };
+        let points_to_lead = lead.map_or(0, |lead| (lead + 1 - i64::from(player.score)).clamp(0, u32::MAX.into()));
+        let mut snapshot = GameSnapshot {
+            last_turn: self.final_round_trigger.is_some() || rounds_left == Some(1),
+            rounds_left,
+            points_to_lead: points_to_lead as u32,
+            opponents,
+            player,
+            scores_hidden: false,
+            opening_needed: self.opening_needed(),
+        };
+        if self.visibility != Visibility::Open {
+            for opponent in &mut snapshot.opponents {
+                opponent.score = 0;
+            }
+            snapshot.points_to_lead = 0;
+            snapshot.scores_hidden = true;
+        }
+        if self.visibility == Visibility::Blind {
+            snapshot.player.score = 0;
+        }
+        snapshot
+    }
+
+    /// Returns whose scores the players can see.
+    pub fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+
+    /// Let players see scores per visibility.
+    pub fn set_visibility(&mut self, visibility: Visibility) {
+        self.visibility = visibility;
+    }
+
+    /// Returns each player's name and score in turn order as viewer sees
+    /// them, with None for scores hidden from them. A viewer who isn't
+    /// playing, or None, is a spectator.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player, Visibility};
+    /// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+    /// game.set_visibility(Visibility::HiddenTotals);
+    /// game.record_round(350);
+    /// let seen = vec![("Ann".to_string(), Some(350)), ("Bob".to_string(), None)];
+    /// assert_eq!(seen, game.scores_seen_by(Some("Ann")));
+    /// let spectator: Vec<Option<u32>> = game.scores_seen_by(None).into_iter().map(|(_name, score)| score).collect();
+    /// assert_eq!(vec![None, None], spectator);
+    /// ```
+    pub fn scores_seen_by(&self, viewer: Option<&str>) -> Vec<(String, Option<u32>)> {
+        self.players
+            .iter()
+            .map(|player| {
+                let seen = self.can_see(&player.name, viewer);
+                (player.name.clone(), seen.then_some(player.score))
+            })
+            .collect()
+    }
+
+    /// Returns event from player's turn as viewer sees it, None if it's
+    /// hidden from them altogether.
+    ///
+    /// Only totals are hidden, so that's a catch-up bonus the viewer can't
+    /// see player's total for, since it gives away who's behind. The points
+    /// each turn banks are seen by all.
+    ///
+    /// Frontends pass what they tell everyone after a turn, its summary and
+    /// any bonus, through this, so what's hidden is the same whichever
+    /// frontend shows it.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::game::{Game, Player, Visibility};
+    /// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+    /// let bonus = Event::CatchUp { player: "Ann".to_string(), bonus: 50 };
+    /// assert_eq!(Some(bonus.clone()), game.redact(&bonus, "Ann", Some("Bob")));
+    /// game.set_visibility(Visibility::HiddenTotals);
+    /// assert_eq!(None, game.redact(&bonus, "Ann", Some("Bob")));
+    /// assert_eq!(Some(bonus.clone()), game.redact(&bonus, "Ann", Some("Ann")));
+    ///
+    /// // What a turn banks isn't a total, so it's still shown.
+    /// let summary = Event::RoundSummary { player: "Ann".to_string(), banked: 350, farkled: false, rolls: 2 };
+    /// assert_eq!(Some(summary.clone()), game.redact(&summary, "Ann", None));
+    /// ```
+    ///
+    /// In a blind game a catch-up bonus is hidden from everyone.
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::game::{Game, Player, Visibility};
+    /// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+    /// game.set_visibility(Visibility::Blind);
+    /// let banked = Event::Banked { score: 350 };
+    /// assert_eq!(Some(banked.clone()), game.redact(&banked, "Ann", Some("Bob")));
+    /// let bonus = Event::CatchUp { player: "Ann".to_string(), bonus: 50 };
+    /// assert_eq!(None, game.redact(&bonus, "Ann", Some("Ann")));
+    /// ```
+    pub fn redact(&self, event: &Event, player: &str, viewer: Option<&str>) -> Option<Event> {
+        match event {
+            Event::CatchUp { .. } if !self.can_see(player, viewer) => None,
+            event => Some(event.clone()),
+        }
+    }
+
+    /// Returns true if viewer can see player's score.
+    fn can_see(&self, player: &str, viewer: Option<&str>) -> bool {
+        match self.visibility {
+            _ if self.is_over() => true,
+            Visibility::Open => true,
+            Visibility::HiddenTotals => viewer == Some(player),
+            Visibility::Blind => false,
+        }
+    }
+
+    /// Returns everyone's totals, once the game's over, from last place
+    /// to first, to show one at a time at the end of a game they were
+    /// hidden in.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::game::{Game, Player, Visibility, WinCondition};
+    /// let players = vec![Player::new("Ann"), Player::new("Bob")];
+    /// let mut game = Game::with_win_condition(players, WinCondition::RoundLimit(1));
+    /// game.set_visibility(Visibility::Blind);
+    /// assert_eq!(None, game.scores_seen_by(Some("Ann"))[0].1);
+    /// game.record_round(500);
+    /// assert!(game.reveal().is_empty());
+    /// game.record_round(300);
+    /// let reveal = vec![
+    ///     Event::TotalRevealed { player: "Bob".to_string(), score: 300, place: 2 },
+    ///     Event::TotalRevealed { player: "Ann".to_string(), score: 500, place: 1 },
+    /// ];
+    /// assert_eq!(reveal, game.reveal());
+    /// ```
+    pub fn reveal(&self) -> Vec<Event> {
+        if !self.is_over() {
+            return Vec::new();
+        }
+        let standings = self.standings().into_iter().enumerate().rev();
+        standings
+            .map(|(i, player)| Event::TotalRevealed { player: player.name.clone(), score: player.score, place: i + 1 })
+            .collect()
+    }
+
+    /// Returns the position in turn order of the player whose round it is.
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the optional rules turns are played by.
+    pub fn rules(&self) -> &ScoringRules {
+        &self.rules
+    }
+
+    /// Play turns by rules.
+    pub fn set_rules(&mut self, rules: ScoringRules) {
+        self.rules = rules;
+    }
+
+    /// Returns the catch-up bonus the current player gets on top of
+    /// round_score, if they're behind everyone else and the rules give one.
+    ///
+    /// The bonus is a percent of round_score, rounded down to 50s.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// use farkle::ScoringRules;
+    /// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+    /// game.set_rules(ScoringRules { catch_up_percent: Some(20), ..ScoringRules::default() });
+    /// // Nobody is behind yet.
+    /// assert_eq!(0, game.catch_up_bonus(1000));
+    /// game.record_round(1000);
+    /// assert_eq!(200, game.catch_up_bonus(1000));
+    /// assert_eq!(50, game.catch_up_bonus(300));
+    /// game.record_round(500);
+    /// assert_eq!(600, game.players()[1].score);
+    /// ```
+    pub fn catch_up_bonus(&self, round_score: u32) -> u32 {
+        let Some(percent) = self.rules.catch_up_percent else {
+            return 0;
+        };
+        if round_score < self.opening_needed() {
+            return 0;
+        }
+        let score = self.players[self.current].score;
+        let others = self.players.iter().enumerate().filter(|(i, _player)| *i != self.current && !self.left[*i]);
+        let mut others = others.map(|(_i, player)| player.score).peekable();
+        if others.peek().is_none() || !others.all(|other| other > score) {
+            return 0;
+        }
+        let bonus = (round_score as u64 * percent as u64 / 100) as u32;
+        let bonus = bonus - bonus % 50;
+        // A bonus can't take anyone past a target they have to hit exactly.
+        let player = &self.players[self.current];
+        let banked = round_score.saturating_add(self.double_bonus(round_score)).saturating_add(bonus);
+        if self.rules.exact_finish && player.score.saturating_add(banked) > player.target {
+            return 0;
+        }
+        bonus
+    }
+
+    /// Returns true if round_score would take the current player past their
+    /// target, when the rules have them finish on it exactly, so it counts
+    /// for nothing.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// use farkle::ScoringRules;
+    /// let mut game = Game::new(vec![Player::with_target("Ann", 1000)]);
+    /// game.set_rules(ScoringRules { exact_finish: true, ..ScoringRules::default() });
+    /// game.record_round(800);
+    /// assert!(game.busts(250));
+    /// game.record_round(250);
+    /// assert_eq!(800, game.players()[0].score);
+    /// assert!(!game.busts(200));
+    /// game.record_round(200);
+    /// assert!(game.is_over());
+    /// ```
+    pub fn busts(&self, round_score: u32) -> bool {
+        let player = &self.players[self.current];
+        let doubled = if self.rules.doubles(self.round_number()) { round_score.saturating_mul(2) } else { round_score };
+        self.rules.exact_finish && player.score.saturating_add(doubled) > player.target
+    }
+
+    /// Returns the points the current player gets on top of round_score
+    /// for banking it in a double points round, if it counts.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// use farkle::ScoringRules;
+    /// let mut game = Game::new(vec![Player::new("Ann")]);
+    /// game.set_rules(ScoringRules { double_every: Some(2), ..ScoringRules::default() });
+    /// assert_eq!(0, game.double_bonus(300));
+    /// game.record_round(300);
+    /// assert_eq!(300, game.double_bonus(300));
+    /// game.record_round(300);
+    /// assert_eq!(900, game.players()[0].score);
+    /// ```
+    pub fn double_bonus(&self, round_score: u32) -> u32 {
+        if !self.rules.doubles(self.round_number()) || round_score < self.opening_needed() || self.busts(round_score) {
+            return 0;
+        }
+        round_score
+    }
+
+    /// Returns the least the current player's turn has to score to count,
+    /// 0 once they've opened or if the rules have no opening score.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// use farkle::ScoringRules;
+    /// let mut game = Game::new(vec![Player::new("Ann")]);
+    /// game.set_rules(ScoringRules { opening_score: Some(300), ..ScoringRules::default() });
+    /// assert_eq!(300, game.opening_needed());
+    /// game.record_round(250);
+    /// assert_eq!(0, game.players()[0].score);
+    /// game.record_round(300);
+    /// assert_eq!(0, game.opening_needed());
+    /// game.record_round(50);
+    /// assert_eq!(350, game.players()[0].score);
+    /// ```
+    pub fn opening_needed(&self) -> u32 {
+        match self.rules.opening_score {
+            Some(opening) if !self.opened[self.current] => opening,
+            _ => 0,
+        }
+    }
+
+    /// Returns the points the current player loses for a turn scoring
+    /// round_score, if it makes three in a row scoring nothing and the rules
+    /// take points for that.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// use farkle::ScoringRules;
+    /// let mut game = Game::new(vec![Player::new("Ann")]);
+    /// game.set_rules(ScoringRules { zilch_penalty: Some(500), ..ScoringRules::default() });
+    /// game.record_round(800);
+    /// game.record_round(0);
+    /// game.record_round(0);
+    /// assert_eq!(0, game.zilch_penalty(50));
+    /// assert_eq!(500, game.zilch_penalty(0));
+    /// game.record_round(0);
+    /// assert_eq!(300, game.players()[0].score);
+    /// // The count starts again after a penalty.
+    /// assert_eq!(0, game.zilch_penalty(0));
+    /// ```
+    pub fn zilch_penalty(&self, round_score: u32) -> u32 {
+        match self.rules.zilch_penalty {
+            Some(penalty) if round_score == 0 && self.scoreless_turns[self.current] >= 2 => penalty,
+            _ => 0,
+        }
+    }
+
+    /// Returns the free rolls the current player holds.
+    pub fn free_rolls(&self) -> u32 {
+        self.free_rolls[self.current]
+    }
+
+    /// Set the free rolls the current player holds, after their turn and
+    /// before it's recorded.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+    /// game.set_free_rolls(1);
+    /// game.record_round(300);
+    /// assert_eq!(0, game.free_rolls());
+    /// game.record_round(0);
+    /// assert_eq!(1, game.free_rolls());
+    /// ```
+    pub fn set_free_rolls(&mut self, held: u32) {
+        self.free_rolls[self.current] = held;
+    }
+
+    /// Give every player total time on their clock.
+    ///
+    /// Players who run out of time have the rest of their rounds skipped.
+    pub fn set_clock(&mut self, total: Duration) {
+        for player in &mut self.players {
+            player.time_left = Some(total);
+        }
+    }
+
+    /// Take elapsed off the current player's clock.
+    ///
+    /// Returns true if that ran their clock out.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// use std::time::Duration;
+    /// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+    /// game.set_clock(Duration::from_secs(60));
+    /// assert_eq!(false, game.charge_time(Duration::from_secs(45)));
+    /// assert_eq!(true, game.charge_time(Duration::from_secs(20)));
+    /// assert_eq!(Some(Duration::ZERO), game.current_player().time_left);
+    /// game.record_round(0);
+    /// // Ann is out of time, so it's Bob's round every round.
+    /// game.record_round(300);
+    /// assert_eq!("Bob", game.current_player().name);
+    /// assert_eq!(2, game.round_number());
+    /// ```
+    pub fn charge_time(&mut self, elapsed: Duration) -> bool {
+        let player = &mut self.players[self.current];
+        match player.time_left {
+            Some(left) => {
+                player.time_left = Some(left.saturating_sub(elapsed));
+                player.out_of_time()
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the player playing the redemption round, while they play it.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player, WinCondition};
+    /// use farkle::ScoringRules;
+    /// let players = vec![Player::new("Ann"), Player::new("Bob"), Player::new("Cy")];
+    /// let mut game = Game::with_win_condition(players, WinCondition::RoundLimit(1));
+    /// game.set_rules(ScoringRules { redemption_round: true, ..ScoringRules::default() });
+    /// game.record_round(4000);
+    /// game.record_round(0);
+    /// game.record_round(300);
+    /// // Bob's last, and 4,001 behind is within the 5,000 six of a kind scores.
+    /// assert_eq!(false, game.is_over());
+    /// assert_eq!("Bob", game.redemption_round().unwrap().name);
+    /// game.record_round(4500);
+    /// assert_eq!(None, game.redemption_round());
+    /// assert_eq!("Bob", game.winner().unwrap().name);
+    ///
+    /// // Past the most one roll scores, the game's over.
+    /// let players = vec![Player::new("Ann"), Player::new("Bob")];
+    /// let mut game = Game::with_win_condition(players, WinCondition::RoundLimit(1));
+    /// game.set_rules(ScoringRules { redemption_round: true, ..ScoringRules::default() });
+    /// game.record_round(5000);
+    /// game.record_round(0);
+    /// assert_eq!(true, game.is_over());
+    /// ```
+    pub fn redemption_round(&self) -> Option<&Player> {
+        match self.redemption {
+            Redemption::Playing(seat) => Some(&self.players[seat]),
+            _ => None,
+        }
+    }
+
+    /// Returns the seat of the player in last place, if one roll could take
+    /// them into first.
+    fn redemption_seat(&self) -> Option<usize> {
+        let standings = self.standings();
+        let leader = standings.first()?;
+        let last = standings.iter().rev().find(|player| !player.out_of_time() && !self.has_left(&player.name))?;
+        if last.name == leader.name {
+            return None;
+        }
+        let lead = match self.win_condition {
+            WinCondition::TargetScore if leader.reached_target() => {
+                i64::from(leader.score) - i64::from(leader.target) + i64::from(last.target)
+            }
+            _ => i64::from(leader.score),
+        };
+        let needed = lead + 1 - i64::from(last.score);
+        let seat = self.players.iter().position(|player| player.name == last.name)?;
+        (needed <= i64::from(self.rules.best_roll())).then_some(seat)
+    }
+
+    /// Returns the player who started the final round, if it's started.
+    pub fn final_round_trigger(&self) -> Option<&Player> {
+        self.final_round_trigger.map(|i| &self.players[i])
+    }
+
+    /// Bank the current player's round score, with any catch-up bonus or
+    /// zilch penalty, and move to the next player. A score short of the
+    /// opening score, or past a target to hit exactly, counts for nothing.
+    ///
+    /// Returns true if this round started the final round.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// let mut game = Game::new(vec![
+    ///     Player::with_target("Ann", 1000),
+    ///     Player::with_target("Bob", 500),
+    /// ]);
+    /// assert_eq!(false, game.record_round(600));
+    /// // Bob has the lower target and reaches it first.
+    /// assert_eq!(true, game.record_round(550));
+    /// assert_eq!(false, game.is_over());
+    /// // Ann gets one last round.
+    /// assert_eq!("Ann", game.current_player().name);
+    /// assert_eq!(false, game.record_round(300));
+    /// assert_eq!(true, game.is_over());
+    /// assert_eq!("Bob", game.winner().unwrap().name);
+    /// ```
+    ///
+    /// Totals can go well past the 32,767 points an `i16` held.
+    /// ```
+    /// use farkle::game::{Game, Player, MARATHON_TARGET};
+    /// let mut game = Game::new(vec![Player::with_target("Ann", MARATHON_TARGET)]);
+    /// for _ in 0..7 {
+    ///     game.record_round(5000);
+    /// }
+    /// assert_eq!(35_000, game.current_player().score);
+    /// assert_eq!(false, game.is_over());
+    /// for _ in 0..13 {
+    ///     game.record_round(5000);
+    /// }
+    /// assert_eq!(true, game.is_over());
+    /// assert_eq!(100_000, game.winner().unwrap().score);
+    ///
+    /// // And never wrap around.
+    /// let mut game = Game::new(vec![Player::with_target("Bob", u32::MAX)]);
+    /// game.record_round(u32::MAX - 100);
+    /// game.record_round(5000);
+    /// assert_eq!(u32::MAX, game.current_player().score);
+    /// ```
+    pub fn record_round(&mut self, round_score: u32) -> bool {
+        let bonus = self.catch_up_bonus(round_score).saturating_add(self.double_bonus(round_score));
+        let penalty = self.zilch_penalty(round_score);
+        let counted = if round_score < self.opening_needed() || self.busts(round_score) { 0 } else { round_score };
+        self.opened[self.current] |= counted > 0;
+        let scoreless = &mut self.scoreless_turns[self.current];
+        *scoreless = if round_score == 0 && penalty == 0 { *scoreless + 1 } else { 0 };
+        let player = &mut self.players[self.current];
+        player.score = player.score.saturating_add(counted).saturating_add(bonus).saturating_sub(penalty);
+        let triggered = self.win_condition == WinCondition::TargetScore
+            && self.final_round_trigger.is_none()
+            && player.reached_target();
+        if triggered {
+            self.final_round_trigger = Some(self.current);
+        }
+        self.advance();
+        triggered
+    }
+
+    /// Move to the next player still playing with time on their clock,
+    /// or to last place for a redemption round once the game would be over.
+    fn advance(&mut self) {
+        if let Redemption::Playing(_) = self.redemption {
+            self.redemption = Redemption::Played;
+            return;
+        }
+        loop {
+            self.current = (self.current + 1) % self.players.len();
+            if self.current == 0 {
+                self.rounds_completed += 1;
+            }
+            if self.is_over() || !(self.players[self.current].out_of_time() || self.left[self.current]) {
+                break;
+            }
+        }
+        if self.is_over() && self.rules.redemption_round {
+            self.redemption = match self.redemption_seat() {
+                Some(seat) => {
+                    self.current = seat;
+                    Redemption::Playing(seat)
+                }
+                None => Redemption::Played,
+            };
+        }
+    }
+
+    /// Returns true once everyone has had their final round, and any
+    /// redemption round's been played.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player, WinCondition};
+    /// let players = vec![Player::new("Ann"), Player::new("Bob")];
+    /// let mut game = Game::with_win_condition(players, WinCondition::RoundLimit(2));
+    /// game.record_round(500);
+    /// game.record_round(300);
+    /// assert_eq!(2, game.round_number());
+    /// game.record_round(0);
+    /// assert_eq!(false, game.is_over());
+    /// game.record_round(350);
+    /// assert_eq!(true, game.is_over());
+    /// assert_eq!("Bob", game.winner().unwrap().name);
+    /// ```
+    pub fn is_over(&self) -> bool {
+        match self.redemption {
+            Redemption::Playing(_) => return false,
+            Redemption::Played => return true,
+            Redemption::NotYet => {}
+        }
+        if self.players.iter().zip(&self.left).all(|(player, left)| player.out_of_time() || *left) {
+            return true;
+        }
+        match self.win_condition {
+            WinCondition::TargetScore => self.final_round_trigger == Some(self.current),
+            WinCondition::RoundLimit(rounds) => self.rounds_completed >= rounds,
+        }
+    }
+
+    /// Returns the winner once the game is over.
+    ///
+    /// In a race to the targets the winner is the player furthest past their
+    /// own target, otherwise (or if every clock ran out first) it's the
+    /// highest score. Ties go to the player earliest in turn order.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// let mut game = Game::new(vec![
+    ///     Player::with_target("Ann", 12_000),
+    ///     Player::with_target("Bob", 8_000),
+    /// ]);
+    /// game.record_round(11_000);
+    /// game.record_round(8_100);
+    /// assert_eq!(None, game.winner());
+    /// // Ann ends further past the 12,000 target than Bob is past 8,000.
+    /// game.record_round(1_500);
+    /// assert_eq!("Ann", game.winner().unwrap().name);
+    /// ```
+    pub fn winner(&self) -> Option<&Player> {
+        if !self.is_over() {
+            return None;
+        }
+        self.standings().into_iter().next()
+    }
+
+    /// Returns the players from first place to last, as the game stands.
+    ///
+    /// Once someone reaches their target, players past their target come
+    /// first, furthest past it first. Otherwise the highest score is first.
+    /// Players who left come after everyone still playing. Ties keep turn
+    /// order.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// let mut game = Game::new(vec![
+    ///     Player::with_target("Ann", 1000),
+    ///     Player::with_target("Bob", 500),
+    ///     Player::with_target("Cat", 5000),
+    /// ]);
+    /// game.record_round(1_200);
+    /// game.record_round(900);
+    /// game.record_round(2_000);
+    /// let names: Vec<&str> = game.standings().iter().map(|player| player.name.as_str()).collect();
+    /// assert_eq!(vec!["Bob", "Ann", "Cat"], names);
+    /// ```
+    pub fn standings(&self) -> Vec<&Player> {
+        let anyone_reached_target = self.players.iter().any(|player| player.reached_target());
+        let by_target = self.win_condition == WinCondition::TargetScore && anyone_reached_target;
+        let mut standings: Vec<&Player> = self.players.iter().collect();
+        standings.sort_by_key(|player| match by_target {
+            true if player.reached_target() => Reverse((true, player.score - player.target)),
+            _ => Reverse((false, player.score)),
+        });
+        standings.sort_by_key(|player| self.has_left(&player.name));
+        standings
+    }
+}
@@ -0,0 +1,85 @@
+//! What every roll of six dice scores under each preset, kept in
+//! `golden/scoring.csv` so any change to how a roll scores shows up in
+//! review. Rebuild the file with `cargo run -- golden > golden/scoring.csv`
+//! after changing the rules on purpose.
+
+use crate::config::Preset;
+
+/// Returns every roll of six dice, each once, in order, like 111111 to
+/// 666666.
+///
+/// ### Examples
+/// ```
+/// let rolls = farkle::golden::distinct_rolls();
+/// assert_eq!(462, rolls.len());
+/// assert_eq!(vec![1, 1, 1, 1, 1, 2], rolls[1]);
+/// ```
+pub fn distinct_rolls() -> Vec<Vec<i16>> {
+    let mut rolls = vec![Vec::new()];
+    for _i in 0..6 {
+        let mut longer = Vec::new();
+        for roll in rolls {
+            let lowest = roll.last().copied().unwrap_or(1);
+            for face in lowest..=6 {
+                let mut next = roll.clone();
+                next.push(face);
+                longer.push(next);
+            }
+        }
+        rolls = longer;
+    }
+    rolls
+}
+
+/// Returns the scores of every distinct roll under each preset, as CSV.
+pub fn table() -> String {
+    let rules: Vec<_> = Preset::ALL.iter().map(|preset| preset.config().rules).collect();
+    let presets: Vec<String> = Preset::ALL.iter().map(Preset::to_string).collect();
+    let mut table = format!("dice,{}\n", presets.join(","));
+    for roll in distinct_rolls() {
+        let dice: String = roll.iter().map(|die| die.to_string()).collect();
+        let scores: Vec<String> = rules.iter().map(|rules| rules.score(&roll).to_string()).collect();
+        table.push_str(&format!("{},{}\n", dice, scores.join(",")));
+    }
+    table
+}
+
+/// Returns a line for each roll that scores differently than expected, a
+/// table like `table` makes.
+///
+/// ### Examples
+/// ```
+/// let expected = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/golden/scoring.csv"));
+/// let differences = farkle::golden::check(expected);
+/// assert!(differences.is_empty(), "{}", differences.join("\n"));
+///
+/// let wrong = farkle::golden::table().replace("111111,5000", "111111,4000");
+/// assert_eq!(vec!["111111 under classic: expected 4000, scored 5000"], farkle::golden::check(&wrong));
+/// ```
+pub fn check(expected: &str) -> Vec<String> {
+    let actual = table();
+    let (mut expected, mut actual) = (expected.lines(), actual.lines());
+    let header = actual.next().unwrap_or_default();
+    if expected.next() != Some(header) {
+        return vec![format!("the table should start '{}', for the presets there are now", header)];
+    }
+    let mut differences = Vec::new();
+    let presets: Vec<&str> = header.split(',').skip(1).collect();
+    for (expected, actual) in expected.by_ref().zip(actual.by_ref()) {
+        let (expected, actual): (Vec<&str>, Vec<&str>) = (expected.split(',').collect(), actual.split(',').collect());
+        if expected.first() != actual.first() {
+            differences.push(format!("expected a row for {}, found {}", actual[0], expected[0]));
+            continue;
+        }
+        for (i, preset) in presets.iter().enumerate() {
+            let (expected, scored) = (expected.get(i + 1).unwrap_or(&"nothing"), actual[i + 1]);
+            if *expected != scored {
+                differences.push(format!("{} under {}: expected {}, scored {}", actual[0], preset, expected, scored));
+            }
+        }
+    }
+    if expected.next().is_some() || actual.next().is_some() {
+        differences.push(format!("the table should have {} rolls", distinct_rolls().len()));
+    }
+    differences
+}
@@ -0,0 +1,157 @@
+//! Catching remote players up when their turn comes round, with what the
+//! others did since their last turn, so they needn't scroll back through
+//! every roll.
+
+use crate::driver::AsyncDecider;
+use crate::event::Event;
+use crate::game::GameSnapshot;
+use crate::render::render_points;
+use crate::turn::{PlayerAction, TurnSnapshot};
+use std::future::Future;
+
+/// Returns what happened in events, the ends of other players' turns, and
+/// how everyone's scores stand in game, with the change since before for
+/// each opponent. None if nothing happened.
+///
+/// ### Examples
+/// ```
+/// use farkle::event::Event;
+/// use farkle::game::{Game, Player};
+/// use farkle::handoff::summarize;
+///
+/// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob"), Player::new("Cy")]);
+/// let before = vec![("Ann".to_string(), 0), ("Bob".to_string(), 0), ("Cy".to_string(), 0)];
+/// game.record_round(300);
+/// game.record_round(350);
+/// game.record_round(0);
+/// let events = vec![
+///     Event::RoundSummary { player: "Bob".to_string(), banked: 350, farkled: false, rolls: 3 },
+///     Event::RoundSummary { player: "Cy".to_string(), banked: 0, farkled: true, rolls: 1 },
+/// ];
+/// assert_eq!(
+///     Some("Since your last turn: Bob banked 350, Cy farkled. Scores: Ann 300, Bob 350 (+350), Cy 0.".to_string()),
+///     summarize(&events, &before, &game.snapshot())
+/// );
+/// assert_eq!(None, summarize(&[], &before, &game.snapshot()));
+/// ```
+pub fn summarize(events: &[Event], before: &[(String, u32)], game: &GameSnapshot) -> Option<String> {
+    let hidden = game.scores_hidden;
+    let happened: Vec<String> = events
+        .iter()
+        .filter_map(|event| match event {
+            Event::RoundSummary { player, farkled: true, .. } => Some(format!("{} farkled", player)),
+            Event::RoundSummary { player, banked: 0, .. } => Some(format!("{} scored nothing", player)),
+            Event::RoundSummary { player, .. } if hidden => Some(format!("{} banked", player)),
+            Event::RoundSummary { player, banked, .. } => Some(format!("{} banked {}", player, render_points(*banked))),
+            Event::TimedOut { player } => Some(format!("{} ran out of time", player)),
+            Event::CatchUp { player, bonus } if !hidden => {
+                Some(format!("{} caught up, +{}", player, render_points(*bonus)))
+            }
+            Event::DoublePoints { player, bonus } if !hidden => {
+                Some(format!("{} doubled up, +{}", player, render_points(*bonus)))
+            }
+            _ => None,
+        })
+        .collect();
+    if happened.is_empty() {
+        return None;
+    }
+    let mut summary = format!("Since your last turn: {}.", happened.join(", "));
+    if !hidden {
+        let mut scores = vec![format!("{} {}", game.player.name, render_points(game.player.score))];
+        for opponent in &game.opponents {
+            let was = before.iter().find(|(name, _)| *name == opponent.name).map_or(0, |(_, score)| *score);
+            let change = i64::from(opponent.score) - i64::from(was);
+            let score = render_points(opponent.score);
+            scores.push(match change {
+                0 => format!("{} {}", opponent.name, score),
+                change if change > 0 => format!("{} {} (+{})", opponent.name, score, render_points(change as u32)),
+                change => format!("{} {} (-{})", opponent.name, score, render_points(change.unsigned_abs() as u32)),
+            });
+        }
+        summary.push_str(&format!(" Scores: {}.", scores.join(", ")));
+    }
+    Some(summary)
+}
+
+/// A remote player's decider, sent `Event::SinceLastTurn` as each of
+/// their turns starts, if anything happened since their last one.
+///
+/// ### Examples
+/// ```
+/// use farkle::bot::Bot;
+/// use farkle::driver::{AsyncDecider, Ready};
+/// use farkle::event::Event;
+/// use farkle::game::{Game, Player};
+/// use farkle::handoff::Handoff;
+///
+/// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+/// let mut bob = Handoff::new(Ready(Bot::new(300)));
+/// bob.on_event(&Event::RoundSummary { player: "Ann".to_string(), banked: 400, farkled: false, rolls: 2 });
+/// game.record_round(400);
+/// bob.on_turn_start(&game.snapshot());
+/// assert_eq!("Since your last turn: Ann banked 400. Scores: Bob 0, Ann 400 (+400).", bob.last_summary().unwrap());
+/// ```
+pub struct Handoff<D> {
+    decider: D,
+    /// Who's playing, once their first turn's started.
+    name: Option<String>,
+    /// What the other players did since this player's last turn.
+    events: Vec<Event>,
+    /// Everyone's scores as this player's last turn started.
+    scores: Vec<(String, u32)>,
+    /// The summary sent as this turn started.
+    summary: Option<String>,
+}
+
+impl<D: AsyncDecider> Handoff<D> {
+    /// Returns decider, caught up as each turn starts.
+    pub fn new(decider: D) -> Handoff<D> {
+        Handoff { decider, name: None, events: Vec::new(), scores: Vec::new(), summary: None }
+    }
+
+    /// Returns the summary sent as the turn started, if there was one.
+    pub fn last_summary(&self) -> Option<&str> {
+        self.summary.as_deref()
+    }
+}
+
+impl<D: AsyncDecider> AsyncDecider for Handoff<D> {
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> impl Future<Output = PlayerAction> + Send {
+        self.decider.choose_keep(dice, turn)
+    }
+
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> impl Future<Output = PlayerAction> + Send {
+        self.decider.choose_bank(turn)
+    }
+
+    fn remark(&mut self) -> Option<String> {
+        self.decider.remark()
+    }
+
+    fn on_turn_start(&mut self, game: &GameSnapshot) {
+        self.name = Some(game.player.name.clone());
+        self.summary = summarize(&self.events, &self.scores, game);
+        if let Some(summary) = &self.summary {
+            self.decider.on_event(&Event::SinceLastTurn { summary: summary.clone() });
+        }
+        self.events.clear();
+        let everyone = std::iter::once(&game.player).chain(&game.opponents);
+        self.scores = everyone.map(|player| (player.name.clone(), player.score)).collect();
+        self.decider.on_turn_start(game);
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        let player = match event {
+            Event::RoundSummary { player, .. }
+            | Event::TimedOut { player }
+            | Event::CatchUp { player, .. }
+            | Event::DoublePoints { player, .. } => player,
+            _ => "",
+        };
+        if !player.is_empty() && self.name.as_deref() != Some(player) {
+            self.events.push(event.clone());
+        }
+        self.decider.on_event(event);
+    }
+}
@@ -0,0 +1,212 @@
+//! Whole games played with scripted players and seeded dice, checked as
+//! they go, so a change to the game loop that plays differently shows up.
+//!
+//! Every response is checked with `TurnEngine::validate_state`, only the
+//! player taking their turn can change score, and the game written down in
+//! notation has to read back in and add up to the same scores.
+
+use crate::event::Event;
+use crate::game::Game;
+use crate::notation::GameRecord;
+use crate::turn::{Decider, Pending, PlayerAction, TurnEngine, TurnSnapshot};
+use rand::Rng;
+use std::collections::VecDeque;
+
+/// Turns a game can take before it's taken to be stuck.
+pub const MAX_TURNS: usize = 10_000;
+
+/// Rejected responses in a row before a player is taken to be stuck.
+const MAX_REJECTED: usize = 20;
+
+/// A player who makes the choices they're given, in order, then keeps
+/// every scoring die and banks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scripted {
+    actions: VecDeque<PlayerAction>,
+}
+
+impl Scripted {
+    /// Returns a player making actions, one per choice, keeps and banks
+    /// alike.
+    pub fn new(actions: impl IntoIterator<Item = PlayerAction>) -> Scripted {
+        Scripted { actions: actions.into_iter().collect() }
+    }
+
+    /// Returns the actions not made yet.
+    pub fn left(&self) -> usize {
+        self.actions.len()
+    }
+}
+
+impl Decider for Scripted {
+    fn choose_keep(&mut self, _dice: &[i16], _turn: &TurnSnapshot) -> PlayerAction {
+        self.actions.pop_front().unwrap_or(PlayerAction::KeepAllScoring)
+    }
+
+    fn choose_bank(&mut self, _turn: &TurnSnapshot) -> PlayerAction {
+        self.actions.pop_front().unwrap_or(PlayerAction::Bank)
+    }
+}
+
+/// A game played to the end.
+#[derive(Debug, Clone)]
+pub struct Playthrough {
+    /// The game as it ended.
+    pub game: Game,
+    /// Everything that happened, with whose turn it was.
+    pub events: Vec<(String, Event)>,
+    /// The game written down.
+    pub record: GameRecord,
+}
+
+impl Playthrough {
+    /// Returns the final scores, in turn order.
+    pub fn scores(&self) -> Vec<u32> {
+        self.game.players().iter().map(|player| player.score).collect()
+    }
+
+    /// Returns the events in name's turns.
+    pub fn events_of(&self, name: &str) -> Vec<&Event> {
+        self.events.iter().filter(|(player, _event)| player == name).map(|(_player, event)| event).collect()
+    }
+}
+
+/// Play game to the end with a decider per player, in turn order, rolling
+/// with rng and checking the game after every response.
+///
+/// Returns the first problem found: a turn that doesn't add up, a score
+/// changing out of turn, a player stuck on rejected responses, a game that
+/// doesn't end, or a record that doesn't read back to the same game.
+///
+/// ### Examples
+/// ```
+/// use farkle::bot::Bot;
+/// use farkle::event::Event;
+/// use farkle::game::{Game, Player, WinCondition};
+/// use farkle::harness::play_through;
+///
+/// let players = vec![Player::new("Ann"), Player::new("Bob")];
+/// let game = Game::with_win_condition(players, WinCondition::RoundLimit(5));
+/// let mut bots = [Bot::new(300), Bot::new(1000)];
+/// let played = play_through(game, &mut farkle::seeded_rng(5), &mut bots).unwrap();
+/// assert!(played.game.is_over());
+/// assert_eq!(5, played.events_of("Ann").iter().filter(|event| matches!(event, Event::RoundSummary { .. })).count());
+///
+/// // The same seed plays the same game.
+/// let game = Game::with_win_condition(vec![Player::new("Ann"), Player::new("Bob")], WinCondition::RoundLimit(5));
+/// let again = play_through(game, &mut farkle::seeded_rng(5), &mut [Bot::new(300), Bot::new(1000)]).unwrap();
+/// assert_eq!(played.scores(), again.scores());
+/// assert_eq!(played.events, again.events);
+/// ```
+///
+/// Scripted players make their choices in order.
+/// ```
+/// use farkle::event::Event;
+/// use farkle::game::{Game, Player, WinCondition};
+/// use farkle::harness::{play_through, Scripted};
+/// use farkle::replay::ReplayRoller;
+/// use farkle::turn::PlayerAction;
+///
+/// let game = Game::with_win_condition(vec![Player::new("Ann")], WinCondition::RoundLimit(1));
+/// let mut dice = ReplayRoller::parse("436124\n32526\n").unwrap();
+/// let script = [PlayerAction::KeepValues(vec![1]), PlayerAction::RollAgain, PlayerAction::KeepValues(vec![5])];
+/// let mut ann = [Scripted::new(script)];
+/// let played = play_through(game, &mut dice, &mut ann).unwrap();
+/// assert_eq!(vec![150], played.scores());
+/// assert_eq!(0, ann[0].left());
+/// assert_eq!("1. Ann 436124:1 32526:5 B 150", played.record.to_string().lines().last().unwrap());
+///
+/// // A player who won't make a choice that fits gets nowhere.
+/// let game = Game::new(vec![Player::new("Ann")]);
+/// let mut stuck = [Scripted::new(std::iter::repeat(PlayerAction::RollAgain).take(100))];
+/// assert!(play_through(game, &mut farkle::daily::round_rng(1, 0), &mut stuck).is_err());
+/// ```
+///
+/// Every preset plays to the end.
+/// ```
+/// use farkle::bot::Bot;
+/// use farkle::config::Preset;
+/// use farkle::game::{Game, Player};
+/// use farkle::harness::play_through;
+///
+/// for (seed, preset) in (1..).zip(Preset::ALL) {
+///     let config = preset.config();
+///     let players = ["Ann", "Bob", "Cat"].map(|name| Player::with_target(name, config.target)).to_vec();
+///     let mut game = Game::with_win_condition(players, config.win_condition);
+///     game.set_rules(config.rules);
+///     let mut bots = [Bot::new(300), Bot::new(600), Bot::new(1000)];
+///     let played = play_through(game, &mut farkle::seeded_rng(seed), &mut bots);
+///     assert!(played.is_ok_and(|played| played.game.winner().is_some()), "{}", preset);
+/// }
+/// ```
+pub fn play_through<R: Rng, D: Decider>(
+    mut game: Game,
+    rng: &mut R,
+    deciders: &mut [D],
+) -> Result<Playthrough, String> {
+    assert_eq!(game.players().len(), deciders.len(), "Every player needs a decider");
+    let mut record = GameRecord::new(game.players().to_vec(), game.win_condition(), game.rules().clone());
+    let mut events = Vec::new();
+    for number in 1.. {
+        if game.is_over() {
+            break;
+        }
+        if number > MAX_TURNS {
+            return Err(format!("the game didn't end after {} turns.", MAX_TURNS));
+        }
+        let (seat, name) = (game.current_index(), game.current_player().name.clone());
+        let decider = &mut deciders[seat];
+        decider.on_turn_start(&game.snapshot());
+        let (mut turn, started) = TurnEngine::start_with_rules(rng, game.rules().clone());
+        turn.set_free_rolls(game.free_rolls());
+        let mut told = started;
+        let mut rejected = 0;
+        let score = loop {
+            for event in told.drain(..) {
+                decider.on_event(&event);
+                record.record(&name, &event);
+                events.push((name.clone(), event));
+            }
+            let snapshot = turn.snapshot();
+            let action = match turn.pending() {
+                Pending::Keep(dice) => decider.choose_keep(dice, &snapshot),
+                Pending::Bank => decider.choose_bank(&snapshot),
+                Pending::Over(score) => break *score,
+            };
+            told = turn.respond(action, rng);
+            turn.validate_state().map_err(|why| format!("turn {}: {}", number, why))?;
+            rejected = if matches!(told[..], [Event::Rejected { .. }]) { rejected + 1 } else { 0 };
+            if rejected > MAX_REJECTED {
+                return Err(format!("turn {}: {} is stuck on choices that don't fit.", number, name));
+            }
+        };
+        let mut ended = Vec::from_iter(turn.summary(&name));
+        let bonus = game.catch_up_bonus(score);
+        if bonus > 0 {
+            ended.push(Event::CatchUp { player: name.clone(), bonus });
+        }
+        let doubled = game.double_bonus(score);
+        if doubled > 0 {
+            ended.push(Event::DoublePoints { player: name.clone(), bonus: doubled });
+        }
+        for event in ended {
+            decider.on_event(&event);
+            record.record(&name, &event);
+            events.push((name.clone(), event));
+        }
+        let before: Vec<u32> = game.players().iter().map(|player| player.score).collect();
+        game.set_free_rolls(turn.free_rolls());
+        game.record_round(score);
+        let mut players = game.players().iter().enumerate();
+        if let Some((_, player)) = players.find(|(i, player)| *i != seat && player.score != before[*i]) {
+            return Err(format!("turn {}: {}'s score changed in {}'s turn.", number, player.name, name));
+        }
+    }
+    let read_back = GameRecord::parse(&record.to_string()).map_err(|error| format!("the record: {}", error))?;
+    let replayed: Vec<u32> = read_back.game().players().iter().map(|player| player.score).collect();
+    let played = Playthrough { game, events, record };
+    if replayed != played.scores() {
+        return Err(format!("the record adds up to {:?}, but the game ended {:?}.", replayed, played.scores()));
+    }
+    Ok(played)
+}
@@ -0,0 +1,159 @@
+//! Names for each of the six dice, like the colours of a physical set,
+//! followed from roll to roll as dice are kept.
+
+use crate::error::FarkleError;
+use crate::event::Event;
+use crate::game::GameSnapshot;
+use crate::parse::positions_of;
+use crate::turn::{Decider, PlayerAction, TurnSnapshot};
+use crate::TOTAL_DICE;
+
+/// A name for each of the six dice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceLabels {
+    labels: Vec<String>,
+}
+
+impl DiceLabels {
+    /// Returns the dice named A to F.
+    pub fn letters() -> DiceLabels {
+        DiceLabels { labels: ["A", "B", "C", "D", "E", "F"].map(String::from).to_vec() }
+    }
+
+    /// Returns the dice named in text: six different names split by commas,
+    /// or letters for A to F.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::labels::DiceLabels;
+    /// let labels = DiceLabels::parse("red, blue, green, white, black, yellow").unwrap();
+    /// assert_eq!("green", labels.label(2));
+    /// assert_eq!("C", DiceLabels::parse("letters").unwrap().label(2));
+    /// assert!(DiceLabels::parse("red, blue").is_err());
+    /// assert!(DiceLabels::parse("red, Red, green, white, black, yellow").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<DiceLabels, FarkleError> {
+        if text.trim() == "letters" {
+            return Ok(DiceLabels::letters());
+        }
+        let labels: Vec<String> = text.split(',').map(|label| label.trim().to_string()).collect();
+        let problem = if labels.len() != TOTAL_DICE as usize || labels.iter().any(String::is_empty) {
+            Some(format!("'{}' doesn't name {} dice. Name each, split by commas, or use letters.", text, TOTAL_DICE))
+        } else {
+            let lower: Vec<String> = labels.iter().map(|label| label.to_lowercase()).collect();
+            (1..lower.len())
+                .find(|i| lower[..*i].contains(&lower[*i]))
+                .map(|i| format!("Two dice are named {}, so they can't be told apart.", labels[i]))
+        };
+        match problem {
+            Some(problem) => Err(FarkleError::InvalidConfig(vec![problem])),
+            None => Ok(DiceLabels { labels }),
+        }
+    }
+
+    /// Returns the name of the die'th die, counting from 0.
+    pub fn label(&self, die: usize) -> &str {
+        &self.labels[die]
+    }
+}
+
+/// A decider told which of the labelled dice were rolled or kept, after
+/// each roll and keep, so frontends can show each die the same way all
+/// turn.
+///
+/// Rolling every die starts over with all six. Any other roll is of the
+/// dice left from the roll before, less those kept.
+pub struct Labelled<'a, D: ?Sized> {
+    decider: &'a mut D,
+    labels: &'a DiceLabels,
+    roll: Vec<i16>,
+    dice: Vec<usize>,
+}
+
+impl<'a, D: Decider + ?Sized> Labelled<'a, D> {
+    /// Returns decider, told which of labels' dice each roll and keep was.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::labels::{DiceLabels, Labelled};
+    /// use farkle::render::render;
+    /// use farkle::turn::{Decider, PlayerAction, TurnSnapshot};
+    ///
+    /// struct Shown(Vec<String>);
+    /// impl Decider for Shown {
+    ///     fn choose_keep(&mut self, _dice: &[i16], _turn: &TurnSnapshot) -> PlayerAction {
+    ///         PlayerAction::Bank
+    ///     }
+    ///     fn choose_bank(&mut self, _turn: &TurnSnapshot) -> PlayerAction {
+    ///         PlayerAction::Bank
+    ///     }
+    ///     fn on_event(&mut self, event: &Event) {
+    ///         if let Event::LabelledDice { .. } = event {
+    ///             self.0.push(render(event));
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let labels = DiceLabels::letters();
+    /// let mut shown = Shown(Vec::new());
+    /// let mut labelled = Labelled::new(&mut shown, &labels);
+    /// labelled.on_event(&Event::Rolled { dice: vec![2, 1, 3, 5, 6, 4] });
+    /// labelled.on_event(&Event::Kept { dice: vec![1, 5], breakdown: farkle::get_breakdown(&[1, 5]) });
+    /// labelled.on_event(&Event::Rolled { dice: vec![6, 6, 2, 1] });
+    /// let expected = vec![
+    ///     "By die: A 2, B 1, C 3, D 5, E 6, F 4",
+    ///     "Kept by die: B 1, D 5",
+    ///     "By die: A 6, C 6, E 2, F 1",
+    /// ];
+    /// assert_eq!(expected, shown.0);
+    /// ```
+    pub fn new(decider: &'a mut D, labels: &'a DiceLabels) -> Labelled<'a, D> {
+        Labelled { decider, labels, roll: Vec::new(), dice: Vec::new() }
+    }
+
+    fn named(&self, positions: &[usize], values: &[i16]) -> Vec<(String, i16)> {
+        positions.iter().zip(values).map(|(i, die)| (self.labels.label(self.dice[*i]).to_string(), *die)).collect()
+    }
+}
+
+impl<'a, D: Decider + ?Sized> Decider for Labelled<'a, D> {
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> PlayerAction {
+        self.decider.choose_keep(dice, turn)
+    }
+
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> PlayerAction {
+        self.decider.choose_bank(turn)
+    }
+
+    fn remark(&mut self) -> Option<String> {
+        self.decider.remark()
+    }
+
+    fn on_turn_start(&mut self, game: &GameSnapshot) {
+        self.decider.on_turn_start(game);
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        self.decider.on_event(event);
+        match event {
+            Event::Rolled { dice } => {
+                if dice.len() == TOTAL_DICE as usize || dice.len() != self.dice.len() {
+                    self.dice = (0..dice.len()).collect();
+                }
+                self.roll = dice.clone();
+                let positions: Vec<usize> = (0..dice.len()).collect();
+                let dice = self.named(&positions, dice);
+                self.decider.on_event(&Event::LabelledDice { dice, kept: false });
+            }
+            Event::Kept { dice, .. } => {
+                if let Ok(positions) = positions_of(&self.roll, dice) {
+                    let kept = self.named(&positions, dice);
+                    self.decider.on_event(&Event::LabelledDice { dice: kept, kept: true });
+                    self.dice = (0..self.dice.len()).filter(|i| !positions.contains(i)).map(|i| self.dice[i]).collect();
+                }
+            }
+            _ => {}
+        }
+    }
+}
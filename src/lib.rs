@@ -1,6 +1,109 @@
-use rand::Rng;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
-use std::io;
+#[cfg(feature = "std")]
+use std::io::{BufRead, Write};
+
+pub mod scoring;
+
+#[cfg(feature = "chat")]
+pub mod chat;
+#[cfg(feature = "std")]
+pub mod clock;
+#[cfg(feature = "std")]
+pub mod coach;
+#[cfg(feature = "std")]
+pub mod config;
+#[cfg(feature = "std")]
+pub mod confirm;
+#[cfg(feature = "std")]
+pub mod correspondence;
+#[cfg(feature = "std")]
+pub mod audit;
+#[cfg(feature = "std")]
+pub mod bot;
+#[cfg(feature = "std")]
+pub mod console;
+#[cfg(feature = "std")]
+pub mod daily;
+#[cfg(feature = "std")]
+pub mod driver;
+#[cfg(feature = "std")]
+pub mod error;
+#[cfg(feature = "std")]
+pub mod event;
+#[cfg(feature = "p2p")]
+pub mod fair;
+#[cfg(feature = "std")]
+pub mod frontend;
+#[cfg(feature = "std")]
+pub mod game;
+#[cfg(feature = "std")]
+pub mod golden;
+#[cfg(feature = "std")]
+pub mod handoff;
+#[cfg(feature = "std")]
+pub mod harness;
+#[cfg(feature = "std")]
+pub mod labels;
+#[cfg(feature = "std")]
+pub mod notation;
+#[cfg(feature = "std")]
+pub mod odds;
+#[cfg(feature = "std")]
+pub mod parse;
+#[cfg(feature = "std")]
+pub mod paths;
+#[cfg(feature = "std")]
+pub mod phase;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod replay;
+#[cfg(feature = "std")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod rngtest;
+#[cfg(feature = "std")]
+pub mod saves;
+#[cfg(feature = "std")]
+pub mod scoresheet;
+#[cfg(feature = "std")]
+pub mod seat;
+#[cfg(feature = "std")]
+pub mod sha256;
+#[cfg(feature = "std")]
+pub mod shell;
+#[cfg(feature = "std")]
+pub mod spectate;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod theme;
+#[cfg(feature = "std")]
+pub mod turn;
+
+pub use scoring::{
+    get_breakdown, get_score, is_of_a_kind, is_straight, is_three_pair, is_two_triplets, keep_repeats,
+    scoring_indices, strip_repeats, Combination, KindScoring, ScoreBreakdown, ScoringPart, ScoringRules,
+};
+
+#[cfg(feature = "std")]
+use confirm::ConfirmPolicy;
+#[cfg(feature = "std")]
+use console::Console;
+#[cfg(feature = "std")]
+use parse::{parse_bank_answer, parse_keep_action};
+#[cfg(feature = "std")]
+use seat::HumanSeat;
+#[cfg(feature = "std")]
+use turn::{play_round, PlayerAction};
 
 static TOTAL_DICE: i8 = 6;
 
@@ -18,6 +121,7 @@ static TOTAL_DICE: i8 = 6;
 /// let expected = HashMap::<i16, i16>::from([(1, 3), (2, 2), (3, 1)]);
 /// assert_eq!(expected, counts);
 /// ```
+#[cfg(feature = "std")]
 pub fn count_dice(dice: &Vec<i16>) -> HashMap<i16, i16> {
     let mut map: HashMap::<i16, i16> = HashMap::new();
     for die in dice {
@@ -27,343 +131,58 @@ pub fn count_dice(dice: &Vec<i16>) -> HashMap<i16, i16> {
     map
 }
 
-/// Returns true if dice contains 3 pairs.
-///
-/// ### Examples
-/// ```
-/// let three_pair = vec![1, 1, 2, 2, 3, 3];
-/// assert_eq!(true, farkle::is_three_pair(&three_pair));
-///
-/// let not_three_pair = vec![1, 1, 2, 2, 3, 4];
-/// assert_eq!(false, farkle::is_three_pair(&not_three_pair));
-///
-/// let four_of_a_kind = vec![1, 1, 2, 2, 2, 2];
-/// assert_eq!(false, farkle::is_three_pair(&four_of_a_kind));
-///
-/// let all_ones = vec![1, 1, 1, 1, 1, 1];
-/// assert_eq!(false, farkle::is_three_pair(&all_ones));
-/// ```
-pub fn is_three_pair(dice: &Vec<i16>) -> bool {
-    let map = count_dice(dice);
-    let mut pairs = 0;
-    for (_die, count) in map {
-        if count == 2 {
-            pairs += 1;
-        }
-    }
-    pairs == 3
-}
-
-/// Returns true if dice contains a straight.
-///
-/// ### Examples
-/// ```
-/// let dice = vec![1, 2, 3, 4, 5, 6];
-/// assert_eq!(true, farkle::is_straight(&dice));
-///
-/// let dice = vec![1, 2, 3, 4, 5, 5];
-/// assert_eq!(false, farkle::is_straight(&dice));
-///
-/// let dice = vec![1, 4, 2, 6, 5, 3];
-/// assert_eq!(true, farkle::is_straight(&dice));
-/// ```
-pub fn is_straight(dice: &[i16]) -> bool {
-    for i in 1..=6 {
-        if !dice.contains(&i) {
-            return false;
-        }
-    }
-    return true;
-}
-
-/// Returns true if dice contains num of a kind.
-///
-/// ### Examples
-/// ```
-/// let dice = vec![1, 1, 1, 1, 1, 1];
-/// assert_eq!(true, farkle::is_of_a_kind(6, &dice));
-///
-/// let dice = vec![1, 1, 1, 1, 1, 2];
-/// assert_eq!(false, farkle::is_of_a_kind(6, &dice));
-///
-/// let dice = vec![1, 1, 1, 1, 1, 2];
-/// assert_eq!(true, farkle::is_of_a_kind(5, &dice));
-///
-/// let dice = vec![1, 1, 1, 1, 2, 2];
-/// assert_eq!(false, farkle::is_of_a_kind(5, &dice));
-///
-/// let dice = vec![1, 1, 1, 1, 2, 2];
-/// assert_eq!(true, farkle::is_of_a_kind(4, &dice));
-///
-/// let dice = vec![1, 1, 1, 2, 2, 2];
-/// assert_eq!(false, farkle::is_of_a_kind(4, &dice));
-///
-/// let dice = vec![1, 1, 1, 2, 2, 2];
-/// assert_eq!(true, farkle::is_of_a_kind(3, &dice));
-/// ```
-pub fn is_of_a_kind(num: i16, dice: &Vec<i16>) -> bool {
-    let map = count_dice(dice);
-    let mut of_a_kind = false;
-    for (_die, count) in map {
-        if count >= num {
-            of_a_kind = true;
-        }
-    }
-    of_a_kind
-}
-
-/// Returns true if dice contains 2 triplets.
-///
-/// ### Examples
-/// ```
-/// # use std::collections::HashMap;
-/// let dice = vec![1, 1, 1, 2, 2, 2];
-/// assert_eq!(true, farkle::is_two_triplets(&dice));
-///
-/// let dice = vec![1, 1, 1, 2, 2, 3];
-/// assert_eq!(false, farkle::is_two_triplets(&dice));
-///
-/// let dice = vec![1, 1, 1, 1, 2, 2];
-/// assert_eq!(false, farkle::is_two_triplets(&dice));
-/// ```
-pub fn is_two_triplets(dice: &Vec<i16>) -> bool {
-    let map = count_dice(dice);
-    let mut triplets = 0;
-    for (_die, count) in map {
-        if count >= 3 {
-            triplets += 1;
-        }
-    }
-    triplets == 2
-}
-
-/// Returns a vector of dice with count < 3.
-///
-/// ### Examples
-/// ```
-/// # use std::collections::HashMap;
-/// let dice = vec![1, 1, 1, 2, 2, 2];
-/// assert_eq!(Vec::<i16>::new(), farkle::strip_repeats(&dice));
-///
-/// let dice = vec![1, 1, 1, 2, 2, 3];
-/// let mut expected = HashMap::new();
-/// expected.insert(2, 2); // 2 appears twice
-/// expected.insert(3, 1); // 3 appears once
-/// let mut actual = HashMap::new();
-/// for die in farkle::strip_repeats(&dice) {
-///     let count = actual.entry(die).or_insert(0);
-///     *count += 1;
-///         }
-/// assert_eq!(expected, actual);
-///
-/// let dice = vec![1, 1, 1, 1, 2, 2];
-/// assert_eq!(vec![2, 2], farkle::strip_repeats(&dice));
-/// ```
-pub fn strip_repeats(dice: &Vec<i16>) -> Vec<i16> {
-    let map = count_dice(dice);
-    let mut new_dice = Vec::new();
-    for (die, count) in map {
-        if count < 3  {
-            for _i in 0..count {
-                new_dice.push(die);
-            }
-        }
-    }
-    new_dice
-}
-
-/// Returns a vector of dice that are 3 of a kind or more.
-///
-/// ### Examples
-/// ```
-/// # use std::collections::HashMap;
-/// let dice = vec![1, 1, 1, 2, 2, 2];
-/// let mut expected = HashMap::new();
-/// expected.insert(2, 3); // 2 appears three times
-/// expected.insert(1, 3); // 1 appears three times
-/// let mut actual = HashMap::new();
-/// for die in farkle::keep_repeats(&dice) {
-///     let count = actual.entry(die).or_insert(0);
-///     *count += 1;
-///         }
-/// assert_eq!(expected, actual);
-///
-/// let dice = vec![1, 1, 1, 2, 2, 3];
-/// assert_eq!(vec![1, 1, 1], farkle::keep_repeats(&dice));
-///
-/// let dice = vec![1, 1, 1, 1, 2, 2];
-/// assert_eq!(vec![1, 1, 1, 1], farkle::keep_repeats(&dice));
-/// ```
-pub fn keep_repeats(dice: &Vec<i16>) -> Vec<i16> {
-    let map = count_dice(dice);
-    let mut new_dice = Vec::new();
-    for (die, count) in map {
-        if count >= 3 {
-            for _i in 0..count {
-                new_dice.push(die);
-            }
-        }
-    }
-    new_dice
-}
-
-// Game logic
-/// Returns the score for a given set of dice.
-///
-///
-/// ### Examples
-/// ```
-/// let dice = vec![1, 1, 1, 2, 2, 2];
-/// assert_eq!(2500, farkle::get_score(&dice));
-///
-/// let dice = vec![1, 1, 2, 2, 3, 3];
-/// assert_eq!(1500, farkle::get_score(&dice));
-///
-/// let dice = vec![1, 2, 3, 4, 5, 6];
-/// assert_eq!(1500, farkle::get_score(&dice));
-///
-/// let dice = vec![1, 1, 1, 1, 1, 1];
-/// assert_eq!(5000, farkle::get_score(&dice));
-///
-/// let dice = vec![1, 1, 1, 1, 1];
-/// assert_eq!(3000, farkle::get_score(&dice));
-///
-/// let dice = vec![1, 1, 1, 1];
-/// assert_eq!(2000, farkle::get_score(&dice));
-///
-/// let dice = vec![1, 1, 1];
-/// assert_eq!(1000, farkle::get_score(&dice));
-///
-/// let dice = vec![1, 1, 1, 5];
-/// assert_eq!(1050, farkle::get_score(&dice));
-///
-/// let dice = vec![1, 1, 1, 5, 5];
-/// assert_eq!(1100, farkle::get_score(&dice));
-///
-/// let dice = vec![1, 1, 5, 5, 5];
-/// assert_eq!(700, farkle::get_score(&dice));
+/// Ask if player wants to keep round score.
 ///
-/// let dice = vec![1, 1, 5];
-/// assert_eq!(250, farkle::get_score(&dice));
-/// ```
-pub fn get_score(dice: &Vec<i16>) -> i16 {
-    let mut score = 0;
-    if is_two_triplets(dice) {
-        return  2500;
-    }
-    else if is_three_pair(dice) {
-        return  1500;
-    }
-    else if is_straight(dice) {
-        return  1500;
-    }
-    else if is_of_a_kind(6, dice) {
-        return 5000;
-    }
-    else if is_of_a_kind(5, dice) {
-        score += 3000;
-    }
-    else if is_of_a_kind(4, dice) {
-        score += 2000;
-    }
-    else if is_of_a_kind(3, dice) {
-        let new_dice = keep_repeats(dice);
-        if new_dice[0] == 1 {
-            score += 1000;
-        } else {
-            score += new_dice[0] * 100;
-            println!("3score: {}", score);
+/// Returns true if player wants to keep score.
+#[cfg(feature = "std")]
+pub fn keep_score<R: BufRead, W: Write>(console: &mut Console<R, W>) -> bool {
+    loop {
+        let input = console.read_line();
+        // End of input means the player has gone.
+        let action = if input.is_empty() { Ok(PlayerAction::Quit) } else { parse_bank_answer(&input) };
+        match action {
+            Ok(PlayerAction::Bank) => return true,
+            Ok(PlayerAction::Quit) => quit(console),
+            Ok(_) => return false,
+            Err(error) => console.say(&error.to_string()),
         }
     }
-    // Score 1's and 5's.
-    let new_dice = strip_repeats(dice);
-    for die in new_dice {
-        if die == 1 {
-            score += 100;
-            println!("beep {}", score);
-        } else if die == 5 {
-            score += 50;
-        };
-    }
-    if score == 0 {
-        println!("No scoring dice.\nYour turn is over.\n");
-    }
-    score
 }
 
-/// Ask if player wants to keep round score.
-///
-/// Returns true if player wants to keep score.
-pub fn keep_score() -> bool {
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    if input.contains("y") {
-        return true;
-    }
-    if input.contains("q") {
-        println!("Thanks for playing!");
-        std::process::exit(0);
-    }
-    false
+/// Say goodbye and exit.
+#[cfg(feature = "std")]
+fn quit<R: BufRead, W: Write>(console: &mut Console<R, W>) -> ! {
+    console.say("Thanks for playing!");
+    std::process::exit(0);
 }
 
-/// Get dice to keep from the user.
+/// Get the player's action for a roll from the user.
 ///
-/// Returns a string of indices of dice to keep.
-pub fn get_dice_to_keep() -> String {
+/// Returns the action, with dice to keep as indices into the roll or as
+/// die values.
+#[cfg(feature = "std")]
+pub fn get_action<R: BufRead, W: Write>(console: &mut Console<R, W>) -> PlayerAction {
     loop {
-        let digits = "123456".to_string();
-        let mut dice_to_keep = String::new();
-        let mut valid_input = true;
-        println!("Enter dice to keep (1-6):");
+        console.say(console.theme.keep_prompt);
         // Get input from user.
-        io::stdin().read_line(&mut dice_to_keep)
-            .expect("Failed to read line");
-        // Check if player wants to quit.
-        if dice_to_keep.trim().contains("q") {
-            println!("Thanks for playing!");
-            std::process::exit(0);
-        }
-        println!("You entered: {}", dice_to_keep);
-        // Check for repeated digits.
-        let mut counts = HashMap::new();
-        for c in dice_to_keep.trim().chars() {
-            let count = counts.entry(c).or_insert(0);
-            *count += 1;
-            if *count > 1 {
-                valid_input = false;
-            }
-        }
-        if !valid_input {
-            println!("You can't keep the same die twice.");
+        let dice_to_keep = console.read_line();
+        // End of input means the player has gone.
+        let action = if dice_to_keep.is_empty() { Ok(PlayerAction::Quit) } else { parse_keep_action(&dice_to_keep) };
+        match action {
+            Ok(PlayerAction::Quit) => quit(console),
+            Ok(action) => return action,
+            Err(error) => console.say(&error.to_string()),
         }
-        // Check for invalid digits.
-        for c in dice_to_keep.trim().chars() {
-            if !digits.contains(c) {
-                println!("Invalid input {}. Try again.", c);
-                valid_input = false;
-            }
-        }
-    if !valid_input {
-        continue;
-    }
-    return dice_to_keep;
     }
 }
 
-/// Returns a vector of dice to keep.
-fn keep_dice(dice: Vec<i16>) -> Vec<i16> {
-    // Get dice to keep from user.
-    let input = get_dice_to_keep();
-
+/// Returns a vector of the dice at indices.
+#[cfg(feature = "std")]
+fn keep_dice(dice: &[i16], indices: &[usize]) -> Vec<i16> {
     // Create a mask of dice to keep.
     let mut keep_mask: Vec<bool> = Vec::new();
-    for c in 1..dice.len() + 1 {
-        if input.contains(&c.to_string()) {
-            keep_mask.push(true);
-        } else {
-            keep_mask.push(false);
-        };
+    for i in 0..dice.len() {
+        keep_mask.push(indices.contains(&i));
     }
     // Filter dice, keeping values at indices that are true in keep_mask.
     let kept_dice: Vec<i16> = dice
@@ -375,57 +194,67 @@ fn keep_dice(dice: Vec<i16>) -> Vec<i16> {
         // Keep the values in dice.
         .map(|(dice, _mask)| *dice)
         .collect();
-    println!("You kept: {:?}", kept_dice);
     kept_dice
 }
 
-/// Returns the score for a turn and the number of dice remaining.
-fn turn(num_dice: i8) -> (i16, i8) {
-    // num_dice is the number of dice to roll.
-    let num_dice:i8 = num_dice;
+/// Returns a reproducible rng for seed.
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// Returns num_dice dice rolled with rng.
+pub fn roll_dice<R: Rng>(num_dice: i8, rng: &mut R) -> Vec<i16> {
     let mut dice: Vec<i16> = Vec::new();
-    let mut rng = rand::thread_rng();
 
-    // Roll dice.
-    for _i in 0..num_dice {
+    // Always draw a full set so players sharing a seed see the same roll
+    // sequence no matter how many dice they kept.
+    for _i in 0..TOTAL_DICE {
         dice.push(rng.gen_range(1..=6));
     }
-    println!("Dice: {:?}", dice);
-    let keepers = keep_dice(dice);
-    let score = get_score(&keepers);
-
-    // Return score and number of dice to roll.
-    (score, keepers.len() as i8)
+    dice.truncate(num_dice as usize);
+    dice
 }
 
 /// Returns the score for a round.
 ///
-/// Take turns in a loop until turn score or number of dice kept is 0.
-pub fn round() -> i16 {
-    let mut round_score = 0;
-    let mut num_dice = TOTAL_DICE;
-    loop {
-        // Get score and number of dice to roll.
-        let (turn_score, num_kept) = turn(num_dice);
-
-        // No keepers or score == end of turn.
-        if num_kept == 0 || turn_score == 0 {
-            break;
-        }
-        // Calculate number of dice to roll for next turn.
-        num_dice = if num_dice - num_kept <= 0 {
-            println!("You got all keepers! Good job!\n");
-            TOTAL_DICE
-        } else {
-            num_dice - num_kept
-        };
-        round_score += turn_score;
-        // Ask if player wants to keep score.
-        println!("Your score this round is {}\nWould you like to keep this score?", round_score);
-        if keep_score() {
-            return round_score;
-        }
-    }
-// End of turn no keepers.
-0
+/// Roll in a loop until the player banks or the dice kept don't score.
+/// Dice are rolled with rng, and risky choices are confirmed per policy.
+/// The player plays through console.
+///
+/// ### Examples
+/// ```
+/// use farkle::confirm::ConfirmPolicy;
+/// use farkle::console::Console;
+/// let mut rng = farkle::daily::round_rng(1, 0);
+/// let mut console = Console::new("a\nn\na\ny\n".as_bytes(), Vec::new());
+/// let score = farkle::round(&mut rng, &ConfirmPolicy::never(), &mut console);
+/// let output = String::from_utf8(console.output).unwrap();
+/// let expected = "\
+/// Dice: [4, 3, 6, 1, 2, 4]
+/// Enter dice to keep (1-6), with 'b' after them to bank too, 'a' to keep all scoring dice, 'b' to keep them and bank, or 's' to see the scoring:
+/// You kept: [1]
+/// One 1 (100) = 100
+/// Your score this round is 100
+/// Would you like to keep this score?
+/// Dice: [3, 2, 5, 2, 6]
+/// This round:
+/// Roll 1: kept [1] +100
+///
+/// Enter dice to keep (1-6), with 'b' after them to bank too, 'a' to keep all scoring dice, 'b' to keep them and bank, or 's' to see the scoring:
+/// You kept: [5]
+/// One 5 (50) = 50
+/// Your score this round is 150
+/// Would you like to keep this score?
+/// Banking 150 points.
+/// ";
+/// assert_eq!(expected, output);
+/// assert_eq!(150, score);
+/// ```
+#[cfg(feature = "std")]
+pub fn round<R: Rng, I: BufRead, O: Write>(
+    rng: &mut R,
+    policy: &ConfirmPolicy,
+    console: &mut Console<I, O>,
+) -> u32 {
+    play_round(rng, &mut HumanSeat::new(console, policy))
 }
@@ -0,0 +1,621 @@
+//! A portable notation for games, a line per turn, for sharing games on
+//! forums and reading them back in. Unlike an audit log it's meant to be
+//! read and edited by hand.
+//!
+//! ```text
+//! [Player "Ann:10000"]
+//! [Player "Bob:10000"]
+//!
+//! 1. Ann 436124:1 35524:55 B 200
+//! 2. Bob 223466:- X 0
+//! ```
+//!
+//! Each roll is the dice rolled, a colon and the dice kept, or "-" for
+//! none, or "R" if a free roll rolled them again. A turn ends with B if it was banked, X if it farkled or T if the
+//! clock ran out, then the points it scored. Rules that aren't the usual
+//! ones are tags like `[Triples "300,200,300,400,500,600"]`,
+//! `[HotStreak "500"]`, `[CatchUp "25"]`, `[Opening "300"]`,
+//! `[ZilchPenalty "500"]`, `[OfAKind "doubling"]`, `[HotDice "roll"]`,
+//! `[Finish "exact"]`, `[FreeRolls "2"]`, `[Redemption "last"]` and `[Double "4"]`, and a game of a number of
+//! rounds has `[Rounds "10"]`.
+//! Players joining or leaving part way through are tags between the turns,
+//! like `[Joined "Cy:10000 at 350"]` and `[Left "Bob"]`.
+
+use crate::error::FarkleError;
+use crate::event::Event;
+use crate::game::{Game, GameSnapshot, Player, WinCondition};
+use crate::parse::{parse_dice, parse_player, positions_of};
+use crate::turn::{Decider, PlayerAction, TurnSnapshot};
+use crate::{KindScoring, ScoringRules, TOTAL_DICE};
+use std::fmt;
+use std::time::Duration;
+
+/// How a turn ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnEnd {
+    Banked,
+    Farkled,
+    /// The player's clock ran out, so the turn didn't count.
+    OutOfTime,
+}
+
+/// A roll and the dice kept from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotatedRoll {
+    pub dice: Vec<i16>,
+    pub kept: Vec<i16>,
+    /// The dice were rolled again with a free roll, keeping none.
+    pub free_roll: bool,
+}
+
+/// A player's turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotatedTurn {
+    pub player: String,
+    pub rolls: Vec<NotatedRoll>,
+    pub end: TurnEnd,
+    /// Points the turn scored.
+    pub score: u32,
+}
+
+/// A player joining or leaving a game part way through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RosterChange {
+    /// The player joined, on the score they have.
+    Joined(Player),
+    /// The player with this name left.
+    Left(String),
+}
+
+/// A game written down turn by turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    /// Players as they started, in turn order.
+    pub players: Vec<Player>,
+    pub win_condition: WinCondition,
+    pub rules: ScoringRules,
+    pub turns: Vec<NotatedTurn>,
+    /// Players joining or leaving, each after the number of turns given.
+    pub roster: Vec<(usize, RosterChange)>,
+    /// The turn being recorded, until its summary.
+    playing: Option<NotatedTurn>,
+}
+
+impl GameRecord {
+    /// Returns a record of a game with no turns yet.
+    pub fn new(players: Vec<Player>, win_condition: WinCondition, rules: ScoringRules) -> GameRecord {
+        GameRecord { players, win_condition, rules, turns: Vec::new(), roster: Vec::new(), playing: None }
+    }
+
+    /// Write down change, coming after the turns recorded so far.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Player, WinCondition};
+    /// use farkle::notation::{GameRecord, RosterChange};
+    /// use farkle::ScoringRules;
+    ///
+    /// let players = vec![Player::new("Ann"), Player::new("Bob")];
+    /// let mut record = GameRecord::new(players, WinCondition::TargetScore, ScoringRules::default());
+    /// record.change_roster(RosterChange::Left("Bob".to_string()));
+    /// record.change_roster(RosterChange::Joined(Player { score: 350, ..Player::new("Cy") }));
+    /// let game = record.game();
+    /// assert!(game.has_left("Bob"));
+    /// assert_eq!(350, game.players()[2].score);
+    /// assert!(record.to_string().ends_with("\n[Left \"Bob\"]\n[Joined \"Cy:10000 at 350\"]\n"));
+    /// assert_eq!(Ok(record.clone()), GameRecord::parse(&record.to_string()));
+    /// ```
+    pub fn change_roster(&mut self, change: RosterChange) {
+        self.roster.push((self.turns.len(), change));
+    }
+
+    /// Write down event, which happened during player's turn. A turn is
+    /// added once its summary is seen.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::game::{Player, WinCondition};
+    /// use farkle::notation::GameRecord;
+    /// use farkle::{get_breakdown, ScoringRules};
+    ///
+    /// let players = vec![Player::new("Ann"), Player::new("Bob")];
+    /// let mut record = GameRecord::new(players, WinCondition::TargetScore, ScoringRules::default());
+    /// let events = [
+    ///     Event::Rolled { dice: vec![4, 3, 6, 1, 2, 4] },
+    ///     Event::Kept { dice: vec![1], breakdown: get_breakdown(&[1]) },
+    ///     Event::Rolled { dice: vec![3, 5, 5, 2, 4] },
+    ///     Event::Kept { dice: vec![5, 5], breakdown: get_breakdown(&[5, 5]) },
+    ///     Event::Banked { score: 200 },
+    ///     Event::RoundSummary { player: "Ann".to_string(), banked: 200, farkled: false, rolls: 2 },
+    /// ];
+    /// for event in &events {
+    ///     record.record("Ann", event);
+    /// }
+    /// let expected = "[Player \"Ann:10000\"]\n[Player \"Bob:10000\"]\n\n1. Ann 436124:1 35524:55 B 200\n";
+    /// assert_eq!(expected, record.to_string());
+    /// ```
+    pub fn record(&mut self, player: &str, event: &Event) {
+        // A catch-up or double points bonus follows the summary, and the
+        // game works it out again from the scores.
+        if let Event::CatchUp { .. } | Event::DoublePoints { .. } | Event::DoubleRound { .. } = event {
+            return;
+        }
+        let turn = self.playing.get_or_insert_with(|| NotatedTurn {
+            player: player.to_string(),
+            rolls: Vec::new(),
+            end: TurnEnd::Banked,
+            score: 0,
+        });
+        match event {
+            Event::Rolled { dice } => {
+                turn.rolls.push(NotatedRoll { dice: dice.clone(), kept: Vec::new(), free_roll: false })
+            }
+            Event::Kept { dice, .. } => {
+                if let Some(roll) = turn.rolls.last_mut() {
+                    roll.kept = dice.clone();
+                }
+            }
+            Event::FreeRollUsed { .. } => {
+                if let Some(roll) = turn.rolls.last_mut() {
+                    roll.free_roll = true;
+                }
+            }
+            Event::Farkled => turn.end = TurnEnd::Farkled,
+            Event::RoundSummary { banked, farkled, .. } => {
+                // Only the clock stops a turn that didn't farkle scoring.
+                if *banked == 0 && !farkled {
+                    turn.end = TurnEnd::OutOfTime;
+                }
+                turn.score = *banked;
+                if let Some(turn) = self.playing.take() {
+                    self.turns.push(turn);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the game as it stands after the turns recorded.
+    ///
+    /// ### Examples
+    /// ```
+    /// let text = "[Player \"Ann:1000\"]\n[Player \"Bob\"]\n\n1. Ann 111234:111 B 1000\n";
+    /// let record = farkle::notation::GameRecord::parse(text).unwrap();
+    /// let game = record.game();
+    /// assert_eq!(1000, game.players()[0].score);
+    /// // Ann reached 1000, so Bob has one last turn.
+    /// assert_eq!(Some("Ann"), game.final_round_trigger().map(|player| player.name.as_str()));
+    /// assert_eq!("Bob", game.current_player().name);
+    /// ```
+    pub fn game(&self) -> Game {
+        self.replay(|_round, _seat, _game| {})
+    }
+
+    /// Returns the round each turn was played in and the player's total
+    /// after it, in turn order.
+    ///
+    /// ### Examples
+    /// ```
+    /// let text = "\
+    /// [Player \"Ann\"]
+    /// [Player \"Bob\"]
+    ///
+    /// 1. Ann 436124:1 B 100
+    /// 2. Bob 223466:- X 0
+    /// 3. Ann 111234:111 B 1000
+    /// ";
+    /// let record = farkle::notation::GameRecord::parse(text).unwrap();
+    /// assert_eq!(vec![(1, 100), (1, 0), (2, 1100)], record.totals());
+    /// ```
+    pub fn totals(&self) -> Vec<(u32, u32)> {
+        let mut totals = Vec::new();
+        self.replay(|round, seat, game| totals.push((round, game.players()[seat].score)));
+        totals
+    }
+
+    /// Returns the game after the turns recorded, telling after_turn the
+    /// round and seat of each turn, and the game after it.
+    fn replay(&self, mut after_turn: impl FnMut(u32, usize, &Game)) -> Game {
+        let mut game = Game::with_win_condition(self.players.clone(), self.win_condition);
+        game.set_rules(self.rules.clone());
+        // Players run out of time by running their clocks out.
+        let clocked = self.turns.iter().any(|turn| turn.end == TurnEnd::OutOfTime);
+        if clocked {
+            game.set_clock(Duration::MAX);
+        }
+        // Changes were checked as they were parsed or played.
+        let change_roster = |game: &mut Game, turns: usize| {
+            for (_after, change) in self.roster.iter().filter(|(after, _change)| *after == turns) {
+                let _ = match change {
+                    RosterChange::Joined(player) => {
+                        let time_left = if clocked { Some(Duration::MAX) } else { player.time_left };
+                        game.add_player(Player { time_left, ..player.clone() })
+                    }
+                    RosterChange::Left(name) => game.remove_player(name),
+                };
+            }
+        };
+        for (i, turn) in self.turns.iter().enumerate() {
+            change_roster(&mut game, i);
+            if turn.end == TurnEnd::OutOfTime {
+                game.charge_time(Duration::MAX);
+            }
+            game.set_free_rolls(self.free_rolls_after(game.free_rolls(), turn));
+            let (round, seat) = (game.round_number(), game.current_index());
+            game.record_round(turn.score);
+            after_turn(round, seat, &game);
+        }
+        change_roster(&mut game, self.turns.len());
+        game
+    }
+
+    /// Parse a game written with `to_string`, or by hand, checking every
+    /// turn could have happened.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::notation::{GameRecord, TurnEnd};
+    /// let text = "\
+    /// [Player \"Ann\"]
+    /// [Player \"Bob\"]
+    /// [HotStreak \"500\"]
+    ///
+    /// 1. Ann 436124:1 35524:55 B 200
+    /// 2. Bob 223466:- X 0
+    /// ";
+    /// let record = GameRecord::parse(text).unwrap();
+    /// assert_eq!(Some(500), record.rules.hot_streak_bonus);
+    /// assert_eq!(TurnEnd::Farkled, record.turns[1].end);
+    /// assert_eq!(Ok(record.clone()), GameRecord::parse(&record.to_string()));
+    ///
+    /// // Dice kept have to be dice rolled, and the points have to add up.
+    /// assert!(GameRecord::parse("[Player \"Ann\"]\n1. Ann 436124:5 B 50\n").is_err());
+    /// assert!(GameRecord::parse("[Player \"Ann\"]\n1. Ann 436124:1 B 150\n").is_err());
+    /// // And it has to be the player's turn.
+    /// assert!(GameRecord::parse("[Player \"Ann\"]\n[Player \"Bob\"]\n1. Bob 436124:1 B 100\n").is_err());
+    /// // Under some rules, hot dice can't be banked.
+    /// let cleared = "[Player \"Ann\"]\n[HotDice \"roll\"]\n1. Ann 111555:111555 B 2500\n";
+    /// assert!(GameRecord::parse(cleared).is_err());
+    /// assert!(GameRecord::parse(&cleared.replace("HotDice", "OfAKind")).is_err());
+    /// // Free rolls have to be earned before they're spent.
+    /// let free = "[Player \"Ann:10000\"]\n[FreeRolls \"1\"]\n\n1. Ann 123456:123456 223344:R 112346:11 B 1700\n";
+    /// let record = GameRecord::parse(free).unwrap();
+    /// assert!(record.turns[0].rolls[1].free_roll);
+    /// assert_eq!(free, record.to_string());
+    /// assert!(GameRecord::parse("[Player \"Ann\"]\n[FreeRolls \"1\"]\n1. Ann 223344:R 112346:11 B 200\n").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<GameRecord, FarkleError> {
+        let mut record = GameRecord::new(Vec::new(), WinCondition::TargetScore, ScoringRules::default());
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let bad_line = |why: &str| FarkleError::SaveCorrupt(format!("Line {}: {}", i + 1, why));
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(tag) = line.strip_prefix('[').and_then(|tag| tag.strip_suffix(']')) {
+                let (name, value) = tag.split_once(' ').ok_or_else(|| bad_line("a tag needs a name and a value."))?;
+                let value = value.trim().trim_matches('"');
+                let number = || value.parse::<u32>().map_err(|_| bad_line("that tag needs a number."));
+                match name {
+                    "Player" => record.players.push(parse_player(value).map_err(|e| bad_line(&e.to_string()))?),
+                    "Joined" | "Left" => {
+                        let change = parse_roster_change(name, value).map_err(|why| bad_line(&why))?;
+                        record.check_roster_change(&change).map_err(|why| bad_line(&why))?;
+                        record.change_roster(change);
+                    }
+                    "Rounds" => record.win_condition = WinCondition::RoundLimit(number()?),
+                    _ if set_rule_tag(&mut record.rules, name, value).map_err(|why| bad_line(&why))? => {}
+                    _ => return Err(bad_line(&format!("there's no {} tag.", name))),
+                }
+                continue;
+            }
+            let turn = parse_turn(line).map_err(|why| bad_line(&why))?;
+            record.check_turn(&turn).map_err(|why| bad_line(&why))?;
+            record.turns.push(turn);
+        }
+        if record.players.is_empty() {
+            return Err(FarkleError::SaveCorrupt("There are no [Player] tags.".to_string()));
+        }
+        Ok(record)
+    }
+
+    /// Check change could have come next.
+    fn check_roster_change(&self, change: &RosterChange) -> Result<(), String> {
+        if self.players.is_empty() {
+            return Err("players join and leave after the [Player] tags.".to_string());
+        }
+        let mut game = self.game();
+        let changed = match change {
+            RosterChange::Joined(player) => game.add_player(player.clone()),
+            RosterChange::Left(name) => game.remove_player(name),
+        };
+        changed.map_err(|error| error.to_string())
+    }
+
+    /// Check turn could have come next, under the rules.
+    fn check_turn(&self, turn: &NotatedTurn) -> Result<(), String> {
+        if self.players.is_empty() {
+            return Err("turns go after the [Player] tags.".to_string());
+        }
+        let game = self.game();
+        if game.is_over() {
+            return Err("the game is already over.".to_string());
+        }
+        if game.current_player().name != turn.player {
+            return Err(format!("it's {}'s turn, not {}'s.", game.current_player().name, turn.player));
+        }
+        let mut num_dice = TOTAL_DICE as usize;
+        let mut score = 0u32;
+        let mut clears = 0;
+        let mut held = game.free_rolls();
+        for (i, roll) in turn.rolls.iter().enumerate() {
+            let last = i + 1 == turn.rolls.len();
+            if roll.dice.len() != num_dice {
+                return Err(format!("roll {} has {} dice, but {} were left to roll.", i + 1, roll.dice.len(), num_dice));
+            }
+            if roll.free_roll {
+                if held == 0 {
+                    return Err(format!("roll {} uses a free roll, but there are none to use.", i + 1));
+                }
+                if last {
+                    return Err(format!("roll {} is rolled again with a free roll, so another roll follows.", i + 1));
+                }
+                held -= 1;
+                continue;
+            }
+            positions_of(&roll.dice, &roll.kept).map_err(|_| format!("roll {} keeps dice it didn't roll.", i + 1))?;
+            let kept = self.rules.breakdown(&roll.kept);
+            if kept.total() == 0 {
+                if last && turn.end == TurnEnd::Farkled {
+                    break;
+                }
+                return Err(format!("roll {} keeps nothing that scores, so the turn should farkle.", i + 1));
+            }
+            if kept.scoring_dice().len() != roll.kept.len() {
+                return Err(format!("roll {} keeps dice that don't score.", i + 1));
+            }
+            held = self.rules.earn_free_roll(held, &kept);
+            score = score.saturating_add(kept.total());
+            num_dice -= roll.kept.len();
+            if num_dice == 0 {
+                if last && self.rules.must_roll_hot_dice {
+                    return Err(format!("roll {} clears the dice, so they have to be rolled again.", i + 1));
+                }
+                num_dice = TOTAL_DICE as usize;
+                clears += 1;
+                if clears > 1 {
+                    score = score.saturating_add(self.rules.hot_streak_bonus.unwrap_or(0));
+                }
+            }
+        }
+        let expected = match turn.end {
+            TurnEnd::Banked => score,
+            TurnEnd::Farkled | TurnEnd::OutOfTime => 0,
+        };
+        if turn.rolls.is_empty() || turn.score != expected {
+            return Err(format!("the turn scores {}, not {}.", expected, turn.score));
+        }
+        Ok(())
+    }
+
+    /// Returns the free rolls a player holding held has after turn.
+    fn free_rolls_after(&self, mut held: u32, turn: &NotatedTurn) -> u32 {
+        for roll in &turn.rolls {
+            held = if roll.free_roll {
+                held.saturating_sub(1)
+            } else {
+                self.rules.earn_free_roll(held, &self.rules.breakdown(&roll.kept))
+            };
+        }
+        held
+    }
+}
+
+/// A decider whose events are written down in a record.
+pub struct Recorded<'a, D: ?Sized> {
+    decider: &'a mut D,
+    record: &'a mut GameRecord,
+    player: &'a str,
+}
+
+impl<'a, D: Decider + ?Sized> Recorded<'a, D> {
+    /// Returns decider, writing down its events for player in record.
+    pub fn new(decider: &'a mut D, record: &'a mut GameRecord, player: &'a str) -> Recorded<'a, D> {
+        Recorded { decider, record, player }
+    }
+}
+
+impl<'a, D: Decider + ?Sized> Decider for Recorded<'a, D> {
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> PlayerAction {
+        self.decider.choose_keep(dice, turn)
+    }
+
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> PlayerAction {
+        self.decider.choose_bank(turn)
+    }
+
+    fn remark(&mut self) -> Option<String> {
+        self.decider.remark()
+    }
+
+    fn on_turn_start(&mut self, game: &GameSnapshot) {
+        self.decider.on_turn_start(game);
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        self.record.record(self.player, event);
+        self.decider.on_event(event);
+    }
+}
+
+/// Returns a turn from a line like "1. Ann 436124:1 B 100", unchecked.
+fn parse_turn(line: &str) -> Result<NotatedTurn, String> {
+    let bad_turn = || "a turn should look like '1. Ann 436124:1 35524:55 B 200'.".to_string();
+    let (_number, rest) = line.split_once(". ").ok_or_else(bad_turn)?;
+    let tokens: Vec<&str> = rest.split_whitespace().collect();
+    let first_roll = tokens.iter().position(|token| token.contains(':')).ok_or_else(bad_turn)?;
+    let (end, score) = match tokens[first_roll..] {
+        [.., end, score] => (end, score.parse().map_err(|_| bad_turn())?),
+        _ => return Err(bad_turn()),
+    };
+    let end = match end {
+        "B" => TurnEnd::Banked,
+        "X" => TurnEnd::Farkled,
+        "T" => TurnEnd::OutOfTime,
+        _ => return Err(bad_turn()),
+    };
+    let mut rolls = Vec::new();
+    for token in &tokens[first_roll..tokens.len() - 2] {
+        let (dice, kept) = token.split_once(':').ok_or_else(bad_turn)?;
+        let dice = parse_dice(dice).map_err(|e| e.to_string())?;
+        let free_roll = kept == "R";
+        let kept = if kept == "-" || free_roll { Vec::new() } else { parse_dice(kept).map_err(|e| e.to_string())? };
+        rolls.push(NotatedRoll { dice, kept, free_roll });
+    }
+    let player = tokens[..first_roll].join(" ");
+    Ok(NotatedTurn { player, rolls, end, score })
+}
+
+/// Returns the change in a tag like `[Joined "Cy:10000 at 350"]` or
+/// `[Left "Bob"]`, unchecked.
+fn parse_roster_change(tag: &str, value: &str) -> Result<RosterChange, String> {
+    if tag == "Left" {
+        return Ok(RosterChange::Left(value.to_string()));
+    }
+    let bad_join = || "Joined should look like [Joined \"Cy:10000 at 350\"].".to_string();
+    let (player, score) = value.rsplit_once(" at ").ok_or_else(bad_join)?;
+    let player = parse_player(player).map_err(|e| e.to_string())?;
+    let score = score.trim().parse().map_err(|_| bad_join())?;
+    Ok(RosterChange::Joined(Player { score, ..player }))
+}
+
+/// Returns the tags writing down how rules differ from the usual ones, as
+/// names and values.
+///
+/// ### Examples
+/// ```
+/// use farkle::notation::{rule_tags, set_rule_tag};
+/// use farkle::{KindScoring, ScoringRules};
+/// let rules = ScoringRules { kinds: KindScoring::Doubling, hot_streak_bonus: Some(500), ..ScoringRules::default() };
+/// let tags = rule_tags(&rules);
+/// assert_eq!(vec![("HotStreak", "500".to_string()), ("OfAKind", "doubling".to_string())], tags);
+///
+/// let mut read = ScoringRules::default();
+/// for (name, value) in &tags {
+///     assert_eq!(Ok(true), set_rule_tag(&mut read, name, value));
+/// }
+/// assert_eq!(rules, read);
+/// assert_eq!(Ok(false), set_rule_tag(&mut read, "Player", "Ann"));
+/// assert!(set_rule_tag(&mut read, "OfAKind", "tripling").is_err());
+/// ```
+pub fn rule_tags(rules: &ScoringRules) -> Vec<(&'static str, String)> {
+    let mut tags = Vec::new();
+    if rules.triples != ScoringRules::STANDARD.triples {
+        let triples: Vec<String> = rules.triples.iter().map(|points| points.to_string()).collect();
+        tags.push(("Triples", triples.join(",")));
+    }
+    let numbers = [
+        ("HotStreak", rules.hot_streak_bonus),
+        ("CatchUp", rules.catch_up_percent),
+        ("Opening", rules.opening_score),
+        ("ZilchPenalty", rules.zilch_penalty),
+    ];
+    tags.extend(numbers.into_iter().filter_map(|(name, number)| number.map(|number| (name, number.to_string()))));
+    if rules.kinds == KindScoring::Doubling {
+        tags.push(("OfAKind", "doubling".to_string()));
+    }
+    if rules.must_roll_hot_dice {
+        tags.push(("HotDice", "roll".to_string()));
+    }
+    if rules.exact_finish {
+        tags.push(("Finish", "exact".to_string()));
+    }
+    if let Some(most) = rules.free_rolls {
+        tags.push(("FreeRolls", most.to_string()));
+    }
+    if rules.redemption_round {
+        tags.push(("Redemption", "last".to_string()));
+    }
+    if let Some(every) = rules.double_every {
+        tags.push(("Double", every.to_string()));
+    }
+    tags
+}
+
+/// Set the rule the tag name says to value, in rules.
+///
+/// Returns false if name isn't a rule's tag, or why value can't be read.
+pub fn set_rule_tag(rules: &mut ScoringRules, name: &str, value: &str) -> Result<bool, String> {
+    let number = || value.parse::<u32>().map_err(|_| "that tag needs a number.".to_string());
+    match name {
+        "HotStreak" => rules.hot_streak_bonus = Some(number()?),
+        "CatchUp" => rules.catch_up_percent = Some(number()?),
+        "Opening" => rules.opening_score = Some(number()?),
+        "ZilchPenalty" => rules.zilch_penalty = Some(number()?),
+        "OfAKind" if value == "doubling" => rules.kinds = KindScoring::Doubling,
+        "HotDice" if value == "roll" => rules.must_roll_hot_dice = true,
+        "Finish" if value == "exact" => rules.exact_finish = true,
+        "FreeRolls" => rules.free_rolls = Some(number()?),
+        "Double" => rules.double_every = Some(number()?),
+        "Redemption" if value == "last" => rules.redemption_round = true,
+        "Triples" => {
+            let points: Option<Vec<u32>> = value.split(',').map(|points| points.trim().parse().ok()).collect();
+            rules.triples = points
+                .and_then(|points| points.try_into().ok())
+                .ok_or_else(|| "Triples needs 6 numbers, three 1s first.".to_string())?;
+        }
+        "OfAKind" | "HotDice" | "Finish" | "Redemption" => return Err(format!("{} can't be \"{}\".", name, value)),
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Returns dice as digits, like "436124", or "-" for none.
+fn digits(dice: &[i16]) -> String {
+    if dice.is_empty() {
+        return "-".to_string();
+    }
+    dice.iter().map(|die| die.to_string()).collect()
+}
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for player in &self.players {
+            writeln!(f, "[Player \"{}:{}\"]", player.name, player.target)?;
+        }
+        if let WinCondition::RoundLimit(rounds) = self.win_condition {
+            writeln!(f, "[Rounds \"{}\"]", rounds)?;
+        }
+        for (name, value) in rule_tags(&self.rules) {
+            writeln!(f, "[{} \"{}\"]", name, value)?;
+        }
+        writeln!(f)?;
+        let roster_changes = |f: &mut fmt::Formatter, turns: usize| {
+            for (_after, change) in self.roster.iter().filter(|(after, _change)| *after == turns) {
+                match change {
+                    RosterChange::Joined(player) => {
+                        writeln!(f, "[Joined \"{}:{} at {}\"]", player.name, player.target, player.score)?
+                    }
+                    RosterChange::Left(name) => writeln!(f, "[Left \"{}\"]", name)?,
+                }
+            }
+            Ok(())
+        };
+        for (i, turn) in self.turns.iter().enumerate() {
+            roster_changes(f, i)?;
+            write!(f, "{}. {}", i + 1, turn.player)?;
+            for roll in &turn.rolls {
+                let kept = if roll.free_roll { "R".to_string() } else { digits(&roll.kept) };
+                write!(f, " {}:{}", digits(&roll.dice), kept)?;
+            }
+            let end = match turn.end {
+                TurnEnd::Banked => "B",
+                TurnEnd::Farkled => "X",
+                TurnEnd::OutOfTime => "T",
+            };
+            writeln!(f, " {} {}", end, turn.score)?;
+        }
+        roster_changes(f, self.turns.len())
+    }
+}
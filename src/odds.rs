@@ -0,0 +1,233 @@
+//! Odds of rolling dice.
+
+use crate::{Combination, ScoreBreakdown, ScoringRules, TOTAL_DICE};
+
+/// Returns every possible roll of num_dice dice, in order.
+///
+/// ### Examples
+/// ```
+/// let rolls = farkle::odds::all_rolls(2);
+/// assert_eq!(36, rolls.len());
+/// assert_eq!(vec![1, 1], rolls[0]);
+/// assert_eq!(vec![1, 2], rolls[1]);
+/// assert_eq!(vec![6, 6], rolls[35]);
+/// ```
+pub fn all_rolls(num_dice: i8) -> Vec<Vec<i16>> {
+    let mut rolls = vec![Vec::new()];
+    for _i in 0..num_dice {
+        let mut longer = Vec::new();
+        for roll in rolls {
+            for face in 1..=6 {
+                let mut next = roll.clone();
+                next.push(face);
+                longer.push(next);
+            }
+        }
+        rolls = longer;
+    }
+    rolls
+}
+
+/// Returns the chance that rolling num_dice dice scores nothing under
+/// rules.
+///
+/// ### Examples
+/// ```
+/// use farkle::odds::farkle_chance;
+/// use farkle::ScoringRules;
+/// let rules = ScoringRules::default();
+/// let chance = farkle_chance(&rules, 1);
+/// assert!((chance - 4.0 / 6.0).abs() < 1e-9);
+///
+/// let chance = farkle_chance(&rules, 6);
+/// assert!((chance - 1080.0 / 46656.0).abs() < 1e-9);
+/// ```
+pub fn farkle_chance(rules: &ScoringRules, num_dice: i8) -> f64 {
+    let rolls = all_rolls(num_dice);
+    let farkles = rolls
+        .iter()
+        .filter(|roll| rules.score(roll) == 0)
+        .count();
+    farkles as f64 / rolls.len() as f64
+}
+
+/// Returns how many of num_dice dice score under rules on average, keeping
+/// every die that can.
+///
+/// ### Examples
+/// ```
+/// use farkle::odds::expected_scoring_dice;
+/// let rules = farkle::ScoringRules::default();
+/// let expected = expected_scoring_dice(&rules, 1);
+/// assert!((expected - 2.0 / 6.0).abs() < 1e-9);
+/// assert!(expected_scoring_dice(&rules, 6) > expected_scoring_dice(&rules, 5));
+/// ```
+pub fn expected_scoring_dice(rules: &ScoringRules, num_dice: i8) -> f64 {
+    let rolls = all_rolls(num_dice);
+    let scoring: usize = rolls.iter().map(|roll| rules.breakdown(roll).scoring_dice().len()).sum();
+    scoring as f64 / rolls.len() as f64
+}
+
+/// Returns the points a turn with at_stake has on average after rolling
+/// num_dice dice once more under rules, keeping every die that scores and
+/// banking. Farkles count as nothing, and no dice left means hot dice,
+/// rolling six.
+///
+/// ### Examples
+/// ```
+/// use farkle::odds::expected_after_roll;
+/// let rules = farkle::ScoringRules::default();
+/// // With one die left, a 1 or a 5 scores and anything else farkles.
+/// let expected = expected_after_roll(&rules, 1, 500);
+/// assert!((expected - (600.0 + 550.0) / 6.0).abs() < 1e-9);
+/// assert!(expected_after_roll(&rules, 0, 500) > 500.0);
+/// ```
+pub fn expected_after_roll(rules: &ScoringRules, num_dice: i8, at_stake: u32) -> f64 {
+    let num_dice = if num_dice == 0 { TOTAL_DICE } else { num_dice };
+    let rolls = all_rolls(num_dice);
+    let points: f64 = rolls
+        .iter()
+        .map(|roll| match rules.score(roll) {
+            0 => 0.0,
+            score => f64::from(at_stake.saturating_add(score)),
+        })
+        .sum();
+    points / rolls.len() as f64
+}
+
+/// How a keep leaves a turn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeepOdds {
+    pub keep: ScoreBreakdown,
+    /// Points at stake after the keep, what banking would score.
+    pub points: u32,
+    pub dice_left: i8,
+    /// Chance the next roll farkles, hot dice rolling six.
+    pub farkle_chance: f64,
+    /// Points on average after rolling once more and banking.
+    pub roll_again: f64,
+}
+
+/// Returns the odds after every keep from dice under rules, most points
+/// first, in a turn that already had at_stake.
+///
+/// ### Examples
+/// ```
+/// use farkle::odds::explore;
+/// use farkle::ScoringRules;
+/// let options = explore(&ScoringRules::default(), &[2, 3, 3, 3, 5, 6], 0);
+/// let points: Vec<u32> = options.iter().map(|option| option.points).collect();
+/// assert_eq!(vec![350, 300, 50], points);
+/// assert_eq!(5, options[2].dice_left);
+/// // Keeping just the 5 leaves better odds than keeping all of it.
+/// assert!(options[2].farkle_chance < options[0].farkle_chance);
+///
+/// let rules = ScoringRules { triples: [1000, 200, 600, 400, 500, 600], ..ScoringRules::default() };
+/// assert_eq!(650, explore(&rules, &[2, 3, 3, 3, 5, 6], 0)[0].points);
+/// ```
+pub fn explore(rules: &ScoringRules, dice: &[i16], at_stake: u32) -> Vec<KeepOdds> {
+    rules
+        .scoring_subsets(dice)
+        .into_iter()
+        .map(|keep| {
+            let points = at_stake.saturating_add(keep.total());
+            let dice_left = (dice.len() - keep.scoring_dice().len()) as i8;
+            let next_roll = if dice_left == 0 { TOTAL_DICE } else { dice_left };
+            KeepOdds {
+                points,
+                dice_left,
+                farkle_chance: farkle_chance(rules, next_roll),
+                roll_again: expected_after_roll(rules, dice_left, points),
+                keep,
+            }
+        })
+        .collect()
+}
+
+/// Returns the points at stake from which banking beats rolling num_dice
+/// dice once more under rules, on average, rounded up to 50s.
+///
+/// Rolling on risks the points at stake on the chance of a farkle, for what
+/// the dice score on average, so the threshold is where the two are equal.
+/// It looks one roll ahead, so it banks a little early when there are many
+/// dice left to roll.
+///
+/// ### Examples
+/// ```
+/// use farkle::odds::bank_threshold;
+/// use farkle::ScoringRules;
+/// let rules = ScoringRules::default();
+/// // With one die left, 25 on average against a two in three chance of a farkle.
+/// assert_eq!(50, bank_threshold(&rules, 1));
+/// assert!(bank_threshold(&rules, 6) > bank_threshold(&rules, 3));
+/// ```
+pub fn bank_threshold(rules: &ScoringRules, num_dice: i8) -> u32 {
+    let rolls = all_rolls(num_dice);
+    let scores: Vec<u32> = rolls.iter().map(|roll| rules.score(roll)).collect();
+    let farkles = scores.iter().filter(|score| **score == 0).count();
+    if farkles == 0 {
+        return u32::MAX;
+    }
+    let threshold = scores.iter().map(|score| f64::from(*score)).sum::<f64>() / farkles as f64;
+    (threshold / 50.0).ceil() as u32 * 50
+}
+
+/// Returns what would have scored under rules instead of a farkle rolling
+/// dice, one die different, and how often that many dice score.
+///
+/// A roll that scores wasn't a farkle for want of a die, so there's nothing
+/// to explain.
+///
+/// ### Examples
+/// ```
+/// use farkle::odds::explain_farkle;
+/// use farkle::ScoringRules;
+/// let rules = ScoringRules::STANDARD;
+/// let explained = explain_farkle(&rules, &[3, 3, 4]);
+/// assert_eq!("You needed a 1, a 5 or a third 3. 3 dice score 72% of the time.", explained);
+///
+/// assert_eq!("", explain_farkle(&rules, &[2, 2, 2, 5, 3, 5]));
+///
+/// let explained = explain_farkle(&rules, &[2, 2, 3, 3, 4, 6]);
+/// assert_eq!(
+///     "You needed a 1, a 5, a third 2, a third 3, a 4 for three pairs or a 6 for three pairs. \
+///      6 dice score 98% of the time.",
+///     explained
+/// );
+/// ```
+pub fn explain_farkle(rules: &ScoringRules, dice: &[i16]) -> String {
+    if rules.score(dice) > 0 {
+        return String::new();
+    }
+    // Each miss, and whether it's a combination rather than a single.
+    let mut needed: Vec<(bool, String)> = Vec::new();
+    for face in 1..=6 {
+        for i in 0..dice.len() {
+            let mut instead = dice.to_vec();
+            instead[i] = face;
+            let breakdown = rules.breakdown(&instead);
+            let best = breakdown.parts.iter().find(|part| part.combination != Combination::Single);
+            let miss = match best.map(|part| part.combination) {
+                None if breakdown.total() > 0 => (false, format!("a {}", face)),
+                Some(Combination::ThreeOfAKind) => (true, format!("a third {}", face)),
+                Some(Combination::Straight) => (true, format!("a {} for a straight", face)),
+                Some(Combination::ThreePairs) => (true, format!("a {} for three pairs", face)),
+                _ => continue,
+            };
+            if !needed.contains(&miss) {
+                needed.push(miss);
+            }
+        }
+    }
+    // Singles first, they're what to hope for most.
+    needed.sort_by_key(|(combination, _miss)| *combination);
+    let needed: Vec<String> = needed.into_iter().map(|(_combination, miss)| miss).collect();
+    let chance = 1.0 - farkle_chance(rules, dice.len() as i8);
+    let needed = match needed.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => return String::new(),
+    };
+    let dice_count = if dice.len() == 1 { "1 die scores".to_string() } else { format!("{} dice score", dice.len()) };
+    format!("You needed {}. {} {:.0}% of the time.", needed, dice_count, chance * 100.0)
+}
@@ -0,0 +1,313 @@
+//! Parsing what players type at the keep prompt.
+
+use crate::error::FarkleError;
+use crate::game::Player;
+use crate::turn::PlayerAction;
+use crate::{ScoringRules, TOTAL_DICE};
+use std::collections::HashSet;
+
+/// Number words accepted for die values, in English, Spanish, French and
+/// German. "six" is the same in English and French.
+static VALUE_WORDS: [(&str, i16); 23] = [
+    ("one", 1), ("two", 2), ("three", 3), ("four", 4), ("five", 5), ("six", 6),
+    ("uno", 1), ("dos", 2), ("tres", 3), ("cuatro", 4), ("cinco", 5), ("seis", 6),
+    ("un", 1), ("deux", 2), ("trois", 3), ("quatre", 4), ("cinq", 5),
+    ("eins", 1), ("zwei", 2), ("drei", 3), ("vier", 4), ("fünf", 5), ("sechs", 6),
+];
+
+/// Dice a player asked to keep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeepSelection {
+    /// Positions in the roll, counting from 0.
+    Positions(Vec<usize>),
+    /// Die values, one per die kept.
+    Values(Vec<i16>),
+}
+
+/// Returns the die value a word names, if any.
+///
+/// ### Examples
+/// ```
+/// assert_eq!(Some(5), farkle::parse::value_word("five"));
+/// assert_eq!(Some(5), farkle::parse::value_word("Cinq"));
+/// assert_eq!(None, farkle::parse::value_word("seven"));
+/// ```
+pub fn value_word(word: &str) -> Option<i16> {
+    let word = word.to_lowercase();
+    VALUE_WORDS
+        .iter()
+        .find(|(name, _value)| *name == word)
+        .map(|(_name, value)| *value)
+}
+
+/// Returns the lowercase words in input, split on whitespace and commas.
+///
+/// ### Examples
+/// ```
+/// assert_eq!(vec!["1", "3", "five"], farkle::parse::tokenize(" 1,3  Five\n"));
+/// assert!(farkle::parse::tokenize(" \n").is_empty());
+/// ```
+pub fn tokenize(input: &str) -> Vec<String> {
+    input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Parse the answer to the keep prompt.
+///
+/// A single command word is a shortcut, anything else is dice to keep,
+/// followed by 'b' to bank them too.
+///
+/// ### Examples
+/// ```
+/// use farkle::parse::parse_keep_action;
+/// use farkle::turn::PlayerAction;
+/// assert_eq!(Ok(PlayerAction::KeepAllScoring), parse_keep_action("a\n"));
+/// assert_eq!(Ok(PlayerAction::Bank), parse_keep_action("b"));
+/// assert_eq!(Ok(PlayerAction::ShowBreakdown), parse_keep_action(" s "));
+/// assert_eq!(Ok(PlayerAction::FreeRoll), parse_keep_action("free"));
+/// assert_eq!(Ok(PlayerAction::Quit), parse_keep_action("quit"));
+/// assert_eq!(Ok(PlayerAction::Keep(vec![1, 0])), parse_keep_action("21"));
+/// assert_eq!(Ok(PlayerAction::KeepValues(vec![5])), parse_keep_action("five"));
+/// let keep_and_bank = PlayerAction::KeepAndBank(Box::new(PlayerAction::Keep(vec![0, 4])));
+/// assert_eq!(Ok(keep_and_bank), parse_keep_action("1 5 bank"));
+/// let keep_all = PlayerAction::KeepAndBank(Box::new(PlayerAction::KeepAllScoring));
+/// assert_eq!(Ok(keep_all), parse_keep_action("a b"));
+///
+/// assert!(parse_keep_action("").is_err());
+/// assert!(parse_keep_action("s b").is_err());
+/// assert!(parse_keep_action("b b").is_err());
+/// assert!(parse_keep_action("1 q").is_err());
+/// assert!(parse_keep_action("135x").is_err());
+/// ```
+pub fn parse_keep_action(input: &str) -> Result<PlayerAction, FarkleError> {
+    let tokens = tokenize(input);
+    if tokens.is_empty() {
+        return Err(FarkleError::InvalidKeep("Enter the dice to keep.".to_string()));
+    }
+    if let [keep @ .., last] = tokens.as_slice() {
+        if !keep.is_empty() && (last == "b" || last == "bank") {
+            return match parse_keep_action(&keep.join(" "))? {
+                keep @ (PlayerAction::Keep(_) | PlayerAction::KeepValues(_) | PlayerAction::KeepAllScoring) => {
+                    Ok(PlayerAction::KeepAndBank(Box::new(keep)))
+                }
+                _ => Err(FarkleError::InvalidKeep("Say which dice to keep before 'b', like '1 5 b'.".to_string())),
+            };
+        }
+    }
+    if tokens.len() == 1 {
+        match tokens[0].as_str() {
+            "a" | "all" => return Ok(PlayerAction::KeepAllScoring),
+            "b" | "bank" => return Ok(PlayerAction::Bank),
+            "s" | "score" => return Ok(PlayerAction::ShowBreakdown),
+            "f" | "free" => return Ok(PlayerAction::FreeRoll),
+            "q" | "quit" => return Ok(PlayerAction::Quit),
+            _ => (),
+        }
+    }
+    match parse_keep(&tokens.join(" "))? {
+        KeepSelection::Positions(positions) => Ok(PlayerAction::Keep(positions)),
+        KeepSelection::Values(values) => Ok(PlayerAction::KeepValues(values)),
+    }
+}
+
+/// Parse the answer to whether to bank the round score.
+///
+/// ### Examples
+/// ```
+/// use farkle::parse::parse_bank_answer;
+/// use farkle::turn::PlayerAction;
+/// assert_eq!(Ok(PlayerAction::Bank), parse_bank_answer("y\n"));
+/// assert_eq!(Ok(PlayerAction::Bank), parse_bank_answer("Yes"));
+/// assert_eq!(Ok(PlayerAction::RollAgain), parse_bank_answer("n"));
+/// assert_eq!(Ok(PlayerAction::RollAgain), parse_bank_answer("roll"));
+/// assert_eq!(Ok(PlayerAction::Quit), parse_bank_answer(" q "));
+///
+/// assert!(parse_bank_answer("yes please quit").is_err());
+/// assert!(parse_bank_answer("maybe").is_err());
+/// assert!(parse_bank_answer("").is_err());
+/// ```
+pub fn parse_bank_answer(input: &str) -> Result<PlayerAction, FarkleError> {
+    let tokens = tokenize(input);
+    let answer = match tokens.as_slice() {
+        [answer] => answer.as_str(),
+        _ => "",
+    };
+    match answer {
+        "y" | "yes" | "b" | "bank" => Ok(PlayerAction::Bank),
+        "n" | "no" | "r" | "roll" => Ok(PlayerAction::RollAgain),
+        "q" | "quit" => Ok(PlayerAction::Quit),
+        _ => Err(FarkleError::InvalidInput("Enter 'y' to bank, 'n' to roll again, or 'q' to quit.".to_string())),
+    }
+}
+
+/// Parse the dice to keep from input.
+///
+/// Digits are positions of dice in the roll (1-6), written together or
+/// separated by spaces or commas. Number words are die values.
+///
+/// ### Examples
+/// ```
+/// use farkle::parse::{parse_keep, KeepSelection};
+/// assert_eq!(Ok(KeepSelection::Positions(vec![0, 2, 4])), parse_keep("135"));
+/// assert_eq!(Ok(KeepSelection::Positions(vec![0, 2, 4])), parse_keep("1,3,5"));
+/// assert_eq!(Ok(KeepSelection::Positions(vec![0, 2, 4])), parse_keep(" 1 3, 5\n"));
+/// assert_eq!(Ok(KeepSelection::Values(vec![1, 1, 5])), parse_keep("one one five"));
+/// assert_eq!(Ok(KeepSelection::Values(vec![1, 5])), parse_keep("uno, cinq"));
+/// assert_eq!(Ok(KeepSelection::Positions(vec![])), parse_keep(""));
+///
+/// assert!(parse_keep("135x").is_err());
+/// assert!(parse_keep("1 7").is_err());
+/// assert!(parse_keep("0").is_err());
+/// assert!(parse_keep("1 1").is_err());
+/// assert!(parse_keep("1 five").is_err());
+/// ```
+pub fn parse_keep(input: &str) -> Result<KeepSelection, FarkleError> {
+    let mut positions = Vec::new();
+    let mut values = Vec::new();
+    for token in tokenize(input) {
+        if let Some(value) = value_word(&token) {
+            values.push(value);
+            continue;
+        }
+        for c in token.chars() {
+            match c.to_digit(10) {
+                Some(digit @ 1..=6) => positions.push(digit as usize - 1),
+                _ => return Err(FarkleError::InvalidKeep(format!("Invalid input {}. Try again.", c))),
+            }
+        }
+    }
+    if !positions.is_empty() && !values.is_empty() {
+        return Err(FarkleError::InvalidKeep("Use either dice positions or dice values, not both.".to_string()));
+    }
+    if !values.is_empty() {
+        return Ok(KeepSelection::Values(values));
+    }
+    // Check for repeated positions.
+    let mut seen = HashSet::new();
+    if !positions.iter().all(|position| seen.insert(*position)) {
+        return Err(FarkleError::InvalidKeep("You can't keep the same die twice.".to_string()));
+    }
+    Ok(KeepSelection::Positions(positions))
+}
+
+/// Returns the positions in dice of values, using each die once.
+///
+/// ### Examples
+/// ```
+/// let dice = vec![5, 1, 3, 1, 2, 4];
+/// assert_eq!(Ok(vec![1, 3, 0]), farkle::parse::positions_of(&dice, &[1, 1, 5]));
+/// assert!(farkle::parse::positions_of(&dice, &[5, 5]).is_err());
+/// ```
+pub fn positions_of(dice: &[i16], values: &[i16]) -> Result<Vec<usize>, FarkleError> {
+    let mut used = vec![false; dice.len()];
+    let mut positions = Vec::new();
+    for value in values {
+        match (0..dice.len()).find(|i| !used[*i] && dice[*i] == *value) {
+            Some(i) => {
+                used[i] = true;
+                positions.push(i);
+            }
+            None => return Err(FarkleError::InvalidKeep(format!("There's no {} left to keep.", value))),
+        }
+    }
+    Ok(positions)
+}
+
+/// Returns each different keep of only scoring dice from dice under rules,
+/// best first, typed as the positions to keep.
+///
+/// ### Examples
+/// ```
+/// use farkle::parse::keep_completions;
+/// let completions = keep_completions(&[4, 1, 6, 5, 6, 3], &farkle::ScoringRules::STANDARD);
+/// assert_eq!(vec!["24", "2", "4"], completions);
+/// assert!(keep_completions(&[2, 3, 4, 6], &farkle::ScoringRules::STANDARD).is_empty());
+/// ```
+pub fn keep_completions(dice: &[i16], rules: &ScoringRules) -> Vec<String> {
+    let keeps = rules.scoring_subsets(dice);
+    keeps
+        .iter()
+        .filter_map(|keep| positions_of(dice, &keep.scoring_dice()).ok())
+        .map(|mut positions| {
+            positions.sort_unstable();
+            positions.iter().map(|i| (i + 1).to_string()).collect()
+        })
+        .collect()
+}
+
+/// Parse dice values, like "1 1 3 5 5 5", "113555" or "one one three".
+///
+/// ### Examples
+/// ```
+/// use farkle::parse::parse_dice;
+/// assert_eq!(Ok(vec![1, 1, 3, 5, 5, 5]), parse_dice("1 1 3 5 5 5"));
+/// assert_eq!(Ok(vec![1, 1, 3, 5]), parse_dice("113 five"));
+/// assert!(parse_dice("1 7").is_err());
+/// assert!(parse_dice("1 1 1 1 1 1 1").is_err());
+/// assert!(parse_dice("").is_err());
+/// ```
+pub fn parse_dice(input: &str) -> Result<Vec<i16>, FarkleError> {
+    let mut dice = Vec::new();
+    for token in tokenize(input) {
+        if let Some(value) = value_word(&token) {
+            dice.push(value);
+            continue;
+        }
+        for c in token.chars() {
+            match c.to_digit(10) {
+                Some(value @ 1..=6) => dice.push(value as i16),
+                _ => return Err(FarkleError::InvalidInput(format!("'{}' isn't a die. Dice are 1 to 6.", token))),
+            }
+        }
+    }
+    if dice.is_empty() || dice.len() > TOTAL_DICE as usize {
+        return Err(FarkleError::InvalidInput(format!("Give 1 to {} dice, like 1 1 3 5 5 5.", TOTAL_DICE)));
+    }
+    Ok(dice)
+}
+
+/// Parse a player from a name with an optional target score, like "Ann:12000".
+///
+/// ### Examples
+/// ```
+/// use farkle::game::Player;
+/// use farkle::parse::parse_player;
+/// assert_eq!(Ok(Player::new("Ann")), parse_player("Ann"));
+/// assert_eq!(Ok(Player::with_target("Bob", 8000)), parse_player("Bob:8000"));
+/// assert!(parse_player("Bob:lots").is_err());
+/// assert!(parse_player(":8000").is_err());
+/// assert!(parse_player("Bob:0").is_err());
+/// // Scores go up in 50s, like the game's target.
+/// assert!(parse_player("Bob:8020").is_err());
+/// ```
+pub fn parse_player(spec: &str) -> Result<Player, FarkleError> {
+    let (name, target) = match spec.split_once(':') {
+        Some((name, target)) => (name.trim(), Some(target.trim())),
+        None => (spec.trim(), None),
+    };
+    if name.is_empty() {
+        return Err(FarkleError::InvalidConfig(vec![format!("Player {} needs a name.", spec)]));
+    }
+    match target {
+        None => Ok(Player::new(name)),
+        Some(target) => match target.parse::<u32>() {
+            Ok(target) if target > 0 && target.is_multiple_of(50) => Ok(Player::with_target(name, target)),
+            Ok(target) if target > 0 => {
+                let problem = format!(
+                    "Scores go up in 50s, so {}'s target of {} plays like {}. Pick a multiple of 50.",
+                    name,
+                    target,
+                    target.next_multiple_of(50)
+                );
+                Err(FarkleError::InvalidConfig(vec![problem]))
+            }
+            _ => {
+                let problem = format!("Target score for {} must be a number from 1 to {}.", name, u32::MAX);
+                Err(FarkleError::InvalidConfig(vec![problem]))
+            }
+        },
+    }
+}
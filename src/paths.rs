@@ -0,0 +1,68 @@
+//! Where farkle keeps its files between games: saves, the daily record,
+//! transcripts, exported dice and the practice bot's history. They go in the platform's data directory,
+//! `$XDG_DATA_HOME/farkle` on Linux, `Application Support` on macOS and
+//! `AppData\Roaming` on Windows, or wherever `FARKLE_DATA_DIR` says.
+
+use crate::error::FarkleError;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The environment variable that names a data directory of its own.
+pub const DATA_DIR_VAR: &str = "FARKLE_DATA_DIR";
+
+/// Returns the directory farkle's files are kept in: `FARKLE_DATA_DIR` if
+/// it's set, else the platform's data directory, where the `directories`
+/// crate would put it, else the working directory.
+///
+/// ### Examples
+/// ```
+/// std::env::set_var(farkle::paths::DATA_DIR_VAR, "/tmp/farkle-data");
+/// assert_eq!(std::path::PathBuf::from("/tmp/farkle-data"), farkle::paths::data_dir());
+/// ```
+pub fn data_dir() -> PathBuf {
+    let var = |name: &str| env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+    let platform = if cfg!(windows) {
+        var("APPDATA").map(|dir| dir.join("farkle").join("data"))
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| home.join("Library").join("Application Support").join("farkle"))
+    } else {
+        let data_home = var("XDG_DATA_HOME").or_else(|| var("HOME").map(|home| home.join(".local").join("share")));
+        data_home.map(|dir| dir.join("farkle"))
+    };
+    var(DATA_DIR_VAR).or(platform).unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Returns where the file called name is kept, making the data directory
+/// if it's not there yet.
+///
+/// A file of that name in the working directory, where farkle used to keep
+/// them, is copied over the first time it's asked for. It's left where it
+/// is, since a file of that name may not be farkle's.
+///
+/// ### Examples
+/// ```
+/// let dir = std::env::temp_dir().join("farkle_paths_doctest");
+/// std::env::set_var(farkle::paths::DATA_DIR_VAR, &dir);
+/// let path = farkle::paths::data_file("doctest.txt").unwrap();
+/// assert_eq!(dir.join("doctest.txt"), path);
+/// assert!(dir.is_dir());
+/// std::fs::remove_dir(&dir).unwrap();
+///
+/// std::fs::write("farkle_paths_doctest.txt", "old").unwrap();
+/// let path = farkle::paths::data_file("farkle_paths_doctest.txt").unwrap();
+/// assert_eq!("old", std::fs::read_to_string(&path).unwrap());
+/// assert!(std::path::Path::new("farkle_paths_doctest.txt").is_file());
+/// std::fs::remove_file("farkle_paths_doctest.txt").unwrap();
+/// std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub fn data_file(name: &str) -> Result<PathBuf, FarkleError> {
+    let dir = data_dir();
+    fs::create_dir_all(&dir).map_err(FarkleError::io(&dir.to_string_lossy()))?;
+    let path = dir.join(name);
+    let old = Path::new(name);
+    if !path.exists() && old.is_file() {
+        fs::copy(old, &path).map_err(FarkleError::io(name))?;
+    }
+    Ok(path)
+}
@@ -0,0 +1,166 @@
+//! A whole game as a state machine, for frontends like GUIs that run their
+//! own loop and ask the game what it's waiting on.
+
+use crate::event::Event;
+use crate::game::{Game, Player};
+use crate::odds::farkle_chance;
+use crate::turn::{Pending, PlayerAction, TurnEngine, TurnSnapshot};
+use crate::ScoreBreakdown;
+use rand::Rng;
+
+/// How risky rolling again is, for a player deciding whether to bank.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Odds {
+    /// Dice that would be rolled.
+    pub dice_left: i8,
+    /// Chance those dice score nothing.
+    pub farkle_chance: f64,
+    /// What rolling again puts at stake.
+    pub at_risk: u32,
+}
+
+/// What a game is waiting on, with what a frontend needs to show it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GamePhase {
+    /// Which dice player keeps from roll. Options are every way to keep
+    /// only scoring dice, best first, and empty on a farkle.
+    AwaitingKeep {
+        player: String,
+        roll: Vec<i16>,
+        options: Vec<ScoreBreakdown>,
+        turn: TurnSnapshot,
+    },
+    /// Whether player banks or rolls again.
+    AwaitingBankDecision { player: String, odds: Odds, turn: TurnSnapshot },
+    /// Nothing, the game is over.
+    Over { winner: Option<Player> },
+}
+
+/// A game and the turn being played in it.
+///
+/// Clocks are left to the frontend, which knows how long players take.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameEngine {
+    game: Game,
+    turn: Option<TurnEngine>,
+}
+
+impl GameEngine {
+    /// Returns game with its first turn rolled, and what happened.
+    pub fn start<R: Rng>(game: Game, rng: &mut R) -> (GameEngine, Vec<Event>) {
+        let mut engine = GameEngine { game, turn: None };
+        let events = engine.start_turn(rng);
+        (engine, events)
+    }
+
+    /// Returns the game as it stands.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Returns what the game is waiting on.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// use farkle::phase::{GameEngine, GamePhase};
+    /// use farkle::turn::PlayerAction;
+    ///
+    /// let mut rng = farkle::daily::round_rng(1, 0);
+    /// let game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+    /// let (mut engine, _) = GameEngine::start(game, &mut rng);
+    /// match engine.next() {
+    ///     GamePhase::AwaitingKeep { player, roll, options, .. } => {
+    ///         assert_eq!("Ann", player);
+    ///         assert_eq!(vec![4, 3, 6, 1, 2, 4], roll);
+    ///         assert_eq!(100, options[0].total());
+    ///     }
+    ///     phase => panic!("{:?}", phase),
+    /// }
+    ///
+    /// engine.respond(PlayerAction::KeepValues(vec![1]), &mut rng);
+    /// match engine.next() {
+    ///     GamePhase::AwaitingBankDecision { odds, .. } => {
+    ///         assert_eq!(5, odds.dice_left);
+    ///         assert_eq!(100, odds.at_risk);
+    ///     }
+    ///     phase => panic!("{:?}", phase),
+    /// }
+    ///
+    /// // Banking moves on to Bob's turn.
+    /// let events = engine.respond(PlayerAction::Bank, &mut rng);
+    /// assert!(events.contains(&farkle::event::Event::Banked { score: 100 }));
+    /// assert_eq!(100, engine.game().players()[0].score);
+    /// assert!(matches!(engine.next(), GamePhase::AwaitingKeep { player, .. } if player == "Bob"));
+    /// ```
+    pub fn next(&self) -> GamePhase {
+        let turn = match &self.turn {
+            Some(turn) if !self.game.is_over() => turn,
+            _ => return GamePhase::Over { winner: self.game.winner().cloned() },
+        };
+        let player = self.game.current_player().name.clone();
+        let snapshot = turn.snapshot();
+        match turn.pending() {
+            Pending::Keep(roll) => GamePhase::AwaitingKeep {
+                player,
+                roll: roll.clone(),
+                options: snapshot.rules.scoring_subsets(roll),
+                turn: snapshot,
+            },
+            Pending::Bank => {
+                let odds = Odds {
+                    dice_left: snapshot.num_dice,
+                    farkle_chance: farkle_chance(&snapshot.rules, snapshot.num_dice),
+                    at_risk: snapshot.score,
+                };
+                GamePhase::AwaitingBankDecision { player, odds, turn: snapshot }
+            }
+            // Turns are recorded as soon as they end, so this doesn't last.
+            Pending::Over(_) => GamePhase::Over { winner: self.game.winner().cloned() },
+        }
+    }
+
+    /// Make the choice the game is waiting on, rolling with rng.
+    ///
+    /// Returns what happened. When that ends the turn, it's recorded with
+    /// its summary and any catch-up bonus, and the next player's first
+    /// roll follows. A response that doesn't fit is rejected, like with
+    /// `TurnEngine::respond`.
+    pub fn respond<R: Rng>(&mut self, action: PlayerAction, rng: &mut R) -> Vec<Event> {
+        let turn = match &mut self.turn {
+            Some(turn) if !self.game.is_over() => turn,
+            _ => return Vec::new(),
+        };
+        let mut events = turn.respond(action, rng);
+        let score = match turn.score() {
+            Some(score) => score,
+            None => return events,
+        };
+        let player = self.game.current_player().name.clone();
+        events.extend(turn.summary(&player));
+        let bonus = self.game.catch_up_bonus(score);
+        if bonus > 0 {
+            events.push(Event::CatchUp { player: player.clone(), bonus });
+        }
+        let doubled = self.game.double_bonus(score);
+        if doubled > 0 {
+            events.push(Event::DoublePoints { player, bonus: doubled });
+        }
+        self.game.set_free_rolls(turn.free_rolls());
+        self.game.record_round(score);
+        events.extend(self.start_turn(rng));
+        events
+    }
+
+    /// Roll the next player's first dice, unless the game is over.
+    fn start_turn<R: Rng>(&mut self, rng: &mut R) -> Vec<Event> {
+        if self.game.is_over() {
+            self.turn = None;
+            return Vec::new();
+        }
+        let (mut turn, events) = TurnEngine::start_with_rules(rng, self.game.rules().clone());
+        turn.set_free_rolls(self.game.free_rolls());
+        self.turn = Some(turn);
+        events
+    }
+}
@@ -0,0 +1,195 @@
+//! Many games at once, shared between threads, like for a server hosting
+//! games for players who come and go.
+
+use crate::clock::{Clock, SystemClock};
+use crate::game::Game;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock, Weak};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// A game in the registry, and when it was last looked up.
+#[derive(Debug)]
+struct Entry {
+    game: Arc<RwLock<Game>>,
+    used: Mutex<Instant>,
+}
+
+/// Games in progress by id. Games nobody has looked up for a while are
+/// taken to be abandoned and expire.
+#[derive(Debug)]
+pub struct GameRegistry {
+    games: RwLock<HashMap<u64, Entry>>,
+    next_id: AtomicU64,
+    idle: Duration,
+}
+
+impl GameRegistry {
+    /// Returns an empty registry, expiring games left idle for idle.
+    pub fn new(idle: Duration) -> GameRegistry {
+        GameRegistry { games: RwLock::new(HashMap::new()), next_id: AtomicU64::new(1), idle }
+    }
+
+    /// Add game, created at now, and returns its id.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// use farkle::registry::GameRegistry;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let registry = GameRegistry::new(Duration::from_secs(600));
+    /// let now = Instant::now();
+    /// let first = registry.create(Game::new(vec![Player::new("Ann")]), now);
+    /// let second = registry.create(Game::new(vec![Player::new("Bob")]), now);
+    /// assert_ne!(first, second);
+    /// assert_eq!(2, registry.len());
+    /// ```
+    pub fn create(&self, game: Game, now: Instant) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let entry = Entry { game: Arc::new(RwLock::new(game)), used: Mutex::new(now) };
+        self.games.write().unwrap_or_else(PoisonError::into_inner).insert(id, entry);
+        id
+    }
+
+    /// Returns the game with id, looked up at now, if it hasn't expired.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// use farkle::registry::GameRegistry;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let registry = GameRegistry::new(Duration::from_secs(600));
+    /// let id = registry.create(Game::new(vec![Player::new("Ann")]), Instant::now());
+    /// let game = registry.get(id, Instant::now()).unwrap();
+    /// game.write().unwrap().record_round(350);
+    /// assert_eq!(350, registry.get(id, Instant::now()).unwrap().read().unwrap().players()[0].score);
+    /// assert!(registry.get(id + 1, Instant::now()).is_none());
+    /// ```
+    pub fn get(&self, id: u64, now: Instant) -> Option<Arc<RwLock<Game>>> {
+        let games = self.games.read().unwrap_or_else(PoisonError::into_inner);
+        let entry = games.get(&id)?;
+        let mut used = entry.used.lock().unwrap_or_else(PoisonError::into_inner);
+        *used = (*used).max(now);
+        Some(Arc::clone(&entry.game))
+    }
+
+    /// Remove the game with id, like when it's over, and returns it.
+    pub fn remove(&self, id: u64) -> Option<Arc<RwLock<Game>>> {
+        let mut games = self.games.write().unwrap_or_else(PoisonError::into_inner);
+        games.remove(&id).map(|entry| entry.game)
+    }
+
+    /// Remove games not looked up for longer than the idle time by now,
+    /// and returns their ids.
+    ///
+    /// Anyone still holding an expired game can finish with it, but it
+    /// can't be looked up again.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Game, Player};
+    /// use farkle::registry::GameRegistry;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let start = Instant::now();
+    /// let registry = GameRegistry::new(Duration::from_secs(600));
+    /// let left = registry.create(Game::new(vec![Player::new("Ann")]), start);
+    /// let playing = registry.create(Game::new(vec![Player::new("Bob")]), start);
+    /// registry.get(playing, start + Duration::from_secs(500));
+    /// assert!(registry.expire(start + Duration::from_secs(600)).is_empty());
+    /// assert_eq!(vec![left], registry.expire(start + Duration::from_secs(700)));
+    /// assert!(registry.get(playing, start + Duration::from_secs(700)).is_some());
+    /// ```
+    pub fn expire(&self, now: Instant) -> Vec<u64> {
+        let mut games = self.games.write().unwrap_or_else(PoisonError::into_inner);
+        let mut expired: Vec<u64> = games
+            .iter()
+            .filter(|(_id, entry)| {
+                let used = *entry.used.lock().unwrap_or_else(PoisonError::into_inner);
+                now.saturating_duration_since(used) > self.idle
+            })
+            .map(|(id, _entry)| *id)
+            .collect();
+        expired.sort_unstable();
+        for id in &expired {
+            games.remove(id);
+        }
+        expired
+    }
+
+    /// Returns how many games there are.
+    pub fn len(&self) -> usize {
+        self.games.read().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    /// Returns true if there are no games.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Start a thread expiring abandoned games in registry every period.
+///
+/// The thread stops once everyone else has dropped the registry.
+///
+/// ### Examples
+/// ```
+/// use farkle::game::{Game, Player};
+/// use farkle::registry::{sweep, GameRegistry};
+/// use std::sync::Arc;
+/// use std::time::{Duration, Instant};
+///
+/// let registry = Arc::new(GameRegistry::new(Duration::ZERO));
+/// registry.create(Game::new(vec![Player::new("Ann")]), Instant::now());
+/// let sweeper = sweep(&registry, Duration::from_millis(10));
+/// while !registry.is_empty() {
+///     std::thread::sleep(Duration::from_millis(10));
+/// }
+/// drop(registry);
+/// sweeper.join().unwrap();
+/// ```
+pub fn sweep(registry: &Arc<GameRegistry>, period: Duration) -> JoinHandle<()> {
+    sweep_with(registry, period, SystemClock)
+}
+
+/// Start a thread expiring abandoned games in registry every period on
+/// clock, like `sweep`.
+///
+/// ### Examples
+/// ```
+/// use farkle::clock::{Clock, TestClock};
+/// use farkle::game::{Game, Player};
+/// use farkle::registry::{sweep_with, GameRegistry};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// // A game idle for an hour is gone, without waiting an hour.
+/// let clock = TestClock::new(Duration::ZERO);
+/// let registry = Arc::new(GameRegistry::new(Duration::from_secs(3600)));
+/// registry.create(Game::new(vec![Player::new("Ann")]), clock.now());
+/// let sweeper = sweep_with(&registry, Duration::from_secs(60), clock.clone());
+/// while !registry.is_empty() {
+///     std::thread::yield_now();
+/// }
+/// assert!(clock.since_1970() >= Duration::from_secs(3600));
+/// drop(registry);
+/// sweeper.join().unwrap();
+/// ```
+pub fn sweep_with<C>(registry: &Arc<GameRegistry>, period: Duration, clock: C) -> JoinHandle<()>
+where
+    C: Clock + Send + 'static,
+{
+    let registry: Weak<GameRegistry> = Arc::downgrade(registry);
+    thread::spawn(move || loop {
+        clock.sleep(period);
+        match registry.upgrade() {
+            Some(registry) => {
+                registry.expire(clock.now());
+            }
+            None => return,
+        }
+    })
+}
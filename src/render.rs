@@ -0,0 +1,399 @@
+//! Text formatting of game events for the command line, and for chat
+//! frontends like Discord, Slack or Matrix.
+
+use crate::event::Event;
+use crate::game::Game;
+use crate::notation::{GameRecord, TurnEnd};
+use crate::theme::Theme;
+use crate::turn::TurnSnapshot;
+use std::time::Duration;
+
+/// Returns the text shown for an event.
+///
+/// ### Examples
+/// ```
+/// use farkle::event::Event;
+/// assert_eq!("Dice: [1, 5, 3]", farkle::render::render(&Event::Rolled { dice: vec![1, 5, 3] }));
+/// assert_eq!("Banking 350 points.", farkle::render::render(&Event::Banked { score: 350 }));
+/// let summary = Event::RoundSummary { player: "Ann".to_string(), banked: 0, farkled: true, rolls: 3 };
+/// assert_eq!("Round score: 0 (farkled on roll 3)", farkle::render::render(&summary));
+/// ```
+pub fn render(event: &Event) -> String {
+    render_with(event, &Theme::CLASSIC)
+}
+
+/// Returns the text shown for an event, in theme.
+///
+/// ### Examples
+/// ```
+/// use farkle::event::Event;
+/// use farkle::render::render_with;
+/// use farkle::theme::Theme;
+/// assert_eq!("Dice: 1 5 3", render_with(&Event::Rolled { dice: vec![1, 5, 3] }, &Theme::MINIMAL));
+/// let banked = render_with(&Event::Banked { score: 350 }, &Theme::HIGH_CONTRAST);
+/// assert_eq!("\x1b[1;30;103mBanking 350 points.\x1b[0m", banked);
+/// ```
+pub fn render_with(event: &Event, theme: &Theme) -> String {
+    match event {
+        Event::Rolled { dice } => format!("Dice: {}", theme.dice(dice)),
+        Event::RolledForOrder { player, die } => {
+            format!("{} rolled {} for the turn order.", player, theme.dice(&[*die]))
+        }
+        Event::TurnOrder { players } => format!("Turn order: {}.", players.join(", ")),
+        Event::Joined { player, score } => format!("{} joins the game on {}.", player, render_points(*score)),
+        Event::Left { player } => format!("{} leaves the game.", player),
+        Event::SameRollAgain { .. } => "Exactly the same six dice as last time!".to_string(),
+        Event::DieRevealed { die, position, of } => format!("Die {} of {}: {}", position, of, theme.dice(&[*die])),
+        Event::LabelledDice { dice, kept: false } => format!("By die: {}", by_die(dice, |die| die.to_string())),
+        Event::LabelledDice { dice, kept: true } => format!("Kept by die: {}", by_die(dice, |die| die.to_string())),
+        Event::Kept { dice, breakdown } if breakdown.total() > 0 => {
+            format!("You kept: {}\n{}", theme.dice(dice), breakdown.explain())
+        }
+        Event::Kept { dice, .. } => format!("You kept: {}", theme.dice(dice)),
+        Event::HotDice => theme.good("You got all keepers! Good job!") + "\n",
+        Event::HotStreak { clears, bonus } => {
+            theme.good(&format!("Hot streak! {} clears this turn, +{} bonus.", clears, bonus)) + "\n"
+        }
+        Event::FreeRollEarned { held } => {
+            theme.good(&format!("Free roll earned! You hold {}.", count_free_rolls(*held)))
+        }
+        Event::FreeRollUsed { held } => format!("Using a free roll, {} left.", held),
+        Event::Farkled => theme.bad("No scoring dice.\nYour turn is over.") + "\n",
+        Event::Banked { score } => theme.good(&format!("Banking {} points.", score)),
+        Event::Rejected { message } => message.clone(),
+        Event::Remark { text } => text.clone(),
+        Event::SinceLastTurn { summary } => summary.clone(),
+        Event::CatchUp { bonus, .. } => format!("Catch-up bonus for being behind: +{}", bonus),
+        Event::DoublePoints { bonus, .. } => format!("Double points round: +{}", bonus),
+        Event::TotalRevealed { player, score, place } => {
+            format!("In {} place, with {} points: {}!", ordinal(*place), render_points(*score), player)
+        }
+        Event::DoubleRound { round } => {
+            format!("Round {} is a double points round: everything banked counts twice.", round)
+        }
+        Event::TimedOut { .. } => "Out of time! Your scoring dice were kept and banked.".to_string(),
+        Event::RoundSummary { farkled: true, rolls, .. } => format!("Round score: 0 (farkled on roll {})", rolls),
+        Event::RoundSummary { banked, rolls, .. } => format!("Round score: {} ({})", banked, count_rolls(*rolls)),
+    }
+}
+
+/// Returns "1 roll", "2 rolls" and so on.
+fn count_rolls(rolls: u32) -> String {
+    if rolls == 1 {
+        "1 roll".to_string()
+    } else {
+        format!("{} rolls", rolls)
+    }
+}
+
+/// Returns "1 free roll", "2 free rolls" and so on.
+fn count_free_rolls(held: u32) -> String {
+    if held == 1 {
+        "1 free roll".to_string()
+    } else {
+        format!("{} free rolls", held)
+    }
+}
+
+/// Returns the text shown for an event in someone else's turn.
+///
+/// ### Examples
+/// ```
+/// use farkle::event::Event;
+/// use farkle::render::render_other;
+/// assert_eq!("Robo rolled [1, 5, 3]", render_other("Robo", &Event::Rolled { dice: vec![1, 5, 3] }));
+/// assert_eq!(
+///     "Robo: \"I'm feeling lucky!\"",
+///     render_other("Robo", &Event::Remark { text: "I'm feeling lucky!".to_string() })
+/// );
+/// ```
+pub fn render_other(name: &str, event: &Event) -> String {
+    render_other_with(name, event, &Theme::CLASSIC)
+}
+
+/// Returns the text shown for an event in someone else's turn, in theme.
+pub fn render_other_with(name: &str, event: &Event, theme: &Theme) -> String {
+    match event {
+        Event::Rolled { dice } => format!("{} rolled {}", name, theme.dice(dice)),
+        Event::RolledForOrder { player, die } => {
+            format!("{} rolled {} for the turn order.", player, theme.dice(&[*die]))
+        }
+        Event::TurnOrder { players } => format!("Turn order: {}.", players.join(", ")),
+        Event::Joined { player, score } => format!("{} joins the game on {}.", player, render_points(*score)),
+        Event::Left { player } => format!("{} leaves the game.", player),
+        Event::SameRollAgain { .. } => format!("{} rolled exactly the same six dice again!", name),
+        Event::DieRevealed { die, position, of } => {
+            format!("{}'s die {} of {}: {}", name, position, of, theme.dice(&[*die]))
+        }
+        Event::LabelledDice { dice, kept: false } => {
+            format!("{}'s dice: {}", name, by_die(dice, |die| die.to_string()))
+        }
+        Event::LabelledDice { dice, kept: true } => format!("{} kept {}", name, by_die(dice, |die| die.to_string())),
+        Event::Kept { dice, .. } => format!("{} kept {}", name, theme.dice(dice)),
+        Event::HotDice => theme.good(&format!("{} got hot dice!", name)) + "\n",
+        Event::HotStreak { clears, bonus } => {
+            theme.good(&format!("{} is on a hot streak: {} clears, +{} bonus!", name, clears, bonus)) + "\n"
+        }
+        Event::FreeRollEarned { held } => format!("{} earned a free roll, now holding {}.", name, held),
+        Event::FreeRollUsed { .. } => format!("{} used a free roll.", name),
+        Event::Farkled => theme.bad(&format!("{} farkled!", name)) + "\n",
+        Event::Banked { score } => theme.good(&format!("{} banked {} points.", name, score)),
+        Event::Rejected { message } => format!("{} tried something odd: {}", name, message),
+        Event::Remark { text } => format!("{}: \"{}\"", name, text),
+        Event::SinceLastTurn { summary } => summary.clone(),
+        Event::CatchUp { player, bonus } => format!("{} gets a catch-up bonus of +{} for being behind.", player, bonus),
+        Event::DoublePoints { player, bonus } => format!("{} gets +{} for the double points round.", player, bonus),
+        Event::TotalRevealed { player, score, place } => {
+            format!("In {} place, with {} points: {}!", ordinal(*place), render_points(*score), player)
+        }
+        Event::DoubleRound { round } => {
+            format!("Round {} is a double points round: everything banked counts twice.", round)
+        }
+        Event::TimedOut { player } => format!("{} is idle, so their scoring dice were kept and banked.", player),
+        Event::RoundSummary { player, farkled: true, rolls, .. } => {
+            format!("{}'s round score: 0 (farkled on roll {})", player, rolls)
+        }
+        Event::RoundSummary { player, banked, rolls, .. } => {
+            format!("{}'s round score: {} ({})", player, banked, count_rolls(*rolls))
+        }
+    }
+}
+
+/// Returns dice as die face emoji, like "⚀⚄⚂".
+///
+/// ### Examples
+/// ```
+/// assert_eq!("⚀⚄⚂⚅", farkle::render::render_dice_emoji(&[1, 5, 3, 6]));
+/// ```
+pub fn render_dice_emoji(dice: &[i16]) -> String {
+    let faces = ['⚀', '⚁', '⚂', '⚃', '⚄', '⚅'];
+    dice.iter()
+        .map(|die| usize::try_from(*die - 1).ok().and_then(|i| faces.get(i)).copied().unwrap_or('?'))
+        .collect()
+}
+
+/// Returns a line for an event in name's turn, short and with emoji, for
+/// a chat message.
+///
+/// ### Examples
+/// ```
+/// use farkle::event::Event;
+/// use farkle::render::render_chat;
+/// assert_eq!("🎲 Ann rolled ⚀⚄⚂", render_chat("Ann", &Event::Rolled { dice: vec![1, 5, 3] }));
+/// assert_eq!("💥 Ann farkled!", render_chat("Ann", &Event::Farkled));
+/// assert_eq!("🏦 Ann banked 1,350.", render_chat("Ann", &Event::Banked { score: 1350 }));
+/// ```
+pub fn render_chat(name: &str, event: &Event) -> String {
+    match event {
+        Event::Rolled { dice } => format!("🎲 {} rolled {}", name, render_dice_emoji(dice)),
+        Event::RolledForOrder { player, die } => {
+            format!("🎲 {} rolled {} for the turn order.", player, render_dice_emoji(&[*die]))
+        }
+        Event::TurnOrder { players } => format!("🔢 Turn order: {}.", players.join(", ")),
+        Event::Joined { player, score } => format!("👋 {} joins the game on {}.", player, render_points(*score)),
+        Event::Left { player } => format!("👋 {} leaves the game.", player),
+        Event::SameRollAgain { dice } => format!("🔁 {} rolled {} again!", name, render_dice_emoji(dice)),
+        Event::DieRevealed { die, .. } => format!("🎲 {} rolls {}…", name, render_dice_emoji(&[*die])),
+        Event::LabelledDice { dice, kept: false } => {
+            format!("🏷️ {}'s dice: {}", name, by_die(dice, |die| render_dice_emoji(&[die])))
+        }
+        Event::LabelledDice { dice, kept: true } => {
+            format!("🏷️ {} kept {}", name, by_die(dice, |die| render_dice_emoji(&[die])))
+        }
+        Event::Kept { dice, breakdown } if breakdown.total() > 0 => {
+            format!("✋ {} kept {} (+{})", name, render_dice_emoji(dice), render_points(breakdown.total()))
+        }
+        Event::Kept { dice, .. } => format!("✋ {} kept {}", name, render_dice_emoji(dice)),
+        Event::HotDice => format!("🔥 {} has hot dice!", name),
+        Event::HotStreak { clears, bonus } => format!("🔥 {} cleared {} times, +{}!", name, clears, render_points(*bonus)),
+        Event::FreeRollEarned { .. } => format!("🎟️ {} earned a free roll.", name),
+        Event::FreeRollUsed { .. } => format!("🎟️ {} used a free roll.", name),
+        Event::Farkled => format!("💥 {} farkled!", name),
+        Event::Banked { score } => format!("🏦 {} banked {}.", name, render_points(*score)),
+        Event::Rejected { message } => format!("⚠️ {}", message),
+        Event::Remark { text } => format!("💬 {}: \"{}\"", name, text),
+        Event::SinceLastTurn { summary } => format!("📰 {}", summary),
+        Event::CatchUp { player, bonus } => format!("🤝 {} catches up, +{}.", player, render_points(*bonus)),
+        Event::DoublePoints { player, bonus } => format!("✖️ {} doubles up, +{}.", player, render_points(*bonus)),
+        Event::TotalRevealed { player, score, place } => {
+            format!("🥁 In {} place, with {}: {}!", ordinal(*place), render_points(*score), player)
+        }
+        Event::DoubleRound { round } => format!("✖️ Round {} scores double.", round),
+        Event::TimedOut { player } => format!("⏰ {} ran out of time.", player),
+        Event::RoundSummary { player, farkled: true, .. } => format!("📋 {}: 0 this round.", player),
+        Event::RoundSummary { player, banked, .. } => format!("📋 {}: {} this round.", player, render_points(*banked)),
+    }
+}
+
+/// Returns everyone's score on one line, leader first, for a chat message.
+/// Hidden scores show as "?", in turn order so the order doesn't give them
+/// away.
+///
+/// ### Examples
+/// ```
+/// use farkle::game::{Game, Player, Visibility};
+/// let mut game = Game::new(vec![Player::new("Ann"), Player::new("Bob")]);
+/// game.record_round(350);
+/// game.record_round(1200);
+/// assert_eq!("🏆 Bob 1,200 · Ann 350", farkle::render::render_chat_scores(&game));
+/// game.set_visibility(Visibility::HiddenTotals);
+/// assert_eq!("🏆 Ann ? · Bob ?", farkle::render::render_chat_scores(&game));
+/// ```
+pub fn render_chat_scores(game: &Game) -> String {
+    let seen = game.scores_seen_by(None);
+    let scores: Vec<String> = if seen.iter().any(|(_name, score)| score.is_none()) {
+        seen.iter().map(|(name, score)| format!("{} {}", name, score.map_or("?".to_string(), render_points))).collect()
+    } else {
+        game.standings().iter().map(|player| format!("{} {}", player.name, render_points(player.score))).collect()
+    };
+    format!("🏆 {}", scores.join(" · "))
+}
+
+/// Returns the game on record as a paper scoresheet: a column per player,
+/// a row per round with each player's total after it, and a dash for a
+/// turn that didn't score. Double points rounds are marked ×2.
+///
+/// ### Examples
+/// ```
+/// let turns = "1. Ann 436124:1 B 100\n2. Bob 223466:- X 0\n3. Ann 111234:111 B 1000\n";
+/// let text = format!("[Player \"Ann\"]\n[Player \"Bob\"]\n\n{}", turns);
+/// let record = farkle::notation::GameRecord::parse(&text).unwrap();
+/// let sheet = "\
+/// Round    Ann  Bob
+///     1    100    -
+///     2  1,100";
+/// assert_eq!(sheet, farkle::render::render_scoresheet(&record));
+/// ```
+pub fn render_scoresheet(record: &GameRecord) -> String {
+    // Players who joined part way through have columns too.
+    let game = record.game();
+    let names: Vec<&str> = game.players().iter().map(|player| player.name.as_str()).collect();
+    let mut rounds: Vec<Vec<String>> = Vec::new();
+    for (turn, (round, total)) in record.turns.iter().zip(record.totals()) {
+        while rounds.len() < round as usize {
+            rounds.push(vec![String::new(); names.len()]);
+        }
+        if let Some(seat) = names.iter().position(|name| *name == turn.player) {
+            let cell = if turn.end == TurnEnd::Banked { render_points(total) } else { "-".to_string() };
+            rounds[round as usize - 1][seat] = cell;
+        }
+    }
+    let width = |seat: usize| {
+        let cells = rounds.iter().map(|cells| cells[seat].chars().count());
+        cells.chain([names[seat].chars().count()]).max().unwrap_or(0)
+    };
+    let widths: Vec<usize> = (0..names.len()).map(width).collect();
+    let row = |first: String, cells: Vec<&str>| {
+        let cells = cells.iter().zip(&widths).map(|(cell, width)| format!("{:>width$}", cell, width = width));
+        let row = [format!("{:>5}", first)].into_iter().chain(cells).collect::<Vec<_>>().join("  ");
+        row.trim_end().to_string()
+    };
+    let mut lines = vec![row("Round".to_string(), names.clone())];
+    for (i, cells) in rounds.iter().enumerate() {
+        let round = i as u32 + 1;
+        // Double points rounds are marked, so their jumps add up.
+        let first = if record.rules.doubles(round) { format!("{}×2", round) } else { round.to_string() };
+        lines.push(row(first, cells.iter().map(String::as_str).collect()));
+    }
+    lines.join("\n")
+}
+
+/// Returns the text shown for a sequence of events.
+///
+/// ### Examples
+/// ```
+/// use farkle::event::Event;
+/// use farkle::get_breakdown;
+/// let events = vec![
+///     Event::Rolled { dice: vec![1, 1, 1, 5, 5, 5] },
+///     Event::Kept { dice: vec![1, 1, 1, 5, 5, 5], breakdown: get_breakdown(&[1, 1, 1, 5, 5, 5]) },
+///     Event::HotDice,
+///     Event::Rolled { dice: vec![2, 3, 4, 6, 6, 2] },
+///     Event::Kept { dice: vec![], breakdown: get_breakdown(&[]) },
+///     Event::Farkled,
+/// ];
+/// let expected = "\
+/// Dice: [1, 1, 1, 5, 5, 5]
+/// You kept: [1, 1, 1, 5, 5, 5]
+/// Two triplets (2500) = 2500
+/// You got all keepers! Good job!
+///
+/// Dice: [2, 3, 4, 6, 6, 2]
+/// You kept: []
+/// No scoring dice.
+/// Your turn is over.
+///
+/// ";
+/// assert_eq!(expected, farkle::render::render_events(&events));
+/// ```
+pub fn render_events(events: &[Event]) -> String {
+    events
+        .iter()
+        .map(|event| render(event) + "\n")
+        .collect()
+}
+
+/// Returns the text shown for the rolls so far this round.
+///
+/// ### Examples
+/// ```
+/// let mut state = farkle::turn::TurnState::new();
+/// state.record(vec![1, 1, 2, 3, 4, 6], vec![1, 1], 200);
+/// state.record(vec![5, 2, 3, 3], vec![5], 50);
+/// let expected = "This round:\nRoll 1: kept [1, 1] +200\nRoll 2: kept [5] +50\n";
+/// assert_eq!(expected, farkle::render::render_history(&state.snapshot()));
+/// ```
+pub fn render_history(snapshot: &TurnSnapshot) -> String {
+    format!("This round:\n{}\n", snapshot.history_lines().join("\n"))
+}
+
+/// Returns labelled dice as each label with its die shown by face, like
+/// "red 3, blue 5".
+fn by_die(dice: &[(String, i16)], face: impl Fn(i16) -> String) -> String {
+    dice.iter().map(|(label, die)| format!("{} {}", label, face(*die))).collect::<Vec<_>>().join(", ")
+}
+
+/// Returns place as an ordinal, like "2nd".
+fn ordinal(place: usize) -> String {
+    let suffix = match (place % 10, place % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", place, suffix)
+}
+
+/// Returns points with commas between thousands.
+///
+/// ### Examples
+/// ```
+/// assert_eq!("10,000", farkle::render::render_points(10_000));
+/// assert_eq!("950", farkle::render::render_points(950));
+/// assert_eq!("4,294,967,295", farkle::render::render_points(u32::MAX));
+/// ```
+pub fn render_points(points: u32) -> String {
+    let digits = points.to_string();
+    let mut text = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            text.push(',');
+        }
+        text.push(digit);
+    }
+    text
+}
+
+/// Returns a clock reading as minutes and seconds.
+///
+/// ### Examples
+/// ```
+/// use std::time::Duration;
+/// assert_eq!("4:05", farkle::render::render_clock(Duration::from_millis(245_900)));
+/// assert_eq!("0:00", farkle::render::render_clock(Duration::ZERO));
+/// ```
+pub fn render_clock(time: Duration) -> String {
+    let secs = time.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
@@ -0,0 +1,140 @@
+//! Dice that come from a list instead of chance, for replaying games,
+//! setting up scenarios and tutorials, and tests that need a roll.
+//!
+//! `ReplayRoller` is an rng, so it goes anywhere dice are rolled. Each roll
+//! is handed out as the six draws `roll_dice` makes, whatever the number of
+//! dice rolled.
+
+use crate::error::FarkleError;
+use crate::notation::GameRecord;
+use crate::parse::parse_dice;
+use crate::TOTAL_DICE;
+use rand::RngCore;
+use std::collections::VecDeque;
+use std::fs;
+
+/// An rng rolling recorded dice, a roll at a time, in order.
+///
+/// Running out is an error from `try_fill_bytes`, and a panic from the
+/// other methods, like any rng that fails. `rolls_left` says whether
+/// there's another roll.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayRoller {
+    /// Draws left, six a roll.
+    draws: VecDeque<i16>,
+}
+
+/// Returns the draw that `gen_range(1..=6)` takes as face.
+fn draw_for(face: i16) -> u32 {
+    // The middle of the face's sixth of the range.
+    ((2 * face as u64 - 1) << 32).div_ceil(12) as u32
+}
+
+impl ReplayRoller {
+    /// Returns a roller rolling rolls in order.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::replay::ReplayRoller;
+    /// let mut roller = ReplayRoller::new([vec![1, 5, 2, 2, 3, 6], vec![5, 5]]).unwrap();
+    /// assert_eq!(vec![1, 5, 2, 2, 3, 6], farkle::roll_dice(6, &mut roller));
+    /// assert_eq!(vec![5, 5], farkle::roll_dice(2, &mut roller));
+    /// assert_eq!(0, roller.rolls_left());
+    ///
+    /// assert!(ReplayRoller::new([vec![7]]).is_err());
+    /// assert!(ReplayRoller::new([vec![1; 7]]).is_err());
+    /// ```
+    pub fn new(rolls: impl IntoIterator<Item = Vec<i16>>) -> Result<ReplayRoller, FarkleError> {
+        let mut draws = VecDeque::new();
+        for (i, roll) in rolls.into_iter().enumerate() {
+            let bad_roll = |why: &str| FarkleError::InvalidInput(format!("Roll {} {}", i + 1, why));
+            if roll.is_empty() || roll.len() > TOTAL_DICE as usize {
+                return Err(bad_roll(&format!("has {} dice, but rolls are 1 to 6 dice.", roll.len())));
+            }
+            if let Some(die) = roll.iter().find(|die| !(1..=6).contains(*die)) {
+                return Err(bad_roll(&format!("has a die showing {}.", die)));
+            }
+            // Dice not rolled are drawn and thrown away.
+            let unrolled = TOTAL_DICE as usize - roll.len();
+            draws.extend(roll.into_iter().chain([1].repeat(unrolled)));
+        }
+        Ok(ReplayRoller { draws })
+    }
+
+    /// Parse rolls, one a line, like "436124" or "4 3 6 1 2 4". Blank lines
+    /// are skipped.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::replay::ReplayRoller;
+    /// use farkle::turn::{Pending, TurnEngine};
+    /// let mut roller = ReplayRoller::parse("111555\n\n2 3\n").unwrap();
+    /// let (turn, _) = TurnEngine::start(&mut roller);
+    /// assert_eq!(&Pending::Keep(vec![1, 1, 1, 5, 5, 5]), turn.pending());
+    /// assert_eq!(1, roller.rolls_left());
+    /// assert!(ReplayRoller::parse("1 2 x\n").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<ReplayRoller, FarkleError> {
+        let rolls = text.lines().filter(|line| !line.trim().is_empty()).map(parse_dice);
+        ReplayRoller::new(rolls.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Returns a roller rolling the dice in a file, written like `parse`
+    /// reads them.
+    pub fn from_file(path: &str) -> Result<ReplayRoller, FarkleError> {
+        let text = fs::read_to_string(path).map_err(FarkleError::io(path))?;
+        ReplayRoller::parse(&text)
+    }
+
+    /// Returns a roller rolling every roll of a recorded game again, in
+    /// order, to replay it.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::bot::Bot;
+    /// use farkle::game::{Game, Player, WinCondition};
+    /// use farkle::harness::play_through;
+    /// use farkle::replay::ReplayRoller;
+    ///
+    /// let players = vec![Player::new("Ann"), Player::new("Bob")];
+    /// let new_game = || Game::with_win_condition(players.clone(), WinCondition::RoundLimit(3));
+    /// let played = play_through(new_game(), &mut farkle::seeded_rng(3), &mut [Bot::new(300), Bot::new(500)]).unwrap();
+    ///
+    /// let mut roller = ReplayRoller::from_record(&played.record).unwrap();
+    /// let replayed = play_through(new_game(), &mut roller, &mut [Bot::new(300), Bot::new(500)]).unwrap();
+    /// assert_eq!(played.record, replayed.record);
+    /// assert_eq!(0, roller.rolls_left());
+    /// ```
+    pub fn from_record(record: &GameRecord) -> Result<ReplayRoller, FarkleError> {
+        let rolls = record.turns.iter().flat_map(|turn| turn.rolls.iter().map(|roll| roll.dice.clone()));
+        ReplayRoller::new(rolls)
+    }
+
+    /// Returns the rolls not rolled yet.
+    pub fn rolls_left(&self) -> usize {
+        self.draws.len().div_ceil(TOTAL_DICE as usize)
+    }
+}
+
+impl RngCore for ReplayRoller {
+    fn next_u32(&mut self) -> u32 {
+        let face = self.draws.pop_front().expect("The recorded dice ran out.");
+        draw_for(face)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        u64::from(self.next_u32())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest).expect("The recorded dice ran out.");
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        for chunk in dest.chunks_mut(4) {
+            let face = self.draws.pop_front().ok_or_else(|| rand::Error::new("The recorded dice ran out."))?;
+            chunk.copy_from_slice(&draw_for(face).to_le_bytes()[..chunk.len()]);
+        }
+        Ok(())
+    }
+}
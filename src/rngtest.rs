@@ -0,0 +1,87 @@
+//! Checking that an rng rolls every face equally often.
+
+use crate::{roll_dice, TOTAL_DICE};
+use rand::Rng;
+use std::fmt;
+
+/// Chi-squared value a fair die stays under 95% of the time, for 5 degrees
+/// of freedom (six faces).
+pub static CRITICAL_VALUE: f64 = 11.07;
+
+/// How often each face came up in a number of dice rolled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaceCounts {
+    /// counts[i] is how many times face i + 1 came up.
+    pub counts: [u64; 6],
+}
+
+impl FaceCounts {
+    /// Returns the faces of rolls dice rolled with rng, a full set at a
+    /// time the way the game rolls them.
+    pub fn roll<R: Rng>(rolls: u64, rng: &mut R) -> FaceCounts {
+        let mut counts = [0; 6];
+        let mut left = rolls;
+        while left > 0 {
+            for die in roll_dice(TOTAL_DICE, rng).into_iter().take(left as usize) {
+                counts[die as usize - 1] += 1;
+                left -= 1;
+            }
+        }
+        FaceCounts { counts }
+    }
+
+    /// Returns the number of dice counted.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns each face's part of the chi-squared value.
+    pub fn contributions(&self) -> [f64; 6] {
+        let expected = self.total() as f64 / 6.0;
+        self.counts.map(|count| {
+            if expected == 0.0 {
+                0.0
+            } else {
+                (count as f64 - expected).powi(2) / expected
+            }
+        })
+    }
+
+    /// Returns the chi-squared value against every face being equally
+    /// likely.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::rngtest::FaceCounts;
+    /// let even = FaceCounts { counts: [100; 6] };
+    /// assert_eq!(0.0, even.chi_squared());
+    ///
+    /// let loaded = FaceCounts { counts: [150, 90, 90, 90, 90, 90] };
+    /// assert_eq!(30.0, loaded.chi_squared());
+    /// assert!(!loaded.looks_uniform());
+    /// ```
+    pub fn chi_squared(&self) -> f64 {
+        self.contributions().iter().sum()
+    }
+
+    /// Returns true if the counts are close enough to even for a fair die.
+    pub fn looks_uniform(&self) -> bool {
+        self.chi_squared() < CRITICAL_VALUE
+    }
+}
+
+impl fmt::Display for FaceCounts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let expected = self.total() as f64 / 6.0;
+        writeln!(f, "Rolled {} dice, expecting {:.1} of each face.", self.total(), expected)?;
+        for (i, (count, contribution)) in self.counts.iter().zip(self.contributions()).enumerate() {
+            writeln!(f, "{}: {} (chi-squared {:.2})", i + 1, count, contribution)?;
+        }
+        writeln!(f, "Chi-squared: {:.2}, under {} for a fair die 95% of the time.", self.chi_squared(), CRITICAL_VALUE)?;
+        if self.looks_uniform() {
+            write!(f, "Looks uniform.")
+        } else {
+            write!(f, "Doesn't look uniform.")
+        }
+    }
+}
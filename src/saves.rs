@@ -0,0 +1,158 @@
+//! Games saved by name, to carry on later, kept in the data directory.
+//!
+//! Each slot is a file of farkle notation, so a save can be read, shared or
+//! edited like any other game written down.
+
+use crate::daily::Date;
+use crate::error::FarkleError;
+use crate::notation::GameRecord;
+use crate::paths;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What slot files end with.
+const EXTENSION: &str = "farkle";
+
+/// A saved game, as listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveSummary {
+    pub name: String,
+    /// When it was saved.
+    pub saved: SystemTime,
+    /// Each player's name and score, in turn order.
+    pub scores: Vec<(String, u32)>,
+    /// The round to be played next.
+    pub round: u32,
+}
+
+impl fmt::Display for SaveSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let secs = self.saved.duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs());
+        let date = Date::from_days((secs / 86_400) as i64);
+        let time = format!("{:02}:{:02}", secs % 86_400 / 3600, secs % 3600 / 60);
+        let scores: Vec<String> = self.scores.iter().map(|(name, score)| format!("{} {}", name, score)).collect();
+        write!(f, "{}  {} {}  {}, round {}", self.name, date, time, scores.join(", "), self.round)
+    }
+}
+
+/// Named saves in a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveSlots {
+    dir: PathBuf,
+}
+
+impl SaveSlots {
+    /// Returns the slots kept in dir, which is made on the first save.
+    pub fn new(dir: impl Into<PathBuf>) -> SaveSlots {
+        SaveSlots { dir: dir.into() }
+    }
+
+    /// Returns the slots in the data directory.
+    pub fn in_data_dir() -> SaveSlots {
+        SaveSlots::new(paths::data_dir().join("saves"))
+    }
+
+    /// Returns where name is kept, if it's a name a slot can have: letters,
+    /// digits, '-' and '_'.
+    fn path(&self, name: &str) -> Result<PathBuf, FarkleError> {
+        let fits = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+        if !fits {
+            let message = format!("'{}' can't name a save. Use letters, digits, '-' and '_'.", name);
+            return Err(FarkleError::InvalidInput(message));
+        }
+        Ok(self.dir.join(format!("{}.{}", name, EXTENSION)))
+    }
+
+    /// Returns where the save called name is kept, if there is one.
+    fn existing(&self, name: &str) -> Result<PathBuf, FarkleError> {
+        let path = self.path(name)?;
+        if !path.is_file() {
+            return Err(FarkleError::InvalidInput(format!("There's no save called {}.", name)));
+        }
+        Ok(path)
+    }
+
+    /// Save record as name, replacing any save called that.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Player, WinCondition};
+    /// use farkle::notation::GameRecord;
+    /// use farkle::saves::SaveSlots;
+    /// use farkle::ScoringRules;
+    ///
+    /// let dir = std::env::temp_dir().join("farkle_saves_doctest");
+    /// let slots = SaveSlots::new(&dir);
+    /// let players = vec![Player::new("Ann"), Player::new("Bob")];
+    /// let record = GameRecord::new(players, WinCondition::TargetScore, ScoringRules::default());
+    /// slots.save("friday", &record).unwrap();
+    /// assert_eq!(record, slots.load("friday").unwrap());
+    /// assert!(slots.save("../friday", &record).is_err());
+    ///
+    /// slots.rename("friday", "saturday").unwrap();
+    /// let listed = slots.list().unwrap();
+    /// assert_eq!(vec!["saturday"], listed.iter().map(|save| save.name.as_str()).collect::<Vec<_>>());
+    /// assert_eq!(vec![("Ann".to_string(), 0), ("Bob".to_string(), 0)], listed[0].scores);
+    /// assert!(slots.load("friday").is_err());
+    ///
+    /// slots.delete("saturday").unwrap();
+    /// assert!(slots.list().unwrap().is_empty());
+    /// assert!(slots.delete("saturday").is_err());
+    /// std::fs::remove_dir(&dir).unwrap();
+    /// ```
+    pub fn save(&self, name: &str, record: &GameRecord) -> Result<(), FarkleError> {
+        let path = self.path(name)?;
+        let dir = self.dir.to_string_lossy();
+        fs::create_dir_all(&self.dir).map_err(FarkleError::io(&dir))?;
+        fs::write(&path, record.to_string()).map_err(FarkleError::io(&path.to_string_lossy()))
+    }
+
+    /// Returns the game saved as name.
+    pub fn load(&self, name: &str) -> Result<GameRecord, FarkleError> {
+        let path = self.existing(name)?;
+        GameRecord::parse(&fs::read_to_string(&path).map_err(FarkleError::io(&path.to_string_lossy()))?)
+    }
+
+    /// Returns every save, the most recent first.
+    pub fn list(&self) -> Result<Vec<SaveSummary>, FarkleError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            // No saves yet.
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(FarkleError::io(&self.dir.to_string_lossy())(error)),
+        };
+        let mut saves = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+            if path.extension().is_none_or(|extension| extension != EXTENSION) || self.path(&name).is_err() {
+                continue;
+            }
+            let game = self.load(&name)?.game();
+            let saved = entry.metadata().and_then(|metadata| metadata.modified()).unwrap_or(UNIX_EPOCH);
+            let scores = game.players().iter().map(|player| (player.name.clone(), player.score)).collect();
+            saves.push(SaveSummary { name, saved, scores, round: game.round_number() });
+        }
+        saves.sort_by(|a, b| b.saved.cmp(&a.saved).then_with(|| a.name.cmp(&b.name)));
+        Ok(saves)
+    }
+
+    /// Delete the save called name.
+    pub fn delete(&self, name: &str) -> Result<(), FarkleError> {
+        let path = self.existing(name)?;
+        fs::remove_file(&path).map_err(FarkleError::io(&path.to_string_lossy()))
+    }
+
+    /// Call the save named from to instead, unless there's one called that
+    /// already.
+    pub fn rename(&self, from: &str, to: &str) -> Result<(), FarkleError> {
+        let (from_path, to_path) = (self.existing(from)?, self.path(to)?);
+        if to_path.exists() {
+            return Err(FarkleError::InvalidInput(format!("There's already a save called {}.", to)));
+        }
+        fs::rename(&from_path, &to_path).map_err(FarkleError::io(&from_path.to_string_lossy()))
+    }
+}
+
@@ -0,0 +1,123 @@
+//! Scoresheets kept on paper or in a spreadsheet, for groups who want to
+//! finish a game they started there.
+//!
+//! A sheet is a CSV file with a row per player per round: the player's name,
+//! the round and the points they banked in it, like `Ann,3,450`. A header
+//! row is fine, and rows can come in any order.
+
+use crate::error::FarkleError;
+use crate::game::{Game, Player, WinCondition};
+
+/// Points a player banked in a round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetRow {
+    pub player: String,
+    pub round: u32,
+    pub points: u32,
+}
+
+/// The rounds written on a scoresheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scoresheet {
+    /// Rows in the order they were written.
+    pub rows: Vec<SheetRow>,
+}
+
+impl Scoresheet {
+    /// Parse a CSV scoresheet.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::scoresheet::Scoresheet;
+    /// let sheet = Scoresheet::parse("Player,Round,Points\nAnn,1,500\nBob,1,0\n").unwrap();
+    /// assert_eq!(2, sheet.rows.len());
+    /// assert!(Scoresheet::parse("Ann,1,lots\n").is_err());
+    /// // A player has one score a round.
+    /// assert!(Scoresheet::parse("Ann,1,500\nAnn,1,300\n").is_err());
+    /// ```
+    pub fn parse(csv: &str) -> Result<Scoresheet, FarkleError> {
+        let mut rows: Vec<SheetRow> = Vec::new();
+        for (i, line) in csv.lines().enumerate() {
+            let bad_row = |why: &str| FarkleError::SaveCorrupt(format!("Row {}: {}", i + 1, why));
+            let fields: Vec<&str> = line.split(',').map(|field| field.trim().trim_matches('"').trim()).collect();
+            let header = i == 0 && fields.first().is_some_and(|field| field.eq_ignore_ascii_case("player"));
+            if line.trim().is_empty() || header {
+                continue;
+            }
+            let [player, round, points] = fields[..] else {
+                return Err(bad_row("a row needs a player, a round and points, like 'Ann,3,450'."));
+            };
+            let round = round.parse().ok().filter(|round| *round > 0).ok_or_else(|| bad_row("rounds count from 1."))?;
+            let points = points.parse().map_err(|_| bad_row("points have to be a number, like 450."))?;
+            if player.is_empty() {
+                return Err(bad_row("the player needs a name."));
+            }
+            if rows.iter().any(|row| row.player == player && row.round == round) {
+                return Err(bad_row(&format!("{} already has points for round {}.", player, round)));
+            }
+            rows.push(SheetRow { player: player.to_string(), round, points });
+        }
+        Ok(Scoresheet { rows })
+    }
+
+    /// Returns the players in turn order, the order they're written in the
+    /// first round.
+    pub fn players(&self) -> Vec<String> {
+        self.rows.iter().filter(|row| row.round == 1).map(|row| row.player.clone()).collect()
+    }
+
+    /// Returns a game between players that's played the rounds on the
+    /// sheet, for the next player in line to carry on.
+    ///
+    /// Points are taken as written, so any bonuses should be in them. The
+    /// game ends, or starts its final round, as it would have at the table.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::game::{Player, WinCondition};
+    /// use farkle::scoresheet::Scoresheet;
+    ///
+    /// let sheet = Scoresheet::parse("Ann,1,500\nBob,1,350\nAnn,2,600\n").unwrap();
+    /// let players = vec![Player::with_target("Ann", 1000), Player::with_target("Bob", 1000)];
+    /// let game = sheet.game(players, WinCondition::TargetScore).unwrap();
+    /// assert_eq!(1100, game.players()[0].score);
+    /// // Ann passed 1000, so Bob plays the final round.
+    /// assert_eq!("Bob", game.current_player().name);
+    /// assert!(game.final_round_trigger().is_some());
+    ///
+    /// // Rounds can't be skipped, and nobody plays on after the game ends.
+    /// let players = vec![Player::new("Ann"), Player::new("Bob")];
+    /// let sheet = Scoresheet::parse("Ann,1,500\nAnn,2,600\n").unwrap();
+    /// assert!(sheet.game(players.clone(), WinCondition::TargetScore).is_err());
+    /// let sheet = Scoresheet::parse("Ann,1,500\nBob,1,350\nAnn,2,600\n").unwrap();
+    /// assert!(sheet.game(players, WinCondition::RoundLimit(1)).is_err());
+    /// ```
+    pub fn game(&self, players: Vec<Player>, win_condition: WinCondition) -> Result<Game, FarkleError> {
+        if players.is_empty() {
+            return Err(FarkleError::SaveCorrupt("Nobody's playing: the sheet has no first round.".to_string()));
+        }
+        if let Some(row) = self.rows.iter().find(|row| !players.iter().any(|player| player.name == row.player)) {
+            return Err(FarkleError::SaveCorrupt(format!("{} is on the sheet, but isn't playing.", row.player)));
+        }
+        let mut game = Game::with_win_condition(players, win_condition);
+        let mut played = 0;
+        while !game.is_over() {
+            let (player, round) = (&game.current_player().name, game.round_number());
+            let Some(row) = self.rows.iter().find(|row| row.player == *player && row.round == round) else {
+                break;
+            };
+            game.record_round(row.points);
+            played += 1;
+        }
+        if played < self.rows.len() {
+            let message = if game.is_over() {
+                format!("the game was over after {} rows, but the sheet goes on.", played)
+            } else {
+                let player = &game.current_player().name;
+                format!("{} has no points for round {}, but the sheet goes on.", player, game.round_number())
+            };
+            return Err(FarkleError::SaveCorrupt(format!("The sheet doesn't add up: {}", message)));
+        }
+        Ok(game)
+    }
+}
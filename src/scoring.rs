@@ -0,0 +1,869 @@
+//! Scoring rules: which dice score and what they're worth.
+//!
+//! This only needs `alloc`, so it builds without the `std` feature for
+//! places with no operating system.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Returns how many of each face are in dice, indexed by face.
+fn counts(dice: &[i16]) -> [i16; 7] {
+    let mut counts = [0; 7];
+    for die in dice {
+        if let Some(count) = usize::try_from(*die).ok().and_then(|face| counts.get_mut(face)) {
+            *count += 1;
+        }
+    }
+    counts
+}
+
+/// Returns the dice whose face appears a number of times keep accepts,
+/// lowest face first.
+fn repeated(dice: &[i16], keep: fn(i16) -> bool) -> Vec<i16> {
+    let mut kept = Vec::new();
+    for (face, count) in counts(dice).iter().enumerate() {
+        if *count > 0 && keep(*count) {
+            kept.extend(core::iter::repeat_n(face as i16, *count as usize));
+        }
+    }
+    kept
+}
+
+/// Returns true if dice contains 3 pairs.
+///
+/// Each pair is exactly two of a face, so four of a kind and a pair aren't
+/// three pairs; they score as four of a kind and whatever the pair scores.
+///
+/// ### Examples
+/// ```
+/// let three_pair = vec![1, 1, 2, 2, 3, 3];
+/// assert_eq!(true, farkle::is_three_pair(&three_pair));
+///
+/// let not_three_pair = vec![1, 1, 2, 2, 3, 4];
+/// assert_eq!(false, farkle::is_three_pair(&not_three_pair));
+///
+/// let four_of_a_kind = vec![1, 1, 2, 2, 2, 2];
+/// assert_eq!(false, farkle::is_three_pair(&four_of_a_kind));
+///
+/// let all_ones = vec![1, 1, 1, 1, 1, 1];
+/// assert_eq!(false, farkle::is_three_pair(&all_ones));
+/// ```
+pub fn is_three_pair(dice: &[i16]) -> bool {
+    three_pairs(&counts(dice))
+}
+
+/// Returns true if counts, indexed by face, are exactly two of three faces.
+fn three_pairs(counts: &[i16; 7]) -> bool {
+    counts.iter().filter(|count| **count == 2).count() == 3
+}
+
+/// Returns true if dice contains a straight.
+///
+/// ### Examples
+/// ```
+/// let dice = vec![1, 2, 3, 4, 5, 6];
+/// assert_eq!(true, farkle::is_straight(&dice));
+///
+/// let dice = vec![1, 2, 3, 4, 5, 5];
+/// assert_eq!(false, farkle::is_straight(&dice));
+///
+/// let dice = vec![1, 4, 2, 6, 5, 3];
+/// assert_eq!(true, farkle::is_straight(&dice));
+/// ```
+pub fn is_straight(dice: &[i16]) -> bool {
+    for i in 1..=6 {
+        if !dice.contains(&i) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Returns true if dice contains num of a kind.
+///
+/// ### Examples
+/// ```
+/// let dice = vec![1, 1, 1, 1, 1, 1];
+/// assert_eq!(true, farkle::is_of_a_kind(6, &dice));
+///
+/// let dice = vec![1, 1, 1, 1, 1, 2];
+/// assert_eq!(false, farkle::is_of_a_kind(6, &dice));
+///
+/// let dice = vec![1, 1, 1, 1, 1, 2];
+/// assert_eq!(true, farkle::is_of_a_kind(5, &dice));
+///
+/// let dice = vec![1, 1, 1, 1, 2, 2];
+/// assert_eq!(false, farkle::is_of_a_kind(5, &dice));
+///
+/// let dice = vec![1, 1, 1, 1, 2, 2];
+/// assert_eq!(true, farkle::is_of_a_kind(4, &dice));
+///
+/// let dice = vec![1, 1, 1, 2, 2, 2];
+/// assert_eq!(false, farkle::is_of_a_kind(4, &dice));
+///
+/// let dice = vec![1, 1, 1, 2, 2, 2];
+/// assert_eq!(true, farkle::is_of_a_kind(3, &dice));
+/// ```
+pub fn is_of_a_kind(num: i16, dice: &[i16]) -> bool {
+    counts(dice).iter().any(|count| *count >= num)
+}
+
+/// Returns true if dice contains 2 triplets.
+///
+/// ### Examples
+/// ```
+/// let dice = vec![1, 1, 1, 2, 2, 2];
+/// assert_eq!(true, farkle::is_two_triplets(&dice));
+///
+/// let dice = vec![1, 1, 1, 2, 2, 3];
+/// assert_eq!(false, farkle::is_two_triplets(&dice));
+///
+/// let dice = vec![1, 1, 1, 1, 2, 2];
+/// assert_eq!(false, farkle::is_two_triplets(&dice));
+/// ```
+pub fn is_two_triplets(dice: &[i16]) -> bool {
+    counts(dice).iter().filter(|count| **count >= 3).count() == 2
+}
+
+/// Returns a vector of dice with count < 3.
+///
+/// ### Examples
+/// ```
+/// # use std::collections::HashMap;
+/// let dice = vec![1, 1, 1, 2, 2, 2];
+/// assert_eq!(Vec::<i16>::new(), farkle::strip_repeats(&dice));
+///
+/// let dice = vec![1, 1, 1, 2, 2, 3];
+/// let mut expected = HashMap::new();
+/// expected.insert(2, 2); // 2 appears twice
+/// expected.insert(3, 1); // 3 appears once
+/// let mut actual = HashMap::new();
+/// for die in farkle::strip_repeats(&dice) {
+///     let count = actual.entry(die).or_insert(0);
+///     *count += 1;
+///         }
+/// assert_eq!(expected, actual);
+///
+/// let dice = vec![1, 1, 1, 1, 2, 2];
+/// assert_eq!(vec![2, 2], farkle::strip_repeats(&dice));
+/// ```
+pub fn strip_repeats(dice: &[i16]) -> Vec<i16> {
+    repeated(dice, |count| count < 3)
+}
+
+/// Returns a vector of dice that are 3 of a kind or more.
+///
+/// ### Examples
+/// ```
+/// # use std::collections::HashMap;
+/// let dice = vec![1, 1, 1, 2, 2, 2];
+/// let mut expected = HashMap::new();
+/// expected.insert(2, 3); // 2 appears three times
+/// expected.insert(1, 3); // 1 appears three times
+/// let mut actual = HashMap::new();
+/// for die in farkle::keep_repeats(&dice) {
+///     let count = actual.entry(die).or_insert(0);
+///     *count += 1;
+///         }
+/// assert_eq!(expected, actual);
+///
+/// let dice = vec![1, 1, 1, 2, 2, 3];
+/// assert_eq!(vec![1, 1, 1], farkle::keep_repeats(&dice));
+///
+/// let dice = vec![1, 1, 1, 1, 2, 2];
+/// assert_eq!(vec![1, 1, 1, 1], farkle::keep_repeats(&dice));
+/// ```
+pub fn keep_repeats(dice: &[i16]) -> Vec<i16> {
+    repeated(dice, |count| count >= 3)
+}
+
+/// What four, five and six of a kind score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KindScoring {
+    /// 2000, 3000 and 5000, whatever the face.
+    Fixed,
+    /// Three of a kind's points doubled for each die past three, like four
+    /// 2s for 400, as in Dix Mille.
+    Doubling,
+}
+
+/// Rules some groups play their own way. The default is the usual game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoringRules {
+    /// Points scored each time the dice are cleared in a turn after the
+    /// first, when playing with a hot dice streak bonus.
+    pub hot_streak_bonus: Option<u32>,
+    /// Percent more a player behind everyone else gets on what they bank,
+    /// when playing with a catch-up bonus.
+    pub catch_up_percent: Option<u32>,
+    /// Points for three of a kind by face, three 1s first.
+    pub triples: [u32; 6],
+    /// The least a turn has to score to count, until a player has banked
+    /// one that does, like the 300 Zilch starts with.
+    pub opening_score: Option<u32>,
+    /// Points lost for three turns in a row scoring nothing, like the 500
+    /// of Zilch.
+    pub zilch_penalty: Option<u32>,
+    /// What four, five and six of a kind score: fixed points, or three of
+    /// a kind doubled for each die past three.
+    pub kinds: KindScoring,
+    /// Clearing the dice has to be followed by rolling all six again,
+    /// rather than banking.
+    pub must_roll_hot_dice: bool,
+    /// Players have to finish on their target exactly. A turn that would
+    /// take them past it counts for nothing.
+    pub exact_finish: bool,
+    /// The most free rolls a player can hold, when playing with them. One
+    /// is earned by keeping a combination of all six dice, and spent to
+    /// roll the same dice again instead of keeping any.
+    pub free_rolls: Option<u32>,
+    /// When the game's over, whoever's in last place gets one more round,
+    /// if one roll could score enough to take them into first.
+    pub redemption_round: bool,
+    /// Every this many rounds, a round where everything banked counts
+    /// double, when playing with double points rounds.
+    pub double_every: Option<u32>,
+}
+
+/// A kind of scoring combination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combination {
+    TwoTriplets,
+    ThreePairs,
+    Straight,
+    SixOfAKind,
+    FiveOfAKind,
+    FourOfAKind,
+    ThreeOfAKind,
+    Single,
+}
+
+impl fmt::Display for Combination {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Combination::TwoTriplets => "Two triplets",
+            Combination::ThreePairs => "Three pairs",
+            Combination::Straight => "Straight",
+            Combination::SixOfAKind => "Six of a kind",
+            Combination::FiveOfAKind => "Five of a kind",
+            Combination::FourOfAKind => "Four of a kind",
+            Combination::ThreeOfAKind => "Three of a kind",
+            Combination::Single => "Single",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Dice that score together and the points they're worth.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoringPart {
+    pub combination: Combination,
+    pub dice: Vec<i16>,
+    pub points: u32,
+}
+
+/// How a set of dice scores.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScoreBreakdown {
+    pub parts: Vec<ScoringPart>,
+}
+
+impl ScoreBreakdown {
+    /// Returns the total points.
+    pub fn total(&self) -> u32 {
+        self.parts.iter().map(|part| part.points).sum()
+    }
+
+    /// Returns the dice that score.
+    pub fn scoring_dice(&self) -> Vec<i16> {
+        self.parts.iter().flat_map(|part| part.dice.clone()).collect()
+    }
+
+    /// Returns how the dice add up, in words.
+    ///
+    /// ### Examples
+    /// ```
+    /// let breakdown = farkle::get_breakdown(&vec![2, 2, 2, 1, 5, 3]);
+    /// assert_eq!("Three 2s (200) + one 1 (100) + one 5 (50) = 350", breakdown.explain());
+    ///
+    /// let breakdown = farkle::get_breakdown(&vec![1, 5, 1]);
+    /// assert_eq!("Two 1s (200) + one 5 (50) = 250", breakdown.explain());
+    ///
+    /// let breakdown = farkle::get_breakdown(&vec![1, 2, 3, 4, 5, 6]);
+    /// assert_eq!("A straight (1500) = 1500", breakdown.explain());
+    /// ```
+    pub fn explain(&self) -> String {
+        if self.parts.is_empty() {
+            return "No scoring dice.".to_string();
+        }
+        // Singles of the same face read better together, as (part, count,
+        // points).
+        let mut terms: Vec<(&ScoringPart, usize, u32)> = Vec::new();
+        for part in &self.parts {
+            match terms.last_mut() {
+                Some((last, count, points)) if part.combination == Combination::Single && *last == part => {
+                    *count += 1;
+                    *points += part.points;
+                }
+                _ => terms.push((part, part.dice.len(), part.points)),
+            }
+        }
+        let terms = terms.into_iter().map(|(part, count, points)| {
+            let words = match part.combination {
+                Combination::TwoTriplets => "two triplets".to_string(),
+                Combination::ThreePairs => "three pairs".to_string(),
+                Combination::Straight => "a straight".to_string(),
+                _ => of_a_kind(count, part.dice[0]),
+            };
+            (words, points)
+        });
+        let mut text = String::new();
+        for (n, (words, points)) in terms.enumerate() {
+            if n == 0 {
+                let mut chars = words.chars();
+                if let Some(first) = chars.next() {
+                    text.extend(first.to_uppercase());
+                    text.push_str(chars.as_str());
+                }
+            } else {
+                text.push_str(" + ");
+                text.push_str(&words);
+            }
+            text.push_str(&format!(" ({})", points));
+        }
+        text.push_str(&format!(" = {}", self.total()));
+        text
+    }
+}
+
+/// Returns words for count dice showing face, like "three 2s".
+fn of_a_kind(count: usize, face: i16) -> String {
+    let number = ["no", "one", "two", "three", "four", "five", "six"].get(count).copied().unwrap_or("many");
+    if count == 1 {
+        format!("{} {}", number, face)
+    } else {
+        format!("{} {}s", number, face)
+    }
+}
+
+impl fmt::Display for ScoreBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.parts.is_empty() {
+            return write!(f, "No scoring dice.");
+        }
+        for part in &self.parts {
+            writeln!(f, "{} {:?}: {}", part.combination, part.dice, part.points)?;
+        }
+        write!(f, "Total: {}", self.total())
+    }
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        ScoringRules::STANDARD
+    }
+}
+
+impl ScoringRules {
+    /// The usual rules.
+    pub const STANDARD: ScoringRules = ScoringRules {
+        hot_streak_bonus: None,
+        catch_up_percent: None,
+        triples: [1000, 200, 300, 400, 500, 600],
+        opening_score: None,
+        zilch_penalty: None,
+        kinds: KindScoring::Fixed,
+        must_roll_hot_dice: false,
+        exact_finish: false,
+        free_rolls: None,
+        redemption_round: false,
+        double_every: None,
+    };
+
+    /// Returns the scoring combinations in dice under these rules.
+    ///
+    /// When dice can be scored more than one way, like six 2s as six of a
+    /// kind or two triplets, the way worth the most is used.
+    /// Among ways worth the same, the one scoring the most dice is used.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::{Combination, KindScoring, ScoringRules};
+    /// let rules = ScoringRules { triples: [300, 200, 300, 400, 500, 600], ..ScoringRules::default() };
+    /// assert_eq!(350, rules.breakdown(&[1, 1, 1, 5]).total());
+    /// assert_eq!(1050, farkle::get_breakdown(&[1, 1, 1, 5]).total());
+    ///
+    /// // Six of a kind is worth more than two triplets or three pairs.
+    /// let breakdown = farkle::get_breakdown(&[2, 2, 2, 2, 2, 2]);
+    /// assert_eq!(Combination::SixOfAKind, breakdown.parts[0].combination);
+    ///
+    /// // Four of a kind and a pair aren't three pairs, which are exactly
+    /// // two of each of three faces, as is_three_pair has it.
+    /// assert_eq!(2100, farkle::get_score(&[1, 1, 1, 1, 5, 5]));
+    /// assert_eq!(2000, farkle::get_score(&[2, 2, 2, 2, 3, 3]));
+    /// let doubling = ScoringRules { kinds: KindScoring::Doubling, ..ScoringRules::default() };
+    /// assert_eq!(vec![Combination::FourOfAKind], doubling.breakdown(&[2, 2, 2, 2, 3, 3]).parts.iter()
+    ///     .map(|part| part.combination).collect::<Vec<_>>());
+    /// assert_eq!(400, doubling.score(&[2, 2, 2, 2, 3, 3]));
+    ///
+    /// // Five of a kind is worth more than three of a kind and singles.
+    /// assert_eq!(3000, farkle::get_score(&[1, 1, 1, 1, 1]));
+    ///
+    /// // With three 1s worth 3000, they're worth more than two triplets, and
+    /// // three of a kind and a 1 are worth more than four of a kind.
+    /// let rules = ScoringRules { triples: [3000, 200, 300, 400, 500, 600], ..ScoringRules::default() };
+    /// let combinations = |dice: &[i16]| -> Vec<Combination> {
+    ///     rules.breakdown(dice).parts.iter().map(|part| part.combination).collect()
+    /// };
+    /// assert_eq!(3500, rules.score(&[1, 1, 1, 5, 5, 5]));
+    /// assert_eq!(vec![Combination::ThreeOfAKind, Combination::ThreeOfAKind], combinations(&[1, 1, 1, 5, 5, 5]));
+    /// assert_eq!(3100, rules.score(&[1, 1, 1, 1]));
+    /// assert_eq!(vec![Combination::ThreeOfAKind, Combination::Single], combinations(&[1, 1, 1, 1]));
+    ///
+    /// // Doubling, four 2s are twice three, and six 1s eight times.
+    /// let rules = ScoringRules { kinds: KindScoring::Doubling, ..ScoringRules::default() };
+    /// assert_eq!(400, rules.score(&[2, 2, 2, 2]));
+    /// assert_eq!(8000, rules.score(&[1, 1, 1, 1, 1, 1]));
+    /// ```
+    pub fn breakdown(&self, dice: &[i16]) -> ScoreBreakdown {
+        let whole = |combination, points| ScoreBreakdown {
+            parts: vec![ScoringPart { combination, dice: dice.to_vec(), points }],
+        };
+        let counts = counts(dice);
+        let six = dice.len() == crate::TOTAL_DICE as usize;
+        let mut ways = Vec::new();
+        if six && counts.iter().all(|count| count % 3 == 0) {
+            ways.push(whole(Combination::TwoTriplets, 2500));
+        }
+        if three_pairs(&counts) {
+            ways.push(whole(Combination::ThreePairs, 1500));
+        }
+        if is_straight(dice) {
+            ways.push(whole(Combination::Straight, 1500));
+        }
+        ways.push(self.face_by_face(&counts));
+        // The first way worth the most, so ties go to the whole roll.
+        let mut best = ScoreBreakdown::default();
+        for way in ways {
+            if way.total() > best.total() || best.parts.is_empty() {
+                best = way;
+            }
+        }
+        best
+    }
+
+    /// Returns the best way to score dice with counts one face at a time,
+    /// as of a kinds and singles.
+    fn face_by_face(&self, counts: &[i16; 7]) -> ScoreBreakdown {
+        let mut kinds = Vec::new();
+        let mut singles = Vec::new();
+        for face in 1..=6 {
+            let count = counts[face as usize] as usize;
+            let single = match face {
+                1 => 100,
+                5 => 50,
+                _ => 0,
+            };
+            // Try singles only, then each size of a kind with singles for
+            // the rest, preferring more dice in a kind on equal points.
+            let mut best = (0, None);
+            for size in [0].into_iter().chain(3..=count) {
+                let kind = self.of_a_kind(size, face);
+                let points = kind.map_or(0, |(_combination, points)| points) + (count - size) as u32 * single;
+                if points >= best.0 {
+                    best = (points, kind.map(|kind| (size, kind)));
+                }
+            }
+            let mut left = count;
+            if let (_points, Some((size, (combination, points)))) = best {
+                kinds.push(ScoringPart { combination, dice: vec![face; size], points });
+                left -= size;
+            }
+            if single > 0 {
+                let part = ScoringPart { combination: Combination::Single, dice: vec![face], points: single };
+                singles.extend(core::iter::repeat_n(part, left));
+            }
+        }
+        kinds.extend(singles);
+        ScoreBreakdown { parts: kinds }
+    }
+
+    /// Returns what size dice showing face score as of a kind, if they do.
+    fn of_a_kind(&self, size: usize, face: i16) -> Option<(Combination, u32)> {
+        let triple = self.triples[face as usize - 1];
+        let (combination, fixed) = match size {
+            3 => (Combination::ThreeOfAKind, triple),
+            4 => (Combination::FourOfAKind, 2000),
+            5 => (Combination::FiveOfAKind, 3000),
+            6 => (Combination::SixOfAKind, 5000),
+            _ => return None,
+        };
+        let points = match self.kinds {
+            KindScoring::Fixed => fixed,
+            KindScoring::Doubling => triple.saturating_mul(1 << (size - 3)),
+        };
+        Some((combination, points))
+    }
+
+    /// Returns the score for dice under these rules.
+    pub fn score(&self, dice: &[i16]) -> u32 {
+        self.breakdown(dice).total()
+    }
+
+    /// Returns the positions of the dice that score in a roll under these
+    /// rules.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::{KindScoring, ScoringRules};
+    /// // Doubling, four 2s score and a pair of 4s, not three pairs, don't.
+    /// let rules = ScoringRules { kinds: KindScoring::Doubling, ..ScoringRules::default() };
+    /// assert_eq!(vec![0, 2, 3, 5], rules.scoring_indices(&[2, 4, 2, 2, 4, 2]));
+    /// assert_eq!(vec![1, 3], rules.scoring_indices(&[2, 5, 3, 1, 4, 4]));
+    /// ```
+    pub fn scoring_indices(&self, dice: &[i16]) -> Vec<usize> {
+        let mut used = vec![false; dice.len()];
+        for die in self.breakdown(dice).scoring_dice() {
+            if let Some(i) = (0..dice.len()).find(|i| !used[*i] && dice[*i] == die) {
+                used[i] = true;
+            }
+        }
+        (0..dice.len()).filter(|i| used[*i]).collect()
+    }
+
+    /// Returns the free rolls a player holding held has after keeping kept,
+    /// one more if it's a combination of all six dice and they have room.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::ScoringRules;
+    /// let rules = ScoringRules { free_rolls: Some(2), ..ScoringRules::default() };
+    /// assert_eq!(1, rules.earn_free_roll(0, &rules.breakdown(&[1, 2, 3, 4, 5, 6])));
+    /// assert_eq!(2, rules.earn_free_roll(2, &rules.breakdown(&[2, 2, 3, 3, 6, 6])));
+    /// assert_eq!(0, rules.earn_free_roll(0, &rules.breakdown(&[1, 1, 1, 5, 5])));
+    /// assert_eq!(0, ScoringRules::STANDARD.earn_free_roll(0, &rules.breakdown(&[1, 2, 3, 4, 5, 6])));
+    /// ```
+    pub fn earn_free_roll(&self, held: u32, kept: &ScoreBreakdown) -> u32 {
+        let whole_roll = kept.parts.iter().any(|part| part.dice.len() == crate::TOTAL_DICE as usize);
+        match self.free_rolls {
+            Some(most) if whole_roll && held < most => held + 1,
+            _ => held,
+        }
+    }
+
+    /// Returns every different way to keep only scoring dice from a roll
+    /// under these rules, most points first, and fewest dice first among
+    /// equal points.
+    ///
+    /// ### Examples
+    /// ```
+    /// let rules = farkle::ScoringRules { triples: [300, 200, 300, 400, 500, 600], ..Default::default() };
+    /// let best = &rules.scoring_subsets(&[1, 1, 1, 4])[0];
+    /// assert_eq!(300, best.total());
+    /// ```
+    pub fn scoring_subsets(&self, dice: &[i16]) -> Vec<ScoreBreakdown> {
+        let mut seen: Vec<Vec<i16>> = Vec::new();
+        let mut subsets = Vec::new();
+        for mask in 1..1usize << dice.len() {
+            let mut kept: Vec<i16> = (0..dice.len()).filter(|i| mask & (1 << i) != 0).map(|i| dice[i]).collect();
+            kept.sort_unstable();
+            if seen.contains(&kept) {
+                continue;
+            }
+            let breakdown = self.breakdown(&kept);
+            if breakdown.total() > 0 && breakdown.scoring_dice().len() == kept.len() {
+                subsets.push(breakdown);
+            }
+            seen.push(kept);
+        }
+        subsets.sort_by(|a, b| {
+            b.total()
+                .cmp(&a.total())
+                .then(a.scoring_dice().len().cmp(&b.scoring_dice().len()))
+        });
+        subsets
+    }
+
+    /// Returns true if round, counting from 1, is a double points round.
+    ///
+    /// ### Examples
+    /// ```
+    /// let rules = farkle::ScoringRules { double_every: Some(4), ..Default::default() };
+    /// assert!(rules.doubles(8));
+    /// assert!(!rules.doubles(9));
+    /// ```
+    pub fn doubles(&self, round: u32) -> bool {
+        self.double_every.is_some_and(|every| every > 0 && round.is_multiple_of(every))
+    }
+
+    /// Returns the most one roll of all six dice can score.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::{KindScoring, ScoringRules};
+    /// assert_eq!(5000, ScoringRules::default().best_roll());
+    /// let rules = ScoringRules { kinds: KindScoring::Doubling, ..ScoringRules::default() };
+    /// assert_eq!(8000, rules.best_roll());
+    /// ```
+    pub fn best_roll(&self) -> u32 {
+        let mut dice: [i16; 6] = [1; 6];
+        let mut best = 0;
+        loop {
+            best = best.max(self.score(&dice));
+            // On to the next set of dice, kept in order so each is scored once.
+            let Some(i) = dice.iter().rposition(|die| *die < 6) else {
+                return best;
+            };
+            let face = dice[i] + 1;
+            dice[i..].fill(face);
+        }
+    }
+
+    /// Returns what three of each face scores, in words for the help text.
+    ///
+    /// ### Examples
+    /// ```
+    /// let expected = "1000 points for 3 ones, 200 for 3 twos, 300 for 3 threes, \
+    ///     400 for 3 fours, 500 for 3 fives, 600 for 3 sixes";
+    /// assert_eq!(expected, farkle::ScoringRules::STANDARD.describe_triples());
+    /// ```
+    pub fn describe_triples(&self) -> String {
+        let names = ["ones", "twos", "threes", "fours", "fives", "sixes"];
+        let mut text = format!("{} points for 3 {}", self.triples[0], names[0]);
+        for (points, name) in self.triples.iter().zip(names).skip(1) {
+            text.push_str(&format!(", {} for 3 {}", points, name));
+        }
+        text
+    }
+
+    /// Returns what four, five and six of a kind score, in words for the
+    /// help text.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::{KindScoring, ScoringRules};
+    /// let expected = ["4 of a kind: 2000 points", "5 of a kind: 3000 points", "6 of a kind: 5000 points"];
+    /// assert_eq!(expected.to_vec(), ScoringRules::STANDARD.describe_kinds());
+    ///
+    /// let rules = ScoringRules { kinds: KindScoring::Doubling, ..ScoringRules::default() };
+    /// let expected = [
+    ///     "4 of a kind: 2 times 3 of a kind",
+    ///     "5 of a kind: 4 times 3 of a kind",
+    ///     "6 of a kind: 8 times 3 of a kind",
+    /// ];
+    /// assert_eq!(expected.to_vec(), rules.describe_kinds());
+    /// ```
+    pub fn describe_kinds(&self) -> Vec<String> {
+        (4..=6)
+            .map(|size| match (self.kinds, self.of_a_kind(size, 2)) {
+                (KindScoring::Doubling, _) => format!("{} of a kind: {} times 3 of a kind", size, 1 << (size - 3)),
+                (KindScoring::Fixed, points) => {
+                    format!("{} of a kind: {} points", size, points.map_or(0, |(_combination, points)| points))
+                }
+            })
+            .collect()
+    }
+}
+
+/// Scores of every roll of 6 dice under some rules, for scoring many rolls
+/// quickly.
+///
+/// There are only 46,656 rolls of 6 dice, 462 if order doesn't matter, so
+/// each is scored once when the table is built and looked up after that.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreTable {
+    /// Scores indexed by roll, the first die the lowest digit in base 6.
+    scores: Vec<u32>,
+}
+
+impl ScoreTable {
+    /// Returns a table of scores under rules.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::scoring::ScoreTable;
+    /// use farkle::ScoringRules;
+    /// let rules = ScoringRules { triples: [300, 200, 300, 400, 500, 600], ..ScoringRules::default() };
+    /// let table = ScoreTable::new(&rules);
+    /// assert_eq!(350, table.score([1, 1, 1, 5, 2, 3]));
+    /// ```
+    pub fn new(rules: &ScoringRules) -> ScoreTable {
+        let dice = crate::TOTAL_DICE as u32;
+        let mut scores = vec![0; 6usize.pow(dice)];
+        // Rolls in any order score the same, so score each set of dice once,
+        // keyed by how many of each face it has.
+        let mut scored: BTreeMap<[i16; 7], u32> = BTreeMap::new();
+        for (index, score) in scores.iter_mut().enumerate() {
+            let roll: Vec<i16> = (0..dice).map(|i| (index / 6usize.pow(i) % 6) as i16 + 1).collect();
+            *score = *scored.entry(counts(&roll)).or_insert_with(|| rules.score(&roll));
+        }
+        ScoreTable { scores }
+    }
+
+    /// Returns the table for the usual rules, built the first time it's
+    /// needed.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::scoring::ScoreTable;
+    /// for roll in farkle::odds::all_rolls(6) {
+    ///     let dice = [roll[0], roll[1], roll[2], roll[3], roll[4], roll[5]];
+    ///     assert_eq!(farkle::get_score(&roll), ScoreTable::standard().score(dice));
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn standard() -> &'static ScoreTable {
+        static STANDARD: std::sync::OnceLock<ScoreTable> = std::sync::OnceLock::new();
+        STANDARD.get_or_init(|| ScoreTable::new(&ScoringRules::STANDARD))
+    }
+
+    /// Returns the score for a roll of 6 dice.
+    ///
+    /// Panics if a die isn't 1 to 6.
+    ///
+    /// ### Examples
+    /// ```
+    /// let table = farkle::scoring::ScoreTable::standard();
+    /// assert_eq!(2500, table.score([1, 2, 1, 2, 1, 2]));
+    /// assert_eq!(0, table.score([2, 3, 4, 6, 6, 2]));
+    /// ```
+    pub fn score(&self, dice: [i16; 6]) -> u32 {
+        self.scores[index(dice.map(|die| u8::try_from(die).unwrap_or(0)))]
+    }
+
+    /// Returns the score for each roll of 6 dice, in order.
+    ///
+    /// Panics if a die isn't 1 to 6.
+    ///
+    /// ### Examples
+    /// ```
+    /// let table = farkle::scoring::ScoreTable::standard();
+    /// let rolls = [[1, 1, 1, 2, 2, 2], [2, 3, 4, 6, 6, 2], [5, 2, 3, 4, 6, 6]];
+    /// assert_eq!(vec![2500, 0, 50], table.score_many(&rolls));
+    /// ```
+    pub fn score_many(&self, rolls: &[[u8; 6]]) -> Vec<u32> {
+        rolls.iter().map(|dice| self.scores[index(*dice)]).collect()
+    }
+}
+
+/// Returns where a roll of 6 dice is in a ScoreTable.
+fn index(dice: [u8; 6]) -> usize {
+    let mut index = 0;
+    for die in dice.iter().rev() {
+        assert!((1..=6).contains(die), "{} isn't a die. Dice are 1 to 6.", die);
+        index = index * 6 + (*die - 1) as usize;
+    }
+    index
+}
+
+/// Returns the score for each roll of 6 dice under the usual rules, looked
+/// up in the standard ScoreTable.
+///
+/// Panics if a die isn't 1 to 6.
+///
+/// ### Examples
+/// ```
+/// let rolls: Vec<[u8; 6]> = vec![[1, 2, 3, 4, 5, 6], [1, 1, 5, 5, 5, 3]];
+/// assert_eq!(vec![1500, 700], farkle::scoring::score_many(&rolls));
+/// ```
+#[cfg(feature = "std")]
+pub fn score_many(rolls: &[[u8; 6]]) -> Vec<u32> {
+    ScoreTable::standard().score_many(rolls)
+}
+
+/// Returns the scoring combinations in a given set of dice, under the
+/// usual rules.
+///
+/// ### Examples
+/// ```
+/// use farkle::Combination;
+/// let breakdown = farkle::get_breakdown(&vec![2, 2, 2, 1, 5, 3]);
+/// let combinations: Vec<Combination> = breakdown.parts.iter().map(|part| part.combination).collect();
+/// assert_eq!(vec![Combination::ThreeOfAKind, Combination::Single, Combination::Single], combinations);
+/// assert_eq!(vec![2, 2, 2, 1, 5], breakdown.scoring_dice());
+/// assert_eq!(350, breakdown.total());
+///
+/// let breakdown = farkle::get_breakdown(&vec![2, 3, 4, 6]);
+/// assert!(breakdown.parts.is_empty());
+/// ```
+pub fn get_breakdown(dice: &[i16]) -> ScoreBreakdown {
+    ScoringRules::STANDARD.breakdown(dice)
+}
+
+/// Returns the score for a given set of dice, under the usual rules.
+///
+///
+/// ### Examples
+/// ```
+/// let dice = vec![1, 1, 1, 2, 2, 2];
+/// assert_eq!(2500, farkle::get_score(&dice));
+///
+/// let dice = vec![1, 1, 2, 2, 3, 3];
+/// assert_eq!(1500, farkle::get_score(&dice));
+///
+/// let dice = vec![1, 2, 3, 4, 5, 6];
+/// assert_eq!(1500, farkle::get_score(&dice));
+///
+/// let dice = vec![1, 1, 1, 1, 1, 1];
+/// assert_eq!(5000, farkle::get_score(&dice));
+///
+/// let dice = vec![1, 1, 1, 1, 1];
+/// assert_eq!(3000, farkle::get_score(&dice));
+///
+/// let dice = vec![1, 1, 1, 1];
+/// assert_eq!(2000, farkle::get_score(&dice));
+///
+/// let dice = vec![1, 1, 1];
+/// assert_eq!(1000, farkle::get_score(&dice));
+///
+/// let dice = vec![1, 1, 1, 5];
+/// assert_eq!(1050, farkle::get_score(&dice));
+///
+/// let dice = vec![1, 1, 1, 5, 5];
+/// assert_eq!(1100, farkle::get_score(&dice));
+///
+/// let dice = vec![1, 1, 5, 5, 5];
+/// assert_eq!(700, farkle::get_score(&dice));
+///
+/// let dice = vec![1, 1, 5];
+/// assert_eq!(250, farkle::get_score(&dice));
+/// ```
+pub fn get_score(dice: &[i16]) -> u32 {
+    get_breakdown(dice).total()
+}
+
+/// Returns the positions of the dice that score in a roll, under the usual
+/// rules, like `ScoringRules::scoring_indices`.
+///
+/// ### Examples
+/// ```
+/// let dice = vec![2, 5, 3, 1, 4, 4];
+/// assert_eq!(vec![1, 3], farkle::scoring_indices(&dice));
+///
+/// let dice = vec![3, 6, 3, 2, 3, 6];
+/// assert_eq!(vec![0, 2, 4], farkle::scoring_indices(&dice));
+/// ```
+pub fn scoring_indices(dice: &[i16]) -> Vec<usize> {
+    ScoringRules::STANDARD.scoring_indices(dice)
+}
+
+/// Returns every different way to keep only scoring dice from a roll under
+/// the usual rules, like `ScoringRules::scoring_subsets`.
+///
+/// ### Examples
+/// ```
+/// let subsets = farkle::scoring::scoring_subsets(&[1, 5, 2]);
+/// let scores: Vec<u32> = subsets.iter().map(|subset| subset.total()).collect();
+/// assert_eq!(vec![150, 100, 50], scores);
+///
+/// // Keeping either 1 is the same keep.
+/// assert_eq!(2, farkle::scoring::scoring_subsets(&[1, 1, 4]).len());
+/// assert!(farkle::scoring::scoring_subsets(&[2, 3, 4, 6]).is_empty());
+/// ```
+pub fn scoring_subsets(dice: &[i16]) -> Vec<ScoreBreakdown> {
+    ScoringRules::STANDARD.scoring_subsets(dice)
+}
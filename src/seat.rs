@@ -0,0 +1,325 @@
+//! Players at the console: people typing, and bots being watched.
+
+use crate::clock::{Clock, SystemClock};
+use crate::coach::Coach;
+use crate::confirm::{confirm, ConfirmPolicy};
+use crate::console::Console;
+use crate::event::Event;
+use crate::game::GameSnapshot;
+use crate::odds::explain_farkle;
+use crate::render::{render_history, render_other_with, render_with};
+use crate::turn::{Decider, PlayerAction, TurnSnapshot};
+use crate::{get_action, keep_dice, keep_score, ScoringRules};
+use std::io::{BufRead, Write};
+use std::time::Duration;
+
+/// A person playing at the console.
+pub struct HumanSeat<'a, R, W> {
+    console: &'a mut Console<R, W>,
+    policy: &'a ConfirmPolicy,
+    coach: Option<&'a mut Coach>,
+    roll: Vec<i16>,
+    rules: ScoringRules,
+    new_roll: bool,
+}
+
+impl<'a, R: BufRead, W: Write> HumanSeat<'a, R, W> {
+    /// Returns a seat asking for choices on console, confirming risky ones
+    /// per policy.
+    pub fn new(console: &'a mut Console<R, W>, policy: &'a ConfirmPolicy) -> HumanSeat<'a, R, W> {
+        HumanSeat {
+            console,
+            policy,
+            coach: None,
+            roll: Vec::new(),
+            rules: ScoringRules::STANDARD,
+            new_roll: false,
+        }
+    }
+
+    /// Returns a seat like `new` where coach reviews every keep.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::coach::Coach;
+    /// use farkle::confirm::ConfirmPolicy;
+    /// use farkle::console::Console;
+    /// use farkle::seat::HumanSeat;
+    /// use farkle::turn::play_round;
+    /// let mut rng = farkle::daily::round_rng(1, 0);
+    /// let mut console = Console::new("3 5\ny\n".as_bytes(), Vec::new());
+    /// let policy = ConfirmPolicy::never();
+    /// let mut coach = Coach::new();
+    /// play_round(&mut rng, &mut HumanSeat::with_coach(&mut console, &policy, &mut coach));
+    /// let output = String::from_utf8(console.output).unwrap();
+    /// assert!(output.contains("You kept: [6, 2]\nTip: keeping [1] would have scored 100, 100 more than [6, 2].\n"));
+    /// assert_eq!(Some(0.0), coach.accuracy());
+    /// ```
+    pub fn with_coach(
+        console: &'a mut Console<R, W>,
+        policy: &'a ConfirmPolicy,
+        coach: &'a mut Coach,
+    ) -> HumanSeat<'a, R, W> {
+        HumanSeat { coach: Some(coach), ..HumanSeat::new(console, policy) }
+    }
+}
+
+impl<'a, R: BufRead, W: Write> Decider for HumanSeat<'a, R, W> {
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> PlayerAction {
+        // Show what's happened so far this round.
+        if self.new_roll && !turn.history.is_empty() {
+            self.console.say(&render_history(turn));
+        }
+        if self.new_roll && turn.free_rolls > 0 {
+            let held = turn.free_rolls;
+            self.console.say(&format!("Free rolls held: {}. Enter 'f' to roll these dice again.", held));
+        }
+        self.new_roll = false;
+        self.rules.clone_from(&turn.rules);
+        loop {
+            let action = get_action(self.console);
+            let kept = match &action {
+                PlayerAction::ShowBreakdown => {
+                    self.console.say(&format!("{}\n", turn.rules.breakdown(dice)));
+                    continue;
+                }
+                PlayerAction::Keep(indices) => keep_dice(dice, indices),
+                PlayerAction::KeepValues(values) => values.clone(),
+                PlayerAction::KeepAndBank(keep) => match keep.as_ref() {
+                    PlayerAction::Keep(indices) => keep_dice(dice, indices),
+                    PlayerAction::KeepValues(values) => values.clone(),
+                    _ => return action,
+                },
+                _ => return action,
+            };
+            if let Some(warning) = self.policy.keep_warning(&turn.rules, dice, &kept) {
+                if !confirm(self.console, &warning) {
+                    continue;
+                }
+            }
+            return action;
+        }
+    }
+
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> PlayerAction {
+        // Ask if player wants to keep score.
+        self.console.say(&self.console.theme.ask_to_bank(turn.score));
+        let roll_again = !keep_score(self.console);
+        let confirmed = match self.policy.roll_again_warning(&turn.rules, turn.score, turn.num_dice) {
+            Some(warning) if roll_again => confirm(self.console, &warning),
+            _ => roll_again,
+        };
+        if confirmed {
+            PlayerAction::RollAgain
+        } else {
+            PlayerAction::Bank
+        }
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        self.console.say(&render_with(event, &self.console.theme));
+        match (event, &mut self.coach) {
+            (Event::Rolled { dice }, _) => {
+                self.roll = dice.clone();
+                self.new_roll = true;
+            }
+            (Event::Farkled, _) => {
+                let explained = explain_farkle(&self.rules, &self.roll);
+                if !explained.is_empty() {
+                    self.console.say(&explained);
+                }
+            }
+            (Event::Kept { dice, .. }, Some(coach)) => {
+                if let Some(tip) = coach.review(&self.rules, &self.roll, dice) {
+                    self.console.say(&tip);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A computer player whose turn is shown on the console.
+pub struct BotSeat<'a, D, R, W> {
+    name: &'a str,
+    bot: &'a mut D,
+    console: &'a mut Console<R, W>,
+}
+
+impl<'a, D: Decider, R: BufRead, W: Write> BotSeat<'a, D, R, W> {
+    /// Returns a seat where bot plays as name, narrated on console.
+    pub fn new(name: &'a str, bot: &'a mut D, console: &'a mut Console<R, W>) -> BotSeat<'a, D, R, W> {
+        BotSeat { name, bot, console }
+    }
+}
+
+impl<'a, D: Decider, R: BufRead, W: Write> Decider for BotSeat<'a, D, R, W> {
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> PlayerAction {
+        self.bot.choose_keep(dice, turn)
+    }
+
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> PlayerAction {
+        self.bot.choose_bank(turn)
+    }
+
+    fn remark(&mut self) -> Option<String> {
+        self.bot.remark()
+    }
+
+    fn on_turn_start(&mut self, game: &GameSnapshot) {
+        self.bot.on_turn_start(game);
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        self.bot.on_event(event);
+        self.console.say(&render_other_with(self.name, event, &self.console.theme));
+    }
+}
+
+/// A decider that pauses before each choice, so people can follow along.
+pub struct Paced<D, C = SystemClock> {
+    decider: D,
+    pause: Duration,
+    clock: C,
+}
+
+impl<D: Decider> Paced<D> {
+    /// Returns decider, waiting pause before each choice.
+    pub fn new(decider: D, pause: Duration) -> Paced<D> {
+        Paced::with_clock(decider, pause, SystemClock)
+    }
+}
+
+impl<D: Decider, C: Clock> Paced<D, C> {
+    /// Returns decider, waiting pause on clock before each choice.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::bot::Bot;
+    /// use farkle::clock::{Clock, TestClock};
+    /// use farkle::seat::Paced;
+    /// use farkle::turn::{play_turn, TurnEngine};
+    /// use std::time::Duration;
+    ///
+    /// let clock = TestClock::new(Duration::ZERO);
+    /// let mut bot = Paced::with_clock(Bot::new(300), Duration::from_secs(1), clock.clone());
+    /// let mut rng = farkle::seeded_rng(3);
+    /// let (mut turn, _events) = TurnEngine::start(&mut rng);
+    /// play_turn(&mut turn, &mut rng, &mut bot);
+    /// // A second for each choice, without the wait.
+    /// assert!(clock.since_1970() >= Duration::from_secs(2));
+    /// ```
+    pub fn with_clock(decider: D, pause: Duration, clock: C) -> Paced<D, C> {
+        Paced { decider, pause, clock }
+    }
+}
+
+impl<D: Decider, C: Clock> Decider for Paced<D, C> {
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> PlayerAction {
+        self.clock.sleep(self.pause);
+        self.decider.choose_keep(dice, turn)
+    }
+
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> PlayerAction {
+        self.clock.sleep(self.pause);
+        self.decider.choose_bank(turn)
+    }
+
+    fn remark(&mut self) -> Option<String> {
+        self.decider.remark()
+    }
+
+    fn on_turn_start(&mut self, game: &GameSnapshot) {
+        self.decider.on_turn_start(game);
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        self.decider.on_event(event);
+    }
+}
+
+/// A decider shown each roll a die at a time, pausing before each die, so
+/// frontends can make a show of the dice coming up.
+///
+/// The dice are rolled as ever, all at once. Only showing them is drawn out.
+pub struct Dramatic<'a, D: ?Sized, C = SystemClock> {
+    decider: &'a mut D,
+    pause: Duration,
+    clock: C,
+}
+
+impl<'a, D: Decider + ?Sized> Dramatic<'a, D> {
+    /// Returns decider, shown each die pause after the one before.
+    pub fn new(decider: &'a mut D, pause: Duration) -> Dramatic<'a, D> {
+        Dramatic::with_clock(decider, pause, SystemClock)
+    }
+}
+
+impl<'a, D: Decider + ?Sized, C: Clock> Dramatic<'a, D, C> {
+    /// Returns decider, shown each die pause on clock after the one before.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::clock::{Clock, TestClock};
+    /// use farkle::event::Event;
+    /// use farkle::seat::Dramatic;
+    /// use farkle::turn::{Decider, PlayerAction, TurnSnapshot};
+    /// use std::time::Duration;
+    ///
+    /// struct Shown(Vec<Event>);
+    /// impl Decider for Shown {
+    ///     fn choose_keep(&mut self, _dice: &[i16], _turn: &TurnSnapshot) -> PlayerAction {
+    ///         PlayerAction::Bank
+    ///     }
+    ///     fn choose_bank(&mut self, _turn: &TurnSnapshot) -> PlayerAction {
+    ///         PlayerAction::Bank
+    ///     }
+    ///     fn on_event(&mut self, event: &Event) {
+    ///         self.0.push(event.clone());
+    ///     }
+    /// }
+    ///
+    /// let clock = TestClock::new(Duration::ZERO);
+    /// let mut shown = Shown(Vec::new());
+    /// let mut dramatic = Dramatic::with_clock(&mut shown, Duration::from_millis(300), clock.clone());
+    /// dramatic.on_event(&Event::Rolled { dice: vec![5, 2] });
+    /// let expected = vec![
+    ///     Event::DieRevealed { die: 5, position: 1, of: 2 },
+    ///     Event::DieRevealed { die: 2, position: 2, of: 2 },
+    ///     Event::Rolled { dice: vec![5, 2] },
+    /// ];
+    /// assert_eq!(expected, shown.0);
+    /// assert_eq!(Duration::from_millis(600), clock.since_1970());
+    /// ```
+    pub fn with_clock(decider: &'a mut D, pause: Duration, clock: C) -> Dramatic<'a, D, C> {
+        Dramatic { decider, pause, clock }
+    }
+}
+
+impl<'a, D: Decider + ?Sized, C: Clock> Decider for Dramatic<'a, D, C> {
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> PlayerAction {
+        self.decider.choose_keep(dice, turn)
+    }
+
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> PlayerAction {
+        self.decider.choose_bank(turn)
+    }
+
+    fn remark(&mut self) -> Option<String> {
+        self.decider.remark()
+    }
+
+    fn on_turn_start(&mut self, game: &GameSnapshot) {
+        self.decider.on_turn_start(game);
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        if let Event::Rolled { dice } = event {
+            for (i, die) in dice.iter().enumerate() {
+                self.clock.sleep(self.pause);
+                self.decider.on_event(&Event::DieRevealed { die: *die, position: i + 1, of: dice.len() });
+            }
+        }
+        self.decider.on_event(event);
+    }
+}
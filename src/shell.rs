@@ -0,0 +1,320 @@
+//! A shell that plays a game a command at a time, like `roll`, `keep 1 3 5`
+//! and `bank`, for players who'd rather type than answer prompts, and for
+//! driving the engine from scripts and tests.
+
+use crate::config::GameConfig;
+use crate::error::FarkleError;
+use crate::event::Event;
+use crate::game::{Game, Player, WinCondition};
+use crate::notation::GameRecord;
+use crate::odds::{bank_threshold, explore, farkle_chance};
+use crate::parse::{parse_dice, parse_keep_action, parse_player};
+use crate::render::{render_other, render_points, render_scoresheet};
+use crate::turn::{Pending, PlayerAction, TurnEngine};
+use crate::ScoringRules;
+use rand::Rng;
+use std::fs;
+
+/// The commands, for `help`.
+pub const SHELL_HELP: &str = "\
+new [player ...]   start a game, with players like Ann or Ann:5000
+roll               start a turn, or roll the dice left
+keep <dice>        keep dice by position, like 'keep 1 3 5', or 'keep a' for all that score
+keep <dice> bank   keep dice and bank, in one go
+free               spend a free roll to roll the same dice again, when playing with them
+bank               bank the points at stake
+score <dice>       show how dice score, like 'score 1 1 5'
+odds               show the odds for the choice at hand
+save <file>        write the game down, in farkle notation
+load <file>        carry on a game written down
+sheet              show the scoresheet, a row per round
+help               show this";
+
+/// A game played a command at a time.
+pub struct Shell<R> {
+    rng: R,
+    game: Game,
+    record: GameRecord,
+    /// The turn being played, if there is one.
+    turn: Option<TurnEngine>,
+}
+
+impl<R: Rng> Shell<R> {
+    /// Returns a shell rolling with rng, with a game for one player under
+    /// rules.
+    pub fn new(rng: R, rules: ScoringRules) -> Shell<R> {
+        let record = GameRecord::new(vec![Player::new("You")], WinCondition::TargetScore, rules);
+        Shell { rng, game: record.game(), record, turn: None }
+    }
+
+    /// Returns the game as it stands, not counting the turn being played.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Run a command.
+    ///
+    /// Returns what happened, or why the command can't be run.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::shell::Shell;
+    /// use farkle::ScoringRules;
+    ///
+    /// let mut shell = Shell::new(farkle::daily::round_rng(1, 0), ScoringRules::default());
+    /// assert_eq!("A game between Ann and Bob. Ann to roll.", shell.run("new Ann Bob").unwrap());
+    /// assert_eq!("Ann rolled [4, 3, 6, 1, 2, 4]", shell.run("roll").unwrap());
+    /// assert!(shell.run("bank nothing").is_err());
+    /// assert_eq!("Ann kept [1]", shell.run("keep 4").unwrap());
+    /// assert_eq!("Ann rolled [3, 2, 5, 2, 6]", shell.run("roll").unwrap());
+    /// assert!(shell.run("roll").is_err());
+    /// shell.run("keep 3").unwrap();
+    /// let expected = "Ann banked 150 points.\nAnn's round score: 150 (2 rolls)\nAnn has 150. Bob to roll.";
+    /// assert_eq!(expected, shell.run("bank").unwrap());
+    /// assert_eq!(150, shell.game().players()[0].score);
+    ///
+    /// assert_eq!("Two 1s (200) + one 5 (50) = 250", shell.run("score 1 1 5").unwrap());
+    /// assert!(shell.run("shuffle").is_err());
+    /// ```
+    ///
+    /// `odds` is about the choice at hand.
+    /// ```
+    /// use farkle::shell::Shell;
+    /// use farkle::ScoringRules;
+    ///
+    /// let mut shell = Shell::new(farkle::daily::round_rng(1, 0), ScoringRules::default());
+    /// assert_eq!("Six dice farkle 2% of the time.", shell.run("odds").unwrap());
+    /// shell.run("roll").unwrap();
+    /// assert_eq!("Keep [1] for 100, leaving 5 dice that farkle 8% of the time.", shell.run("odds").unwrap());
+    /// shell.run("keep a").unwrap();
+    /// let expected = "Rolling 5 dice farkles 8% of the time. Bank from 3,200 at stake.";
+    /// assert_eq!(expected, shell.run("odds").unwrap());
+    /// ```
+    ///
+    /// Only whole turns are saved.
+    /// ```
+    /// use farkle::shell::Shell;
+    /// use farkle::ScoringRules;
+    ///
+    /// let path = std::env::temp_dir().join("farkle_shell_doctest.txt");
+    /// let path = path.to_str().unwrap();
+    /// let mut shell = Shell::new(farkle::daily::round_rng(1, 0), ScoringRules::default());
+    /// shell.run("new Ann Bob").unwrap();
+    /// shell.run("roll").unwrap();
+    /// shell.run("keep a").unwrap();
+    /// assert!(shell.run(&format!("save {}", path)).is_err());
+    /// shell.run("bank").unwrap();
+    /// shell.run(&format!("save {}", path)).unwrap();
+    ///
+    /// let mut again = Shell::new(farkle::seeded_rng(2), ScoringRules::default());
+    /// let loaded = again.run(&format!("load {}", path)).unwrap();
+    /// assert_eq!("Carrying on a game between Ann and Bob. Bob to roll.", loaded);
+    /// assert_eq!(100, again.game().players()[0].score);
+    /// assert_eq!("Round  Ann  Bob\n    1  100", again.run("sheet").unwrap());
+    /// std::fs::remove_file(path).unwrap();
+    /// ```
+    pub fn run(&mut self, line: &str) -> Result<String, FarkleError> {
+        let line = line.trim();
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        match command {
+            "new" => self.new_game(rest),
+            "roll" => self.roll(),
+            "keep" => self.keep(rest),
+            "bank" if rest.is_empty() => self.respond(PlayerAction::Bank),
+            "free" if rest.is_empty() => self.respond(PlayerAction::FreeRoll),
+            "score" => {
+                let dice = parse_dice(rest)?;
+                let breakdown = self.game.rules().breakdown(&dice);
+                Ok(breakdown.explain())
+            }
+            "odds" => Ok(self.odds()),
+            "save" if !rest.is_empty() => self.save(rest),
+            "load" if !rest.is_empty() => self.load(rest),
+            "sheet" if rest.is_empty() => Ok(render_scoresheet(&self.record)),
+            "help" => Ok(SHELL_HELP.to_string()),
+            _ => Err(FarkleError::InvalidInput(format!("'{}' isn't a command. Try 'help'.", line))),
+        }
+    }
+
+    /// Start a game between players, or "You" alone.
+    fn new_game(&mut self, players: &str) -> Result<String, FarkleError> {
+        let mut players = players.split_whitespace().map(parse_player).collect::<Result<Vec<_>, _>>()?;
+        if players.is_empty() {
+            players.push(Player::new("You"));
+        }
+        GameConfig::validate_players(&players)?;
+        let rules = self.game.rules().clone();
+        self.record = GameRecord::new(players, WinCondition::TargetScore, rules);
+        self.game = self.record.game();
+        self.turn = None;
+        Ok(format!("A game between {}. {}", list_names(&self.record.players), self.whose_turn()))
+    }
+
+    /// Start a turn, or roll the dice left in the one being played.
+    fn roll(&mut self) -> Result<String, FarkleError> {
+        let refuse = |message: &str| Err(FarkleError::InvalidInput(message.to_string()));
+        match self.turn.as_ref().map(TurnEngine::pending) {
+            Some(Pending::Bank) => self.respond(PlayerAction::RollAgain),
+            Some(Pending::Keep(_)) => refuse("Keep some dice first, like 'keep 1'."),
+            _ if self.game.is_over() => refuse("The game's over. Start another with 'new'."),
+            _ => {
+                let (mut turn, events) = TurnEngine::start_with_rules(&mut self.rng, self.game.rules().clone());
+                turn.set_free_rolls(self.game.free_rolls());
+                self.turn = Some(turn);
+                Ok(self.tell(&events))
+            }
+        }
+    }
+
+    /// Keep dice from the roll.
+    fn keep(&mut self, dice: &str) -> Result<String, FarkleError> {
+        if !matches!(self.turn.as_ref().map(TurnEngine::pending), Some(Pending::Keep(_))) {
+            return Err(FarkleError::InvalidKeep("There are no dice to keep. Roll first.".to_string()));
+        }
+        match parse_keep_action(dice)? {
+            action @ (PlayerAction::Keep(_)
+            | PlayerAction::KeepValues(_)
+            | PlayerAction::KeepAllScoring
+            | PlayerAction::KeepAndBank(_)) => {
+                self.respond(action)
+            }
+            _ => Err(FarkleError::InvalidKeep("Say which dice to keep, like 'keep 1 3 5'.".to_string())),
+        }
+    }
+
+    /// Make the turn's pending choice, and end the turn if that's it over.
+    fn respond(&mut self, action: PlayerAction) -> Result<String, FarkleError> {
+        let Some(turn) = self.turn.as_mut() else {
+            return Err(FarkleError::InvalidInput("No turn is being played. Start one with 'roll'.".to_string()));
+        };
+        let events = turn.respond(action, &mut self.rng);
+        if let [Event::Rejected { message }] = &events[..] {
+            return Err(FarkleError::InvalidKeep(message.clone()));
+        }
+        let mut lines = self.tell(&events);
+        if let Some(turn) = self.turn.take_if(|turn| turn.score().is_some()) {
+            lines.push('\n');
+            lines.push_str(&self.end_turn(&turn));
+        }
+        Ok(lines)
+    }
+
+    /// Count the turn just played, returning what happened.
+    fn end_turn(&mut self, turn: &TurnEngine) -> String {
+        let player = self.game.current_player().clone();
+        let round_score = turn.score().unwrap_or_default();
+        let mut events = Vec::from_iter(turn.summary(&player.name));
+        let bonus = self.game.catch_up_bonus(round_score);
+        if bonus > 0 {
+            events.push(Event::CatchUp { player: player.name.clone(), bonus });
+        }
+        let doubled = self.game.double_bonus(round_score);
+        if doubled > 0 {
+            events.push(Event::DoublePoints { player: player.name.clone(), bonus: doubled });
+        }
+        let mut told = self.tell(&events);
+        let (seat, opening, penalty) =
+            (self.game.current_index(), self.game.opening_needed(), self.game.zilch_penalty(round_score));
+        let busts = self.game.busts(round_score);
+        self.game.set_free_rolls(turn.free_rolls());
+        self.game.record_round(round_score);
+        if round_score > 0 && round_score < opening {
+            told.push_str(&format!("\nThat's short of the {} it takes to get on the board.", opening));
+        } else if busts {
+            let target = render_points(player.target);
+            told.push_str(&format!("\nThat goes past {}, which has to be hit exactly.", target));
+        }
+        if penalty > 0 {
+            told.push_str(&format!("\nThree turns in a row scoring nothing: {} points off.", penalty));
+        }
+        let total = self.game.players()[seat].score;
+        format!("{}\n{} has {}. {}", told, player.name, render_points(total), self.whose_turn())
+    }
+
+    /// Returns who's next to roll, or who won.
+    fn whose_turn(&self) -> String {
+        match self.game.winner() {
+            Some(winner) if self.game.is_over() => format!("{} wins!", winner.name),
+            _ => format!("{} to roll.", self.game.current_player().name),
+        }
+    }
+
+    /// Returns events as text, writing them down for `save`.
+    fn tell(&mut self, events: &[Event]) -> String {
+        let player = &self.game.current_player().name;
+        for event in events {
+            self.record.record(player, event);
+        }
+        let lines: Vec<String> = events.iter().map(|event| render_other(player, event)).collect();
+        lines.join("\n").trim_end().to_string()
+    }
+
+    /// Returns the odds for the choice at hand.
+    fn odds(&self) -> String {
+        match self.turn.as_ref().map(|turn| (turn.pending(), turn.snapshot())) {
+            Some((Pending::Keep(dice), turn)) => {
+                let options = explore(&turn.rules, dice, turn.score);
+                if options.is_empty() {
+                    return format!("{:?} farkles.", dice);
+                }
+                let lines: Vec<String> = options
+                    .iter()
+                    .map(|option| {
+                        let (farkle, dice_left) = (option.farkle_chance * 100.0, option.dice_left);
+                        let keep = option.keep.scoring_dice();
+                        let points = render_points(option.points);
+                        let leaving = match dice_left {
+                            0 => return format!("Keep {:?} for {}, clearing the dice.", keep, points),
+                            1 => "1 die that farkles".to_string(),
+                            _ => format!("{} dice that farkle", dice_left),
+                        };
+                        format!("Keep {:?} for {}, leaving {} {:.0}% of the time.", keep, points, leaving, farkle)
+                    })
+                    .collect();
+                lines.join("\n")
+            }
+            Some((Pending::Bank, turn)) => {
+                let dice = match turn.num_dice {
+                    1 => "1 die".to_string(),
+                    num_dice => format!("{} dice", num_dice),
+                };
+                format!(
+                    "Rolling {} farkles {:.0}% of the time. Bank from {} at stake.",
+                    dice,
+                    farkle_chance(&turn.rules, turn.num_dice) * 100.0,
+                    render_points(bank_threshold(self.game.rules(), turn.num_dice))
+                )
+            }
+            _ => format!("Six dice farkle {:.0}% of the time.", farkle_chance(self.game.rules(), 6) * 100.0),
+        }
+    }
+
+    /// Write the game down at path.
+    fn save(&self, path: &str) -> Result<String, FarkleError> {
+        if self.turn.is_some() {
+            return Err(FarkleError::InvalidInput("Finish the turn first, so it's written down whole.".to_string()));
+        }
+        fs::write(path, self.record.to_string()).map_err(FarkleError::io(path))?;
+        Ok(format!("Saved to {}.", path))
+    }
+
+    /// Carry on the game written down at path.
+    fn load(&mut self, path: &str) -> Result<String, FarkleError> {
+        let record = GameRecord::parse(&fs::read_to_string(path).map_err(FarkleError::io(path))?)?;
+        self.game = record.game();
+        self.record = record;
+        self.turn = None;
+        Ok(format!("Carrying on a game between {}. {}", list_names(&self.record.players), self.whose_turn()))
+    }
+}
+
+/// Returns players' names like "Ann, Bob and Cat".
+fn list_names(players: &[Player]) -> String {
+    let names: Vec<&str> = players.iter().map(|player| player.name.as_str()).collect();
+    match names.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, others)) => format!("{} and {}", others.join(", "), last),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,65 @@
+//! Holding back events from spectators, like a stream delay, so nobody
+//! watching can tell a player what's happening as it happens.
+
+use crate::event::Event;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Events waiting to be sent to spectators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delayed {
+    delay: Duration,
+    waiting: VecDeque<(Instant, Event)>,
+}
+
+impl Delayed {
+    /// Returns a buffer holding each event back for delay.
+    pub fn new(delay: Duration) -> Delayed {
+        Delayed { delay, waiting: VecDeque::new() }
+    }
+
+    /// Hold back event, which happened at now. Pass it through
+    /// `Game::redact` with no viewer first, so spectators see no more than
+    /// the game shows them.
+    pub fn push(&mut self, event: Event, now: Instant) {
+        self.waiting.push_back((now + self.delay, event));
+    }
+
+    /// Returns the events due by now, in the order they happened.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::spectate::Delayed;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let start = Instant::now();
+    /// let mut spectators = Delayed::new(Duration::from_secs(30));
+    /// spectators.push(Event::Rolled { dice: vec![1, 5, 3] }, start);
+    /// spectators.push(Event::Banked { score: 150 }, start + Duration::from_secs(10));
+    /// assert!(spectators.release(start + Duration::from_secs(29)).is_empty());
+    /// assert_eq!(vec![Event::Rolled { dice: vec![1, 5, 3] }], spectators.release(start + Duration::from_secs(35)));
+    /// assert_eq!(Some(start + Duration::from_secs(40)), spectators.next_due());
+    /// assert_eq!(vec![Event::Banked { score: 150 }], spectators.release(start + Duration::from_secs(40)));
+    /// assert_eq!(None, spectators.next_due());
+    /// ```
+    pub fn release(&mut self, now: Instant) -> Vec<Event> {
+        let mut due = Vec::new();
+        while self.waiting.front().is_some_and(|(at, _event)| *at <= now) {
+            if let Some((_at, event)) = self.waiting.pop_front() {
+                due.push(event);
+            }
+        }
+        due
+    }
+
+    /// Returns when the next event is due, to wait until.
+    pub fn next_due(&self) -> Option<Instant> {
+        self.waiting.front().map(|(at, _event)| *at)
+    }
+
+    /// Returns all the events held back, like at the end of a game.
+    pub fn flush(&mut self) -> Vec<Event> {
+        self.waiting.drain(..).map(|(_at, event)| event).collect()
+    }
+}
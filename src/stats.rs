@@ -0,0 +1,268 @@
+//! Statistics about a game, tallied from its events, for the end of game
+//! screen.
+
+use crate::event::Event;
+use crate::game::{Game, GameSnapshot};
+use crate::{get_breakdown, ScoringRules};
+use crate::odds::{expected_scoring_dice, farkle_chance};
+use crate::render::{render_other, render_points};
+use crate::turn::{Decider, PlayerAction, TurnSnapshot};
+
+/// What one player did over a game.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PlayerStats {
+    pub name: String,
+    pub rounds: u32,
+    pub rolls: u32,
+    pub farkles: u32,
+    pub hot_dice: u32,
+    /// Most points banked in a round.
+    pub best_round: u32,
+    /// dice_rolled[n] is how many rolls were of n dice.
+    pub dice_rolled: [u32; 7],
+    /// Rolls with no scoring dice.
+    pub farkled_rolls: u32,
+    /// Dice that could be kept for points, over every roll.
+    pub scoring_dice: u32,
+    /// Rolls of six that came up the same as the roll of six before.
+    pub same_rolls: u32,
+    /// Points added for banking in double points rounds.
+    pub double_points: u32,
+}
+
+impl PlayerStats {
+    /// Returns how many rolls a player with the same rolls farkles on
+    /// average.
+    pub fn expected_farkles(&self) -> f64 {
+        self.expected(|num_dice| farkle_chance(&ScoringRules::STANDARD, num_dice))
+    }
+
+    /// Returns how lucky the player's rolls were: 100 for as many scoring
+    /// dice as average, more for luckier rolls, or None before they roll.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::stats::GameStats;
+    /// let mut stats = GameStats::new();
+    /// stats.record("Ann", &Event::Rolled { dice: vec![1, 1, 5, 5, 2, 2] });
+    /// stats.record("Bob", &Event::Rolled { dice: vec![2, 3, 4, 6, 6, 2] });
+    /// let luck: Vec<Option<f64>> = stats.players().iter().map(|player| player.luck()).collect();
+    /// assert!(luck[0].unwrap() > 100.0);
+    /// assert_eq!(Some(0.0), luck[1]);
+    /// assert_eq!(1, stats.players()[1].farkled_rolls);
+    /// ```
+    pub fn luck(&self) -> Option<f64> {
+        let expected = self.expected(|num_dice| expected_scoring_dice(&ScoringRules::STANDARD, num_dice));
+        (expected > 0.0).then(|| 100.0 * self.scoring_dice as f64 / expected)
+    }
+
+    /// Returns the sum of per_roll for the number of dice in each roll.
+    fn expected(&self, per_roll: fn(i8) -> f64) -> f64 {
+        (1..=6)
+            .filter(|num_dice| self.dice_rolled[*num_dice as usize] > 0)
+            .map(|num_dice| self.dice_rolled[num_dice as usize] as f64 * per_roll(num_dice))
+            .sum()
+    }
+}
+
+/// Each player's stats and a transcript of everything that happened.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GameStats {
+    players: Vec<PlayerStats>,
+    /// Each player's last roll of six, sorted.
+    last_full_rolls: Vec<Vec<i16>>,
+    transcript: Vec<String>,
+}
+
+impl GameStats {
+    /// Returns stats with nothing tallied yet.
+    pub fn new() -> GameStats {
+        GameStats::default()
+    }
+
+    /// Returns the stats of each player seen so far, in the order they were
+    /// first seen.
+    pub fn players(&self) -> &[PlayerStats] {
+        &self.players
+    }
+
+    /// Returns a line for everything that happened.
+    pub fn transcript(&self) -> &[String] {
+        &self.transcript
+    }
+
+    /// Tally event, which happened during player's round.
+    ///
+    /// Returns SameRollAgain if event is a roll of six that came up the same
+    /// as the player's last, to tell them.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::stats::GameStats;
+    /// let mut stats = GameStats::new();
+    /// assert_eq!(None, stats.record("Ann", &Event::Rolled { dice: vec![2, 3, 3, 4, 6, 6] }));
+    /// assert_eq!(None, stats.record("Ann", &Event::Rolled { dice: vec![3, 4] }));
+    /// let again = stats.record("Ann", &Event::Rolled { dice: vec![6, 3, 2, 6, 4, 3] });
+    /// assert_eq!(Some(Event::SameRollAgain { dice: vec![6, 3, 2, 6, 4, 3] }), again);
+    /// assert_eq!(1, stats.players()[0].same_rolls);
+    /// assert_eq!("Ann rolled exactly the same six dice again!", stats.transcript()[3]);
+    /// ```
+    pub fn record(&mut self, player: &str, event: &Event) -> Option<Event> {
+        self.transcript.push(render_other(player, event).trim_end().to_string());
+        let i = match self.players.iter().position(|stats| stats.name == player) {
+            Some(i) => i,
+            None => {
+                self.players.push(PlayerStats { name: player.to_string(), ..PlayerStats::default() });
+                self.last_full_rolls.push(Vec::new());
+                self.players.len() - 1
+            }
+        };
+        let stats = &mut self.players[i];
+        let mut derived = None;
+        match event {
+            Event::Rolled { dice } => {
+                if let Some(rolls) = stats.dice_rolled.get_mut(dice.len()) {
+                    *rolls += 1;
+                }
+                let breakdown = get_breakdown(dice);
+                stats.farkled_rolls += (breakdown.total() == 0) as u32;
+                stats.scoring_dice += breakdown.scoring_dice().len() as u32;
+                if dice.len() == 6 {
+                    let mut sorted = dice.clone();
+                    sorted.sort_unstable();
+                    if sorted == self.last_full_rolls[i] {
+                        stats.same_rolls += 1;
+                        derived = Some(Event::SameRollAgain { dice: dice.clone() });
+                    }
+                    self.last_full_rolls[i] = sorted;
+                }
+            }
+            Event::HotDice => stats.hot_dice += 1,
+            Event::DoublePoints { bonus, .. } => stats.double_points += bonus,
+            Event::RoundSummary { banked, farkled, rolls, .. } => {
+                stats.rounds += 1;
+                stats.rolls += rolls;
+                stats.farkles += *farkled as u32;
+                stats.best_round = stats.best_round.max(*banked);
+            }
+            _ => {}
+        }
+        if let Some(derived) = &derived {
+            self.transcript.push(render_other(player, derived).trim_end().to_string());
+        }
+        derived
+    }
+
+    /// Returns the final standings of game and who stood out.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::game::{Game, Player, WinCondition};
+    /// use farkle::stats::GameStats;
+    ///
+    /// let mut game = Game::with_win_condition(vec![Player::new("Ann"), Player::new("Bob")], WinCondition::RoundLimit(1));
+    /// let mut stats = GameStats::new();
+    /// for (name, banked) in [("Ann", 450), ("Bob", 0)] {
+    ///     let summary = Event::RoundSummary { player: name.to_string(), banked, farkled: banked == 0, rolls: 2 };
+    ///     stats.record(name, &summary);
+    ///     game.record_round(banked);
+    /// }
+    /// let expected = "\
+    /// Final standings:
+    /// 1. Ann: 450 points
+    /// 2. Bob: 0 points
+    ///
+    /// Biggest round: Ann, 450 points
+    /// Most farkles: Bob, 1";
+    /// assert_eq!(expected, stats.podium(&game));
+    /// ```
+    pub fn podium(&self, game: &Game) -> String {
+        let mut lines = vec!["Final standings:".to_string()];
+        for (place, player) in game.standings().iter().enumerate() {
+            let left = if game.has_left(&player.name) { " (left)" } else { "" };
+            lines.push(format!("{}. {}: {} points{}", place + 1, player.name, render_points(player.score), left));
+        }
+        lines.push(String::new());
+        let highlights = [
+            ("Biggest round", self.most(|stats| stats.best_round), " points"),
+            ("Most farkles", self.most(|stats| stats.farkles), ""),
+            ("Most hot dice", self.most(|stats| stats.hot_dice), ""),
+            ("Same roll twice", self.most(|stats| stats.same_rolls), ""),
+            ("Most from double points", self.most(|stats| stats.double_points), " points"),
+        ];
+        for (title, most, unit) in highlights {
+            if let Some((name, count)) = most {
+                lines.push(format!("{}: {}, {}{}", title, name, render_points(count), unit));
+            }
+        }
+        // Luck, for arguing about after.
+        for stats in &self.players {
+            if let Some(luck) = stats.luck() {
+                lines.push(format!(
+                    "{}'s luck: {:.0} (farkled on {} of {} rolls, {:.1} expected)",
+                    stats.name,
+                    luck,
+                    stats.farkled_rolls,
+                    stats.dice_rolled.iter().sum::<u32>(),
+                    stats.expected_farkles()
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Returns the first player with the highest stat, if it isn't 0.
+    fn most(&self, stat: fn(&PlayerStats) -> u32) -> Option<(&str, u32)> {
+        let mut most: Option<(&str, u32)> = None;
+        for stats in &self.players {
+            let count = stat(stats);
+            if count > 0 && most.is_none_or(|(_name, best)| count > best) {
+                most = Some((&stats.name, count));
+            }
+        }
+        most
+    }
+}
+
+/// A decider whose events are tallied in game stats.
+pub struct Tallied<'a, D: ?Sized> {
+    decider: &'a mut D,
+    stats: &'a mut GameStats,
+    player: &'a str,
+}
+
+impl<'a, D: Decider + ?Sized> Tallied<'a, D> {
+    /// Returns decider, tallying its events for player in stats.
+    pub fn new(decider: &'a mut D, stats: &'a mut GameStats, player: &'a str) -> Tallied<'a, D> {
+        Tallied { decider, stats, player }
+    }
+}
+
+impl<'a, D: Decider + ?Sized> Decider for Tallied<'a, D> {
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> PlayerAction {
+        self.decider.choose_keep(dice, turn)
+    }
+
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> PlayerAction {
+        self.decider.choose_bank(turn)
+    }
+
+    fn remark(&mut self) -> Option<String> {
+        self.decider.remark()
+    }
+
+    fn on_turn_start(&mut self, game: &GameSnapshot) {
+        self.decider.on_turn_start(game);
+    }
+
+    fn on_event(&mut self, event: &Event) {
+        let derived = self.stats.record(self.player, event);
+        self.decider.on_event(event);
+        if let Some(derived) = derived {
+            self.decider.on_event(&derived);
+        }
+    }
+}
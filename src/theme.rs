@@ -0,0 +1,151 @@
+//! How the game looks on the command line: its prompts, its colours and how
+//! dice are drawn.
+
+use crate::error::FarkleError;
+use crate::render::render_dice_emoji;
+use std::fmt;
+
+/// How dice are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiceStyle {
+    /// In brackets, like "[1, 5, 3]".
+    List,
+    /// Just the numbers, like "1 5 3".
+    Digits,
+    /// As die faces, like "⚀ ⚄ ⚂".
+    Faces,
+}
+
+/// Colours, as ANSI escape codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    /// For the dice.
+    pub dice: &'static str,
+    /// For banking and hot dice.
+    pub good: &'static str,
+    /// For farkles.
+    pub bad: &'static str,
+}
+
+/// A look for the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub name: &'static str,
+    /// Asked before each keep.
+    pub keep_prompt: &'static str,
+    /// Asked before each bank, with "{score}" for the points at stake.
+    pub bank_prompt: &'static str,
+    pub dice: DiceStyle,
+    /// No colours if None.
+    pub palette: Option<Palette>,
+}
+
+impl Theme {
+    /// The usual look.
+    pub const CLASSIC: Theme = Theme {
+        name: "classic",
+        keep_prompt: "Enter dice to keep (1-6), with 'b' after them to bank too, 'a' to keep all scoring dice, \
+            'b' to keep them and bank, or 's' to see the scoring:",
+        bank_prompt: "Your score this round is {score}\nWould you like to keep this score?",
+        dice: DiceStyle::List,
+        palette: None,
+    };
+
+    /// Short prompts, for players who know the game.
+    pub const MINIMAL: Theme = Theme {
+        name: "minimal",
+        keep_prompt: "Keep (1-6, a, b, s):",
+        bank_prompt: "{score} at stake. Bank? (y/n)",
+        dice: DiceStyle::Digits,
+        palette: None,
+    };
+
+    /// Bold, bright colours, for reading at a glance.
+    pub const HIGH_CONTRAST: Theme = Theme {
+        name: "high-contrast",
+        palette: Some(Palette { dice: "\x1b[1;97m", good: "\x1b[1;30;103m", bad: "\x1b[1;97;41m" }),
+        ..Theme::CLASSIC
+    };
+
+    /// Dice drawn as their faces.
+    pub const FACES: Theme = Theme { name: "faces", dice: DiceStyle::Faces, ..Theme::CLASSIC };
+
+    /// Every built-in theme, in the order they're listed.
+    pub const ALL: [Theme; 4] = [Theme::CLASSIC, Theme::MINIMAL, Theme::HIGH_CONTRAST, Theme::FACES];
+
+    /// Returns the built-in theme called name.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::theme::Theme;
+    /// assert_eq!(Ok(Theme::HIGH_CONTRAST), Theme::named("High-Contrast"));
+    /// assert!(Theme::named("neon").is_err());
+    /// ```
+    pub fn named(name: &str) -> Result<Theme, FarkleError> {
+        let name = name.trim().to_lowercase();
+        Theme::ALL.into_iter().find(|theme| theme.name == name).ok_or_else(|| {
+            let names: Vec<&str> = Theme::ALL.iter().map(|theme| theme.name).collect();
+            let problem = format!("'{}' isn't a theme. Pick one of {}.", name, names.join(", "));
+            FarkleError::InvalidConfig(vec![problem])
+        })
+    }
+
+    /// Returns dice drawn in the theme.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::theme::Theme;
+    /// assert_eq!("[1, 5, 3]", Theme::CLASSIC.dice(&[1, 5, 3]));
+    /// assert_eq!("1 5 3", Theme::MINIMAL.dice(&[1, 5, 3]));
+    /// assert_eq!("⚀ ⚄ ⚂", Theme::FACES.dice(&[1, 5, 3]));
+    /// assert_eq!("\x1b[1;97m[1, 5, 3]\x1b[0m", Theme::HIGH_CONTRAST.dice(&[1, 5, 3]));
+    /// ```
+    pub fn dice(&self, dice: &[i16]) -> String {
+        let text = match self.dice {
+            DiceStyle::List => format!("{:?}", dice),
+            DiceStyle::Digits => dice.iter().map(i16::to_string).collect::<Vec<_>>().join(" "),
+            DiceStyle::Faces => render_dice_emoji(dice).chars().map(String::from).collect::<Vec<_>>().join(" "),
+        };
+        paint(self.palette.map(|palette| palette.dice), &text)
+    }
+
+    /// Returns the question asked before banking score.
+    ///
+    /// ### Examples
+    /// ```
+    /// assert_eq!("350 at stake. Bank? (y/n)", farkle::theme::Theme::MINIMAL.ask_to_bank(350));
+    /// ```
+    pub fn ask_to_bank(&self, score: u32) -> String {
+        self.bank_prompt.replace("{score}", &score.to_string())
+    }
+
+    /// Returns text coloured as good news.
+    pub fn good(&self, text: &str) -> String {
+        paint(self.palette.map(|palette| palette.good), text)
+    }
+
+    /// Returns text coloured as bad news.
+    pub fn bad(&self, text: &str) -> String {
+        paint(self.palette.map(|palette| palette.bad), text)
+    }
+}
+
+/// Returns text in colour, if there is one.
+fn paint(colour: Option<&str>, text: &str) -> String {
+    match colour {
+        Some(colour) => format!("{}{}\x1b[0m", colour, text),
+        None => text.to_string(),
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::CLASSIC
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
@@ -0,0 +1,881 @@
+//! State of a player's turn: every roll until they bank or farkle.
+
+use crate::event::Event;
+use crate::game::GameSnapshot;
+use crate::parse::positions_of;
+use crate::{keep_dice, roll_dice, ScoringRules, TOTAL_DICE};
+use rand::Rng;
+use std::fmt;
+
+/// A roll made during a turn, the dice kept from it and the points they
+/// added to the turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollRecord {
+    pub dice: Vec<i16>,
+    pub kept: Vec<i16>,
+    pub score: u32,
+    /// Points on top of the dice kept, like a hot streak bonus.
+    pub bonus: u32,
+}
+
+impl RollRecord {
+    /// Returns the points the roll added to the turn, bonus and all.
+    pub fn points(&self) -> u32 {
+        self.score.saturating_add(self.bonus)
+    }
+}
+
+impl fmt::Display for RollRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.score == 0 {
+            return write!(f, "farkled {:?}", self.dice);
+        }
+        write!(f, "kept {:?} +{}", self.kept, self.score)?;
+        if self.bonus > 0 {
+            write!(f, " (+{} hot streak bonus)", self.bonus)?;
+        }
+        Ok(())
+    }
+}
+
+/// Something a player can do during a turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayerAction {
+    /// Keep the dice at these positions in the roll.
+    Keep(Vec<usize>),
+    /// Keep one die of each of these values.
+    KeepValues(Vec<i16>),
+    /// Keep every scoring die.
+    KeepAllScoring,
+    /// Show how the roll scores.
+    ShowBreakdown,
+    /// Bank the turn score. Straight after a roll, every scoring die is
+    /// kept first.
+    Bank,
+    /// Roll the remaining dice.
+    RollAgain,
+    /// Spend a free roll to roll the same dice again, keeping none.
+    FreeRoll,
+    /// Keep dice as Keep, KeepValues or KeepAllScoring says, then bank,
+    /// in one step. Either both happen or, if the keep doesn't fit the
+    /// roll, neither does.
+    KeepAndBank(Box<PlayerAction>),
+    /// Leave the game.
+    Quit,
+}
+
+/// Running state of the current turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnState {
+    num_dice: i8,
+    score: u32,
+    history: Vec<RollRecord>,
+    clears: u32,
+    farkled: bool,
+}
+
+/// Read only view of a turn, for frontends to display.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnSnapshot {
+    pub num_dice: i8,
+    pub score: u32,
+    pub history: Vec<RollRecord>,
+    /// Rules the turn is played by.
+    pub rules: ScoringRules,
+    /// Free rolls the player holds.
+    pub free_rolls: u32,
+}
+
+impl TurnSnapshot {
+    /// Returns a line per roll in the turn.
+    ///
+    /// ### Examples
+    /// ```
+    /// let mut state = farkle::turn::TurnState::new();
+    /// state.record(vec![1, 1, 2, 3, 4, 6], vec![1, 1], 200);
+    /// state.record(vec![5, 2, 3, 3], vec![5], 50);
+    /// let expected = vec!["Roll 1: kept [1, 1] +200", "Roll 2: kept [5] +50"];
+    /// assert_eq!(expected, state.snapshot().history_lines());
+    /// ```
+    pub fn history_lines(&self) -> Vec<String> {
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(i, roll)| format!("Roll {}: {}", i + 1, roll))
+            .collect()
+    }
+}
+
+impl Default for TurnState {
+    fn default() -> Self {
+        TurnState::new()
+    }
+}
+
+impl TurnState {
+    /// Returns the state at the start of a turn.
+    pub fn new() -> TurnState {
+        TurnState {
+            num_dice: TOTAL_DICE,
+            score: 0,
+            history: Vec::new(),
+            clears: 0,
+            farkled: false,
+        }
+    }
+
+    /// Returns the number of dice to roll next.
+    pub fn num_dice(&self) -> i8 {
+        self.num_dice
+    }
+
+    /// Returns the points scored so far this turn, the sum of each roll's
+    /// points, or 0 once the turn has farkled.
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Returns the rolls made so far this turn.
+    pub fn history(&self) -> &[RollRecord] {
+        &self.history
+    }
+
+    /// Returns how many times every die has been kept this turn.
+    pub fn clears(&self) -> u32 {
+        self.clears
+    }
+
+    /// Record a scoring roll.
+    ///
+    /// Returns true if every die was kept, so all the dice get rolled again.
+    ///
+    /// ### Examples
+    /// ```
+    /// let mut state = farkle::turn::TurnState::new();
+    /// assert_eq!(false, state.record(vec![1, 1, 2, 3, 4, 6], vec![1, 1], 200));
+    /// assert_eq!(4, state.num_dice());
+    /// assert_eq!(true, state.record(vec![5, 5, 5, 1], vec![5, 5, 5, 1], 600));
+    /// assert_eq!(6, state.num_dice());
+    /// assert_eq!(800, state.score());
+    /// assert_eq!(2, state.history().len());
+    /// ```
+    pub fn record(&mut self, dice: Vec<i16>, kept: Vec<i16>, score: u32) -> bool {
+        let num_kept = kept.len() as i8;
+        self.score = self.score.saturating_add(score);
+        self.history.push(RollRecord { dice, kept, score, bonus: 0 });
+        if self.num_dice - num_kept <= 0 {
+            self.num_dice = TOTAL_DICE;
+            self.clears += 1;
+            true
+        } else {
+            self.num_dice -= num_kept;
+            false
+        }
+    }
+
+    /// Record a roll that farkled with kept, losing the turn score.
+    ///
+    /// ### Examples
+    /// ```
+    /// let mut state = farkle::turn::TurnState::new();
+    /// state.record(vec![1, 1, 1, 5, 5, 5], vec![1, 1, 1, 5, 5, 5], 2500);
+    /// state.farkle(vec![2, 3, 4, 6, 6, 2], vec![]);
+    /// assert_eq!(0, state.score());
+    /// assert!(state.is_farkled());
+    /// let points: Vec<u32> = state.history().iter().map(|roll| roll.points()).collect();
+    /// assert_eq!(vec![2500, 0], points);
+    /// let expected = vec!["Roll 1: kept [1, 1, 1, 5, 5, 5] +2500", "Roll 2: farkled [2, 3, 4, 6, 6, 2]"];
+    /// assert_eq!(expected, state.snapshot().history_lines());
+    /// ```
+    pub fn farkle(&mut self, dice: Vec<i16>, kept: Vec<i16>) {
+        self.history.push(RollRecord { dice, kept, score: 0, bonus: 0 });
+        self.score = 0;
+        self.farkled = true;
+    }
+
+    /// Returns true once the turn has farkled.
+    pub fn is_farkled(&self) -> bool {
+        self.farkled
+    }
+
+    /// Add bonus points to the last roll recorded.
+    ///
+    /// ### Examples
+    /// ```
+    /// let mut state = farkle::turn::TurnState::new();
+    /// state.record(vec![1, 1, 1, 5, 5, 5], vec![1, 1, 1, 5, 5, 5], 2500);
+    /// state.add_bonus(500);
+    /// assert_eq!(3000, state.score());
+    /// assert_eq!(vec!["Roll 1: kept [1, 1, 1, 5, 5, 5] +2500 (+500 hot streak bonus)"], state.snapshot().history_lines());
+    /// ```
+    pub fn add_bonus(&mut self, bonus: u32) {
+        if let Some(roll) = self.history.last_mut() {
+            roll.bonus = roll.bonus.saturating_add(bonus);
+            self.score = self.score.saturating_add(bonus);
+        }
+    }
+
+    /// Returns a snapshot of the turn.
+    pub fn snapshot(&self) -> TurnSnapshot {
+        TurnSnapshot {
+            num_dice: self.num_dice,
+            score: self.score,
+            history: self.history.clone(),
+            rules: ScoringRules::default(),
+            free_rolls: 0,
+        }
+    }
+}
+
+/// Makes a player's choices during their turn, and sees what happens.
+pub trait Decider {
+    /// Returns what to keep from dice, given the turn so far.
+    fn choose_keep(&mut self, dice: &[i16], turn: &TurnSnapshot) -> PlayerAction;
+
+    /// Returns whether to bank the turn score or roll again.
+    fn choose_bank(&mut self, turn: &TurnSnapshot) -> PlayerAction;
+
+    /// Returns something to say about the last choice, if anything.
+    fn remark(&mut self) -> Option<String> {
+        None
+    }
+
+    /// See something that happened during the turn.
+    fn on_event(&mut self, _event: &Event) {}
+
+    /// See the game as it stands, before the turn starts.
+    fn on_turn_start(&mut self, _game: &GameSnapshot) {}
+}
+
+/// Pass on the decider's remark about its last choice, if it made one.
+fn pass_on_remark<D: Decider + ?Sized>(decider: &mut D) {
+    if let Some(text) = decider.remark() {
+        decider.on_event(&Event::Remark { text });
+    }
+}
+
+/// The choice a turn is waiting on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pending {
+    /// Which of these dice to keep.
+    Keep(Vec<i16>),
+    /// Whether to bank or roll again.
+    Bank,
+    /// Nothing, the turn is over with this score.
+    Over(u32),
+}
+
+/// A turn as a state machine, waiting on one choice at a time.
+///
+/// The turn only changes when it's given a response, so a frontend can
+/// give up waiting on a player (they disconnected, or took too long) and
+/// respond for them later without losing anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnEngine {
+    state: TurnState,
+    pending: Pending,
+    rolls: u32,
+    rules: ScoringRules,
+    free_rolls: u32,
+}
+
+/// Check indices are positions of different dice in a roll of dice.
+fn check_indices(dice: &[i16], indices: &[usize]) -> Result<(), String> {
+    for (i, index) in indices.iter().enumerate() {
+        if *index >= dice.len() {
+            return Err(format!("There's no die {} in a roll of {} dice.", index + 1, dice.len()));
+        }
+        if indices[..i].contains(index) {
+            return Err(format!("Die {} can only be kept once.", index + 1));
+        }
+    }
+    Ok(())
+}
+
+impl TurnEngine {
+    /// Returns a new turn after its first roll, and what happened.
+    pub fn start<R: Rng>(rng: &mut R) -> (TurnEngine, Vec<Event>) {
+        TurnEngine::start_with_rules(rng, ScoringRules::default())
+    }
+
+    /// Returns a new turn played by rules after its first roll, and what
+    /// happened.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::turn::{PlayerAction, TurnEngine};
+    /// use farkle::ScoringRules;
+    ///
+    /// let rules = ScoringRules { hot_streak_bonus: Some(500), ..ScoringRules::default() };
+    /// let mut rng = farkle::seeded_rng(2);
+    /// let (mut turn, _) = TurnEngine::start_with_rules(&mut rng, rules);
+    /// // Clear the dice, then roll a straight with all six.
+    /// let mut events = turn.respond(PlayerAction::KeepAllScoring, &mut rng);
+    /// for _ in 0..3 {
+    ///     turn.respond(PlayerAction::RollAgain, &mut rng);
+    ///     events = turn.respond(PlayerAction::KeepAllScoring, &mut rng);
+    /// }
+    /// assert!(matches!(events[..], [Event::Kept { .. }, Event::HotDice, Event::HotStreak { clears: 2, bonus: 500 }]));
+    /// assert_eq!(2450, turn.snapshot().score);
+    /// ```
+    pub fn start_with_rules<R: Rng>(rng: &mut R, rules: ScoringRules) -> (TurnEngine, Vec<Event>) {
+        let mut turn = TurnEngine {
+            state: TurnState::new(),
+            pending: Pending::Bank,
+            rolls: 0,
+            rules,
+            free_rolls: 0,
+        };
+        let events = turn.roll(rng);
+        (turn, events)
+    }
+
+    /// Returns the choice the turn is waiting on.
+    pub fn pending(&self) -> &Pending {
+        &self.pending
+    }
+
+    /// Returns a snapshot of the turn.
+    pub fn snapshot(&self) -> TurnSnapshot {
+        TurnSnapshot { rules: self.rules.clone(), free_rolls: self.free_rolls, ..self.state.snapshot() }
+    }
+
+    /// Returns the free rolls the player holds.
+    pub fn free_rolls(&self) -> u32 {
+        self.free_rolls
+    }
+
+    /// Give the player the free rolls they hold going into the turn.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::turn::{Pending, PlayerAction, TurnEngine};
+    /// use farkle::ScoringRules;
+    ///
+    /// let rules = ScoringRules { free_rolls: Some(2), ..ScoringRules::default() };
+    /// let mut rng = farkle::daily::round_rng(19, 0);
+    /// let (mut turn, _) = TurnEngine::start_with_rules(&mut rng, rules);
+    /// // Three pairs use all six dice, earning a free roll.
+    /// let events = turn.respond(PlayerAction::KeepAllScoring, &mut rng);
+    /// assert_eq!(Event::FreeRollEarned { held: 1 }, events[1]);
+    /// turn.respond(PlayerAction::RollAgain, &mut rng);
+    ///
+    /// // Spending it rolls the same number of dice again.
+    /// let events = turn.respond(PlayerAction::FreeRoll, &mut rng);
+    /// assert!(matches!(&events[..], [Event::FreeRollUsed { held: 0 }, Event::Rolled { dice }] if dice.len() == 6));
+    /// assert_eq!(0, turn.free_rolls());
+    /// let events = turn.respond(PlayerAction::FreeRoll, &mut rng);
+    /// assert!(matches!(events[..], [Event::Rejected { .. }]));
+    ///
+    /// // They carry over from turn to turn.
+    /// let (mut turn, _) = TurnEngine::start(&mut rng);
+    /// turn.set_free_rolls(1);
+    /// assert_eq!(1, turn.snapshot().free_rolls);
+    /// ```
+    pub fn set_free_rolls(&mut self, held: u32) {
+        self.free_rolls = held;
+    }
+
+    /// Returns the number of the latest roll, counting from 1.
+    pub fn roll_number(&self) -> u32 {
+        self.rolls
+    }
+
+    /// Returns the turn score once the turn is over.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::turn::{PlayerAction, TurnEngine};
+    ///
+    /// let mut rng = farkle::daily::round_rng(19, 0);
+    /// let (mut turn, _) = TurnEngine::start(&mut rng);
+    /// // Three pairs clear the dice, then three 1s, then a farkle.
+    /// for _ in 0..2 {
+    ///     turn.respond(PlayerAction::KeepAllScoring, &mut rng);
+    ///     turn.respond(PlayerAction::RollAgain, &mut rng);
+    /// }
+    /// assert_eq!(2500, turn.snapshot().score);
+    /// turn.respond(PlayerAction::KeepAllScoring, &mut rng);
+    ///
+    /// // The farkle loses everything, hot dice and all.
+    /// assert_eq!(Some(0), turn.score());
+    /// assert_eq!(0, turn.snapshot().score);
+    /// let lines = vec!["Roll 1: kept [1, 3, 6, 3, 1, 6] +1500", "Roll 2: kept [1, 1, 1] +1000", "Roll 3: farkled [6, 4, 2]"];
+    /// assert_eq!(lines, turn.snapshot().history_lines());
+    /// ```
+    pub fn score(&self) -> Option<u32> {
+        match self.pending {
+            Pending::Over(score) => Some(score),
+            _ => None,
+        }
+    }
+
+    /// Returns a RoundSummary event for player once the turn is over.
+    pub fn summary(&self, player: &str) -> Option<Event> {
+        let banked = self.score()?;
+        Some(Event::RoundSummary {
+            player: player.to_string(),
+            banked,
+            farkled: banked == 0,
+            rolls: self.rolls,
+        })
+    }
+
+    /// Returns the response to use for a player who didn't make the
+    /// pending choice: keep the scoring dice and bank.
+    pub fn default_action(&self) -> PlayerAction {
+        PlayerAction::Bank
+    }
+
+    /// Roll the dice left.
+    fn roll<R: Rng>(&mut self, rng: &mut R) -> Vec<Event> {
+        let dice = roll_dice(self.state.num_dice(), rng);
+        self.rolls += 1;
+        self.pending = Pending::Keep(dice.clone());
+        vec![Event::Rolled { dice }]
+    }
+
+    /// Make the pending choice, rolling with rng if that's the choice.
+    ///
+    /// Returns what happened. A response that doesn't fit the choice is
+    /// rejected and the same choice stays pending. Debug builds check the
+    /// turn with `validate_state` after each response.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::turn::{Pending, PlayerAction, TurnEngine};
+    ///
+    /// let mut rng = farkle::daily::round_rng(1, 0);
+    /// let (mut turn, _) = TurnEngine::start(&mut rng);
+    /// assert_eq!(&Pending::Keep(vec![4, 3, 6, 1, 2, 4]), turn.pending());
+    ///
+    /// let events = turn.respond(PlayerAction::RollAgain, &mut rng);
+    /// assert!(matches!(events[..], [Event::Rejected { .. }]));
+    /// assert_eq!(&Pending::Keep(vec![4, 3, 6, 1, 2, 4]), turn.pending());
+    ///
+    /// turn.respond(PlayerAction::Keep(vec![3]), &mut rng);
+    /// assert_eq!(&Pending::Bank, turn.pending());
+    /// turn.respond(turn.default_action(), &mut rng);
+    /// assert_eq!(Some(100), turn.score());
+    /// let summary = Event::RoundSummary { player: "Ann".to_string(), banked: 100, farkled: false, rolls: 1 };
+    /// assert_eq!(Some(summary), turn.summary("Ann"));
+    /// ```
+    ///
+    /// Only the dice rolled decide the score, so a client trying to cheat
+    /// gets nowhere.
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::turn::{PlayerAction, TurnEngine};
+    ///
+    /// let mut rng = farkle::daily::round_rng(1, 0);
+    /// let (mut turn, _) = TurnEngine::start(&mut rng);
+    /// let before = turn.clone();
+    /// // Dice are [4, 3, 6, 1, 2, 4], with only the 1 scoring.
+    /// let cheats = vec![
+    ///     PlayerAction::Keep(vec![0, 1, 2, 3, 4, 5]),
+    ///     PlayerAction::Keep(vec![3, 3, 3]),
+    ///     PlayerAction::Keep(vec![3, 6]),
+    ///     PlayerAction::KeepValues(vec![1, 1, 1]),
+    ///     PlayerAction::KeepValues(vec![5]),
+    ///     PlayerAction::RollAgain,
+    ///     // Dice that don't score, from a roll that does, aren't a farkle.
+    ///     PlayerAction::Keep(vec![0]),
+    ///     PlayerAction::Keep(vec![]),
+    /// ];
+    /// for cheat in cheats {
+    ///     let events = turn.respond(cheat, &mut rng);
+    ///     assert!(matches!(events[..], [Event::Rejected { .. }]));
+    ///     assert_eq!(before, turn);
+    /// }
+    ///
+    /// // Once the 1 is kept, only banking or rolling again will do, so no
+    /// // more dice can be kept, scoring or not, and nothing else banks.
+    /// turn.respond(PlayerAction::Keep(vec![3]), &mut rng);
+    /// let before = turn.clone();
+    /// let cheats = vec![
+    ///     PlayerAction::Keep(vec![0]),
+    ///     PlayerAction::Keep(vec![3]),
+    ///     PlayerAction::KeepValues(vec![4]),
+    ///     PlayerAction::KeepAllScoring,
+    ///     PlayerAction::ShowBreakdown,
+    ///     PlayerAction::Quit,
+    /// ];
+    /// for cheat in cheats {
+    ///     let events = turn.respond(cheat, &mut rng);
+    ///     assert!(matches!(events[..], [Event::Rejected { .. }]));
+    ///     assert_eq!(before, turn);
+    /// }
+    /// ```
+    ///
+    /// Under rules where hot dice have to be rolled, clearing the dice rolls
+    /// all six again, even for a player asking to bank.
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::turn::{Pending, PlayerAction, TurnEngine};
+    /// use farkle::ScoringRules;
+    ///
+    /// let rules = ScoringRules { must_roll_hot_dice: true, ..ScoringRules::default() };
+    /// let mut rng = farkle::daily::round_rng(19, 0);
+    /// // Three pairs clear the dice.
+    /// let (mut turn, _) = TurnEngine::start_with_rules(&mut rng, rules);
+    /// let events = turn.respond(PlayerAction::Bank, &mut rng);
+    /// assert!(matches!(events[..], [Event::Kept { .. }, Event::HotDice, Event::Rolled { .. }]));
+    /// assert!(matches!(turn.pending(), Pending::Keep(dice) if dice.len() == 6));
+    /// ```
+    ///
+    /// Keeping all the scoring dice keeps what scores under the turn's
+    /// rules, not the usual ones.
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::replay::ReplayRoller;
+    /// use farkle::turn::{PlayerAction, TurnEngine};
+    /// use farkle::{KindScoring, ScoringRules};
+    ///
+    /// let rules = ScoringRules { kinds: KindScoring::Doubling, ..ScoringRules::default() };
+    /// let mut roller = ReplayRoller::new([vec![2, 4, 2, 2, 4, 2]]).unwrap();
+    /// let (mut turn, _) = TurnEngine::start_with_rules(&mut roller, rules);
+    /// let events = turn.respond(PlayerAction::KeepAllScoring, &mut roller);
+    /// // Four 2s score twice three 2s, not the usual 2000.
+    /// assert!(matches!(&events[..], [Event::Kept { dice, .. }] if dice == &vec![2, 2, 2, 2]));
+    /// assert_eq!(400, turn.snapshot().score);
+    /// ```
+    ///
+    /// Keeping and banking in one step does both or neither.
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::turn::{PlayerAction, TurnEngine};
+    ///
+    /// let mut rng = farkle::daily::round_rng(1, 0);
+    /// let (mut turn, _) = TurnEngine::start(&mut rng);
+    /// let before = turn.clone();
+    /// // Dice are [4, 3, 6, 1, 2, 4].
+    /// let events = turn.respond(PlayerAction::KeepAndBank(Box::new(PlayerAction::Keep(vec![6]))), &mut rng);
+    /// assert!(matches!(events[..], [Event::Rejected { .. }]));
+    /// assert_eq!(before, turn);
+    /// let events = turn.respond(PlayerAction::KeepAndBank(Box::new(PlayerAction::KeepValues(vec![1]))), &mut rng);
+    /// assert!(matches!(events[..], [Event::Kept { .. }, Event::Banked { score: 100 }]));
+    /// assert_eq!(Some(100), turn.score());
+    /// ```
+    pub fn respond<R: Rng>(&mut self, action: PlayerAction, rng: &mut R) -> Vec<Event> {
+        let events = self.apply(action, rng);
+        debug_assert_eq!(Ok(()), self.validate_state());
+        events
+    }
+
+    /// Make the pending choice, without checking the turn after.
+    fn apply<R: Rng>(&mut self, action: PlayerAction, rng: &mut R) -> Vec<Event> {
+        let dice = match &self.pending {
+            Pending::Keep(dice) => dice.clone(),
+            // Only banking banks. Anything else, like a keep sent again,
+            // leaves the choice pending.
+            Pending::Bank => {
+                let message = match action {
+                    PlayerAction::RollAgain => return self.roll(rng),
+                    PlayerAction::Bank => return self.bank(),
+                    PlayerAction::FreeRoll => {
+                        "A free roll rolls dice instead of keeping them, so roll first.".to_string()
+                    }
+                    PlayerAction::Keep(_)
+                    | PlayerAction::KeepValues(_)
+                    | PlayerAction::KeepAllScoring
+                    | PlayerAction::KeepAndBank(_) => "The dice are kept already, so bank or roll again.".to_string(),
+                    action => format!("{:?} isn't a choice now, so bank or roll again.", action),
+                };
+                return vec![Event::Rejected { message }];
+            }
+            Pending::Over(_) => return Vec::new(),
+        };
+        let (action, then_bank) = match action {
+            PlayerAction::KeepAndBank(keep) => match *keep {
+                keep @ (PlayerAction::Keep(_) | PlayerAction::KeepValues(_) | PlayerAction::KeepAllScoring) => {
+                    (keep, true)
+                }
+                keep => return vec![Event::Rejected { message: format!("{:?} doesn't say which dice to keep.", keep) }],
+            },
+            action => (action, false),
+        };
+        let choice = match action {
+            PlayerAction::Keep(indices) => check_indices(&dice, &indices).map(|_| (indices, then_bank)),
+            PlayerAction::KeepValues(values) => positions_of(&dice, &values)
+                .map(|indices| (indices, then_bank))
+                .map_err(|error| error.to_string()),
+            PlayerAction::KeepAllScoring => Ok((self.rules.scoring_indices(&dice), then_bank)),
+            PlayerAction::Bank => Ok((self.rules.scoring_indices(&dice), true)),
+            PlayerAction::FreeRoll if self.rules.free_rolls.is_none() => {
+                Err("Free rolls aren't part of these rules.".to_string())
+            }
+            PlayerAction::FreeRoll if self.free_rolls == 0 => Err("There are no free rolls left.".to_string()),
+            PlayerAction::FreeRoll => {
+                self.free_rolls -= 1;
+                let mut events = vec![Event::FreeRollUsed { held: self.free_rolls }];
+                events.extend(self.roll(rng));
+                return events;
+            }
+            action => Err(format!("{:?} doesn't say which dice to keep.", action)),
+        };
+        let (indices, bank) = match choice {
+            Ok(choice) => choice,
+            Err(message) => return vec![Event::Rejected { message }],
+        };
+        let keepers = keep_dice(&dice, &indices);
+        let breakdown = self.rules.breakdown(&keepers);
+        let score = breakdown.total();
+        // Dice that don't score can't ride along with ones that do, or
+        // keeping the whole roll would always clear the dice.
+        if score > 0 && breakdown.scoring_dice().len() < keepers.len() {
+            let message = format!(
+                "{:?} don't all score. Keep only scoring dice, like {:?}.",
+                keepers,
+                breakdown.scoring_dice()
+            );
+            return vec![Event::Rejected { message }];
+        }
+        // Only a roll with nothing to keep farkles. Keeping dice that don't
+        // score from one that does is a mistake, so it's asked again.
+        if score == 0 && self.rules.score(&dice) > 0 {
+            let message = format!(
+                "{:?} don't score. Keep scoring dice, like {:?}.",
+                keepers,
+                keep_dice(&dice, &self.rules.scoring_indices(&dice))
+            );
+            return vec![Event::Rejected { message }];
+        }
+        let held = self.rules.earn_free_roll(self.free_rolls, &breakdown);
+        let mut events = vec![Event::Kept { dice: keepers.clone(), breakdown }];
+
+        // No keepers or score == end of turn.
+        if keepers.is_empty() || score == 0 {
+            self.state.farkle(dice, keepers);
+            self.pending = Pending::Over(0);
+            events.push(Event::Farkled);
+            return events;
+        }
+        if held > self.free_rolls {
+            self.free_rolls = held;
+            events.push(Event::FreeRollEarned { held });
+        }
+        if self.state.record(dice, keepers, score) {
+            events.push(Event::HotDice);
+            match self.rules.hot_streak_bonus {
+                Some(bonus) if self.state.clears() > 1 => {
+                    self.state.add_bonus(bonus);
+                    events.push(Event::HotStreak { clears: self.state.clears(), bonus });
+                }
+                _ => {}
+            }
+            if self.rules.must_roll_hot_dice {
+                events.extend(self.roll(rng));
+                return events;
+            }
+        }
+        if bank {
+            events.extend(self.bank());
+        } else {
+            self.pending = Pending::Bank;
+        }
+        events
+    }
+
+    /// Make the pending choice like `respond`, for a frontend that saw
+    /// roll roll_number.
+    ///
+    /// A response meant for an earlier roll is rejected, so a late or
+    /// repeated message can't keep dice off the wrong roll.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::event::Event;
+    /// use farkle::turn::{Pending, PlayerAction, TurnEngine};
+    ///
+    /// let mut rng = farkle::daily::round_rng(1, 0);
+    /// let (mut turn, _) = TurnEngine::start(&mut rng);
+    /// // Dice are [4, 3, 6, 1, 2, 4].
+    /// for indices in [vec![6], vec![3, 3]] {
+    ///     let events = turn.respond_to(1, PlayerAction::Keep(indices), &mut rng);
+    ///     assert!(matches!(events[..], [Event::Rejected { .. }]));
+    /// }
+    /// turn.respond_to(1, PlayerAction::Keep(vec![3]), &mut rng);
+    ///
+    /// // The keep replayed before the choice to bank is rejected too, rather
+    /// // than banking the turn.
+    /// let events = turn.respond_to(1, PlayerAction::Keep(vec![3]), &mut rng);
+    /// assert!(matches!(events[..], [Event::Rejected { .. }]));
+    /// assert_eq!(&Pending::Bank, turn.pending());
+    /// assert_eq!(None, turn.score());
+    ///
+    /// turn.respond_to(1, PlayerAction::RollAgain, &mut rng);
+    /// assert_eq!(2, turn.roll_number());
+    ///
+    /// // The same keep sent again is for the roll before.
+    /// let events = turn.respond_to(1, PlayerAction::Keep(vec![3]), &mut rng);
+    /// assert!(matches!(events[..], [Event::Rejected { .. }]));
+    /// assert!(matches!(turn.pending(), Pending::Keep(_)));
+    /// ```
+    pub fn respond_to<R: Rng>(&mut self, roll_number: u32, action: PlayerAction, rng: &mut R) -> Vec<Event> {
+        if roll_number != self.rolls {
+            let message = format!("That was for roll {}, but this is roll {}.", roll_number, self.rolls);
+            return vec![Event::Rejected { message }];
+        }
+        self.respond(action, rng)
+    }
+
+    /// Check the turn adds up: every die kept was rolled, kept and left
+    /// dice make up each roll, and the scores match the dice kept.
+    ///
+    /// Returns the first problem found. A problem means a bug in the engine,
+    /// not a bad choice by a player.
+    ///
+    /// ### Examples
+    /// ```
+    /// use farkle::turn::{PlayerAction, TurnEngine};
+    ///
+    /// let mut rng = farkle::daily::round_rng(1, 0);
+    /// let (mut turn, _) = TurnEngine::start(&mut rng);
+    /// turn.respond(PlayerAction::Keep(vec![3]), &mut rng);
+    /// turn.respond(PlayerAction::RollAgain, &mut rng);
+    /// assert_eq!(Ok(()), turn.validate_state());
+    /// ```
+    pub fn validate_state(&self) -> Result<(), String> {
+        let mut num_dice = TOTAL_DICE as usize;
+        let mut score = 0u32;
+        for (i, roll) in self.state.history.iter().enumerate() {
+            let number = i + 1;
+            if roll.dice.len() != num_dice {
+                let rolled = roll.dice.len();
+                return Err(format!("Roll {} had {} dice, but {} were left to roll.", number, rolled, num_dice));
+            }
+            if let Some(die) = roll.dice.iter().find(|die| !(1..=6).contains(*die)) {
+                return Err(format!("Roll {} has a die showing {}.", number, die));
+            }
+            if positions_of(&roll.dice, &roll.kept).is_err() {
+                return Err(format!("Roll {} kept {:?}, which aren't all in {:?}.", number, roll.kept, roll.dice));
+            }
+            let kept = self.rules.breakdown(&roll.kept);
+            if roll.score != kept.total() {
+                let kept_score = kept.total();
+                return Err(format!("Roll {} scored {}, but {:?} scores {}.", number, roll.score, roll.kept, kept_score));
+            }
+            if kept.total() > 0 && kept.scoring_dice().len() != roll.kept.len() {
+                return Err(format!("Roll {} kept {:?}, which don't all score.", number, roll.kept));
+            }
+            if self.state.farkled && number == self.state.history.len() {
+                break;
+            }
+            num_dice -= roll.kept.len();
+            if num_dice == 0 {
+                num_dice = TOTAL_DICE as usize;
+            }
+            score = score.saturating_add(roll.points());
+        }
+        if self.state.farkled {
+            score = 0;
+        }
+        if num_dice != self.state.num_dice as usize {
+            return Err(format!("{} dice are left to roll, but the rolls leave {}.", self.state.num_dice, num_dice));
+        }
+        if score != self.state.score {
+            return Err(format!("The turn score is {}, but the rolls add up to {}.", self.state.score, score));
+        }
+        match &self.pending {
+            Pending::Keep(dice) if dice.len() != num_dice => Err(format!(
+                "The roll waiting on a keep has {} dice, but {} were left to roll.",
+                dice.len(),
+                num_dice
+            )),
+            Pending::Keep(dice) if dice.iter().any(|die| !(1..=6).contains(die)) => {
+                Err(format!("The roll waiting on a keep has a die that isn't 1 to 6: {:?}.", dice))
+            }
+            Pending::Over(banked) if *banked != 0 && *banked != score => {
+                Err(format!("The turn banked {}, but the rolls add up to {}.", banked, score))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// End the turn with the score so far.
+    fn bank(&mut self) -> Vec<Event> {
+        self.pending = Pending::Over(self.state.score());
+        vec![Event::Banked { score: self.state.score() }]
+    }
+}
+
+/// Returns the score for a turn, with decider making the choices.
+///
+/// Roll in a loop until the decider banks or the dice kept don't score.
+/// Dice are rolled with rng.
+///
+/// ### Examples
+/// ```
+/// use farkle::turn::{Decider, PlayerAction, TurnSnapshot};
+///
+/// // Keep every scoring die and bank after the first roll.
+/// struct Cautious;
+/// impl Decider for Cautious {
+///     fn choose_keep(&mut self, _dice: &[i16], _turn: &TurnSnapshot) -> PlayerAction {
+///         PlayerAction::KeepAllScoring
+///     }
+///     fn choose_bank(&mut self, _turn: &TurnSnapshot) -> PlayerAction {
+///         PlayerAction::Bank
+///     }
+/// }
+///
+/// let mut rng = farkle::daily::round_rng(1, 0);
+/// assert_eq!(100, farkle::turn::play_round(&mut rng, &mut Cautious));
+/// ```
+pub fn play_round<R: Rng, D: Decider + ?Sized>(rng: &mut R, decider: &mut D) -> u32 {
+    play_round_with_rules(rng, ScoringRules::default(), decider)
+}
+
+/// Returns the score for a turn played by rules, with decider making the
+/// choices.
+///
+/// ### Examples
+/// ```
+/// use farkle::replay::ReplayRoller;
+/// use farkle::turn::{play_round_with_rules, Decider, PlayerAction, TurnSnapshot};
+/// use farkle::{KindScoring, ScoringRules};
+///
+/// struct Cautious;
+/// impl Decider for Cautious {
+///     fn choose_keep(&mut self, _dice: &[i16], _turn: &TurnSnapshot) -> PlayerAction {
+///         PlayerAction::KeepAllScoring
+///     }
+///     fn choose_bank(&mut self, _turn: &TurnSnapshot) -> PlayerAction {
+///         PlayerAction::Bank
+///     }
+/// }
+///
+/// let rules = ScoringRules { kinds: KindScoring::Doubling, ..ScoringRules::default() };
+/// let mut rng = ReplayRoller::new([vec![3, 3, 3, 3, 6, 2]]).unwrap();
+/// assert_eq!(600, play_round_with_rules(&mut rng, rules, &mut Cautious));
+/// ```
+pub fn play_round_with_rules<R: Rng, D: Decider + ?Sized>(rng: &mut R, rules: ScoringRules, decider: &mut D) -> u32 {
+    let (mut turn, events) = TurnEngine::start_with_rules(rng, rules);
+    tell(decider, &events);
+    play_turn(&mut turn, rng, decider)
+}
+
+/// Tell decider what happened.
+fn tell<D: Decider + ?Sized>(decider: &mut D, events: &[Event]) {
+    for event in events {
+        decider.on_event(event);
+    }
+}
+
+/// Returns the score for turn once it's over, with decider making the
+/// choices left.
+pub fn play_turn<R: Rng, D: Decider + ?Sized>(turn: &mut TurnEngine, rng: &mut R, decider: &mut D) -> u32 {
+    loop {
+        let action = match turn.pending() {
+            Pending::Keep(dice) => decider.choose_keep(dice, &turn.snapshot()),
+            Pending::Bank => decider.choose_bank(&turn.snapshot()),
+            Pending::Over(score) => return *score,
+        };
+        pass_on_remark(decider);
+        let events = turn.respond(action, rng);
+        tell(decider, &events);
+    }
+}
@@ -0,0 +1,128 @@
+//! The command line game run the way players run it, with what they type
+//! scripted on stdin.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::process::{self, Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Runs of the game so far, so each gets a directory of its own.
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns how farkle run with args went, typing stdin.
+///
+/// Each run is in an empty directory with its own data directory, so what
+/// one saves can't change another.
+fn farkle(args: &[&str], stdin: &str) -> Output {
+    let run = RUNS.fetch_add(1, Ordering::SeqCst);
+    let dir = env::temp_dir().join(format!("farkle-cli-{}-{}", process::id(), run));
+    fs::create_dir_all(&dir).unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_farkle"))
+        .args(args)
+        .current_dir(&dir)
+        .env("FARKLE_DATA_DIR", dir.join("data"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    output
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn seeded_games_roll_the_same_dice() {
+    let args = ["--player", "Ann", "--seed", "9"];
+    let first = farkle(&args, "a\ny\n");
+    assert!(first.status.success(), "{}", stderr(&first));
+    let text = stdout(&first);
+    assert!(text.contains("Welcome to Farkle!"), "{}", text);
+    assert!(text.contains("Dice: [4, 1, 6, 5, 6, 3]"), "{}", text);
+    assert!(text.contains("Banking 150 points."), "{}", text);
+    assert!(text.contains("Thanks for playing!"), "{}", text);
+    assert_eq!(text, stdout(&farkle(&args, "a\ny\n")));
+}
+
+#[test]
+fn seeded_games_take_keeps_typed_as_positions() {
+    // Keeping the 1 alone from [4, 1, 6, 5, 6, 3] leaves five dice.
+    let output = farkle(&["--player", "Ann", "--seed", "9"], "2\ny\n");
+    let text = stdout(&output);
+    assert!(text.contains("You kept: [1]"), "{}", text);
+    assert!(text.contains("Banking 100 points."), "{}", text);
+}
+
+#[test]
+fn score_shows_every_keep() {
+    let output = farkle(&["score", "1", "1", "3", "5", "5", "5"], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    let text = stdout(&output);
+    assert!(text.contains("Total: 700"), "{}", text);
+    assert!(text.contains("Every keep that scores:"), "{}", text);
+}
+
+#[test]
+fn score_takes_rule_flags_after_the_dice() {
+    let output = farkle(&["score", "1", "1", "1", "4", "--triples", "300,200,300,400,500,600"], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("Total: 300"), "{}", stdout(&output));
+}
+
+#[test]
+fn score_rejects_what_isnt_a_die() {
+    let output = farkle(&["score", "1", "x"], "");
+    assert_eq!(Some(1), output.status.code());
+    assert!(stderr(&output).contains("'x' isn't a die."), "{}", stderr(&output));
+}
+
+#[test]
+fn bad_flag_values_print_usage_instead_of_panicking() {
+    let cases: [(&[&str], &str); 9] = [
+        (&["--player", "Ann", "--clock", "inf"], "--clock needs a number of minutes"),
+        (&["--player", "Ann", "--clock", "1e300"], "--clock needs a number of minutes"),
+        (&["demo", "--pause", "inf"], "--pause needs a number of seconds"),
+        (&["correspondence", "game.txt", "--player", "Ann", "--player", "Bob", "--deadline", "inf"], "--deadline"),
+        (&["--player", "Ann", "--hot-streak-bonus", "lots"], "--hot-streak-bonus needs a number of points"),
+        (&["--player", "Ann", "--preset", "nope"], "'nope' isn't a preset."),
+        (&["--player", "Ann", "--confirm-over", "lots"], "--confirm-over needs a number of points"),
+        (&["--player", "Ann:1030"], "Ann's target of 1030 plays like 1050"),
+        (&["--player", "Ann", "--bot", "Ann"], "Two players are called Ann."),
+    ];
+    for (args, message) in cases {
+        let output = farkle(args, "");
+        assert_eq!(Some(1), output.status.code(), "{:?}: {}", args, stderr(&output));
+        assert!(stderr(&output).contains(message), "{:?}: {}", args, stderr(&output));
+    }
+}
+
+#[test]
+fn presets_change_how_dice_score() {
+    let classic = farkle(&["score", "2", "2", "2", "2", "--preset", "classic"], "");
+    assert!(stdout(&classic).contains("Total: 2000"), "{}", stdout(&classic));
+    let dix_mille = farkle(&["score", "2", "2", "2", "2", "--preset", "dix-mille"], "");
+    assert!(stdout(&dix_mille).contains("Total: 400"), "{}", stdout(&dix_mille));
+}
+
+#[test]
+fn presets_show_in_the_welcome() {
+    let output = farkle(&["--player", "Ann", "--seed", "9", "--preset", "marathon"], "");
+    assert!(stdout(&output).contains("Reach 100,000 points to win!"), "{}", stdout(&output));
+}
+
+#[test]
+fn blind_games_keep_catch_up_bonuses_hidden() {
+    let output = farkle(&["--players", "3", "--humans", "0", "--blind", "--catch-up", "20", "--pause", "0", "--seed", "5"], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(!stdout(&output).contains("catch-up bonus of"), "{}", stdout(&output));
+}